@@ -1,5 +1,9 @@
 use lambda_http::Error;
 
+// Anyone could sign in as the dev member, never ship it
+#[cfg(feature = "dev-identity")]
+compile_error!("dev-identity must not be enabled for api-lambda");
+
 #[tokio::main]
 pub async fn main() -> Result<(), Error> {
     let config_key = std::env::var("CONFIG_KEY").expect("CONFIG_KEY must be set");
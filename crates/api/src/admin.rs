@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use axum::{http::StatusCode, Extension};
+use infra::InfraModule;
+use minibell::{
+    duty,
+    usecases::{self, UseCase},
+};
+use serde::Deserialize;
+use shaku::HasComponent;
+
+use crate::{
+    error::ApiError,
+    extract::{ApiJson, ApiPath},
+    AdminHeader,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct DutyCategoryJson {
+    name: String,
+    parent: Option<String>,
+    sort: i32,
+}
+
+/// Create or update a duty category
+pub async fn put_duty_category(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AdminHeader(access_type): AdminHeader,
+    ApiPath(category_id): ApiPath<String>,
+    ApiJson(json): ApiJson<DutyCategoryJson>,
+) -> Result<StatusCode, ApiError> {
+    use usecases::insert_duty_categories::*;
+
+    let insert_categories = InsertDutyCategories {
+        duty_repo: infra.as_ref().resolve_ref(),
+    };
+    let input = Input {
+        categories: vec![duty::DutyCategory {
+            id: category_id,
+            name: json.name,
+            parent: json.parent,
+            sort: json.sort,
+        }],
+    };
+    insert_categories.execute(&access_type, input).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DutyJson {
+    category: String,
+    name: String,
+    description: Option<String>,
+    short_name: Option<String>,
+    patch: f64,
+    image: String,
+    sort: i32,
+    #[serde(default)]
+    phrases: Vec<DutyPhraseJson>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DutyPhraseJson {
+    name: String,
+    progression: f64,
+}
+
+/// Create or update a duty, replacing its phrases
+pub async fn put_duty(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AdminHeader(access_type): AdminHeader,
+    ApiPath(duty_id): ApiPath<String>,
+    ApiJson(json): ApiJson<DutyJson>,
+) -> Result<StatusCode, ApiError> {
+    use usecases::insert_duties::*;
+
+    let insert_duties = InsertDuties {
+        duty_repo: infra.as_ref().resolve_ref(),
+    };
+    let duty = duty::Duty {
+        id: duty_id,
+        category: json.category,
+        name: json.name,
+        description: json.description,
+        short_name: json.short_name,
+        patch: json.patch,
+        image: json.image,
+        sort: json.sort,
+    };
+    let phrases = json
+        .phrases
+        .into_iter()
+        .map(|phrase| duty::DutyPhrase {
+            name: phrase.name,
+            progression: phrase.progression,
+        })
+        .collect();
+    insert_duties
+        .execute(
+            &access_type,
+            Input {
+                duties: vec![(duty, phrases)],
+            },
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn delete_duty(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AdminHeader(access_type): AdminHeader,
+    ApiPath(duty_id): ApiPath<String>,
+) -> Result<StatusCode, ApiError> {
+    use usecases::delete_duty::*;
+
+    let delete_duty = DeleteDuty {
+        duty_repo: infra.as_ref().resolve_ref(),
+    };
+    delete_duty.execute(&access_type, Input { duty_id }).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Cancel an event that is not over, even once started
+pub async fn cancel_event(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AdminHeader(access_type): AdminHeader,
+    ApiPath(event_id): ApiPath<String>,
+) -> Result<StatusCode, ApiError> {
+    use usecases::cancel_event::*;
+
+    let cancel_event = CancelEvent {
+        event_repo: infra.as_ref().resolve_ref(),
+        permission_service: infra.as_ref().resolve_ref(),
+    };
+    cancel_event
+        .execute(&access_type, Input { event_id })
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Finish a published event, even before it started
+pub async fn finish_event(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AdminHeader(access_type): AdminHeader,
+    ApiPath(event_id): ApiPath<String>,
+) -> Result<StatusCode, ApiError> {
+    use usecases::finish_event::*;
+
+    let finish_event = FinishEvent {
+        event_repo: infra.as_ref().resolve_ref(),
+    };
+    finish_event
+        .execute(&access_type, Input { event_id })
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Suspend the member and sign them out everywhere
+pub async fn suspend_member(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AdminHeader(access_type): AdminHeader,
+    ApiPath(member_id): ApiPath<u64>,
+) -> Result<StatusCode, ApiError> {
+    set_suspended(&infra, &access_type, member_id, true).await
+}
+
+pub async fn lift_suspension(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AdminHeader(access_type): AdminHeader,
+    ApiPath(member_id): ApiPath<u64>,
+) -> Result<StatusCode, ApiError> {
+    set_suspended(&infra, &access_type, member_id, false).await
+}
+
+async fn set_suspended(
+    infra: &InfraModule,
+    access_type: &minibell::AccessType,
+    member_id: u64,
+    suspended: bool,
+) -> Result<StatusCode, ApiError> {
+    use usecases::suspend_member::*;
+
+    let suspend = SuspendMember {
+        member_repo: infra.resolve_ref(),
+    };
+    suspend
+        .execute(
+            access_type,
+            Input {
+                member_id,
+                suspended,
+            },
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
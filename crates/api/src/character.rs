@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+use infra::InfraModule;
+use minibell::{
+    character::Character,
+    usecases::{self, UseCase},
+};
+use serde::{Deserialize, Serialize};
+use shaku::HasComponent;
+
+use crate::{error::ApiError, extract::ApiJson, AccessTypeHeader};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterJson {
+    name: String,
+    world_id: String,
+}
+
+impl From<Character> for CharacterJson {
+    fn from(character: Character) -> Self {
+        Self {
+            name: character.name,
+            world_id: character.world_id,
+        }
+    }
+}
+
+pub async fn get_character(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+) -> Result<Json<CharacterJson>, ApiError> {
+    use usecases::get_character::*;
+
+    let get_character = GetCharacter {
+        character_repo: infra.as_ref().resolve_ref(),
+    };
+    let character = get_character.execute(&access_type, ()).await?;
+
+    Ok(Json(character.into()))
+}
+
+pub async fn set_character(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiJson(json): ApiJson<CharacterJson>,
+) -> Result<Json<CharacterJson>, ApiError> {
+    use usecases::set_character::*;
+
+    let set_character = SetCharacter {
+        character_repo: infra.as_ref().resolve_ref(),
+        world_repo: infra.as_ref().resolve_ref(),
+    };
+    let character = set_character
+        .execute(
+            &access_type,
+            Input {
+                name: json.name,
+                world_id: json.world_id,
+            },
+        )
+        .await?;
+
+    Ok(Json(character.into()))
+}
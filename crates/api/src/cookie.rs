@@ -0,0 +1,147 @@
+use axum::http::{
+    header::{self, HeaderMap, HeaderValue},
+    Method, StatusCode,
+};
+use minibell::member::SESSION_MAX_LIFETIME_DAYS;
+use rand::{distributions::Alphanumeric, Rng};
+
+/// Header echoing the CSRF cookie on state changing requests
+const CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// Cookie sessions, enabled by setting SESSION_COOKIE_NAME
+/// Bearer tokens keep working for bots and the CLI
+#[derive(Debug, Clone)]
+pub struct CookieConfig {
+    pub name: String,
+    pub domain: Option<String>,
+}
+
+impl CookieConfig {
+    pub fn from_env() -> Option<Self> {
+        Some(Self {
+            name: std::env::var("SESSION_COOKIE_NAME").ok()?,
+            domain: std::env::var("SESSION_COOKIE_DOMAIN").ok(),
+        })
+    }
+
+    /// Double submit cookie, readable by the frontend to copy it in the header
+    fn csrf_name(&self) -> String {
+        format!("{}_csrf", self.name)
+    }
+
+    fn cookie(&self, name: &str, value: &str, max_age: i64, http_only: bool) -> HeaderValue {
+        let mut cookie = format!(
+            "{}={}; Path=/; Max-Age={}; Secure; SameSite=Lax",
+            name, value, max_age
+        );
+        if http_only {
+            cookie.push_str("; HttpOnly");
+        }
+        if let Some(domain) = &self.domain {
+            cookie.push_str(&format!("; Domain={}", domain));
+        }
+
+        HeaderValue::from_str(&cookie).expect("Cookie must be a valid header value")
+    }
+
+    /// Set the session and CSRF cookies, return the CSRF token
+    pub fn sign_in(&self, headers: &mut HeaderMap, token: &str) -> String {
+        let csrf_token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+        let max_age = SESSION_MAX_LIFETIME_DAYS * 24 * 60 * 60;
+
+        headers.append(
+            header::SET_COOKIE,
+            self.cookie(&self.name, token, max_age, true),
+        );
+        headers.append(
+            header::SET_COOKIE,
+            self.cookie(&self.csrf_name(), &csrf_token, max_age, false),
+        );
+
+        csrf_token
+    }
+
+    pub fn sign_out(&self, headers: &mut HeaderMap) {
+        headers.append(header::SET_COOKIE, self.cookie(&self.name, "", 0, true));
+        headers.append(
+            header::SET_COOKIE,
+            self.cookie(&self.csrf_name(), "", 0, false),
+        );
+    }
+
+    /// Session token from the cookie
+    /// State changing requests must echo the CSRF cookie in the header
+    pub fn session_token<'a>(
+        &self,
+        method: &Method,
+        headers: &'a HeaderMap,
+    ) -> Option<Result<&'a str, StatusCode>> {
+        let token = get(headers, &self.name)?;
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            return Some(Ok(token));
+        }
+
+        let csrf_cookie = get(headers, &self.csrf_name());
+        let csrf_header = headers
+            .get(CSRF_HEADER)
+            .and_then(|value| value.to_str().ok());
+        match (csrf_cookie, csrf_header) {
+            (Some(cookie), Some(header)) if !cookie.is_empty() && cookie == header => {
+                Some(Ok(token))
+            }
+            _ => Some(Err(StatusCode::FORBIDDEN)),
+        }
+    }
+}
+
+/// Value of the named cookie in the request
+fn get<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+
+    use super::CookieConfig;
+
+    #[test]
+    fn csrf() {
+        let config = CookieConfig {
+            name: "session".to_string(),
+            domain: None,
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("session=token; session_csrf=csrf"),
+        );
+
+        assert_eq!(
+            config.session_token(&Method::GET, &headers),
+            Some(Ok("token"))
+        );
+        assert_eq!(
+            config.session_token(&Method::POST, &headers),
+            Some(Err(StatusCode::FORBIDDEN))
+        );
+
+        headers.insert("X-CSRF-Token", HeaderValue::from_static("csrf"));
+        assert_eq!(
+            config.session_token(&Method::POST, &headers),
+            Some(Ok("token"))
+        );
+    }
+}
@@ -0,0 +1,459 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Extension, Json,
+};
+use infra::InfraModule;
+use minibell::{
+    event::{Event, SignUpStatus},
+    member::{DiscordClient, MemberRepository},
+    reconfirmation::ReconfirmationStatus,
+    usecases::{self, UseCase},
+    AccessType, Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use serde_with::{serde_as, DisplayFromStr};
+use shaku::HasComponent;
+
+const PING: u8 = 1;
+const APPLICATION_COMMAND: u8 = 2;
+const MESSAGE_COMPONENT: u8 = 3;
+
+const PONG: u8 = 1;
+const CHANNEL_MESSAGE_WITH_SOURCE: u8 = 4;
+
+/// Only visible to the member who used the interaction
+const EPHEMERAL: u64 = 1 << 6;
+
+#[derive(Debug, Deserialize)]
+struct Interaction {
+    #[serde(rename = "type")]
+    kind: u8,
+    data: Option<InteractionData>,
+    /// Set when the interaction comes from a guild
+    member: Option<InteractionMember>,
+    /// Set when the interaction comes from a DM
+    user: Option<InteractionUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InteractionData {
+    /// Command name, for application command
+    name: Option<String>,
+    #[serde(default)]
+    options: Vec<CommandOption>,
+    /// Component id, for message component
+    custom_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommandOption {
+    name: String,
+    value: Option<Value>,
+    #[serde(default)]
+    options: Vec<CommandOption>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InteractionMember {
+    user: InteractionUser,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+struct InteractionUser {
+    #[serde_as(as = "DisplayFromStr")]
+    id: u64,
+}
+
+impl Interaction {
+    /// Discord user who sent the interaction
+    fn user_id(&self) -> Option<u64> {
+        match (&self.member, &self.user) {
+            (Some(member), _) => Some(member.user.id),
+            (None, Some(user)) => Some(user.id),
+            (None, None) => None,
+        }
+    }
+
+    /// Interactions are run as the discord user who sent them, if they are an active member
+    /// Users who never signed in or left the guild are guests, suspended members are forbidden
+    async fn access_type(&self, member_repo: &dyn MemberRepository) -> Result<AccessType, Error> {
+        let Some(user_id) = self.user_id() else {
+            return Ok(AccessType::Guest);
+        };
+
+        match member_repo.get_member(user_id).await {
+            Ok(member) if member.is_suspended() => Err(Error::Forbidden),
+            Ok(member) if member.is_active() => Ok(AccessType::Member(member.id)),
+            Ok(_) | Err(Error::ItemNotFound) => Ok(AccessType::Guest),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct InteractionResponse {
+    #[serde(rename = "type")]
+    kind: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+impl InteractionResponse {
+    fn pong() -> Self {
+        Self {
+            kind: PONG,
+            data: None,
+        }
+    }
+
+    fn ephemeral(content: impl ToString) -> Self {
+        Self {
+            kind: CHANNEL_MESSAGE_WITH_SOURCE,
+            data: Some(json!({
+                "content": content.to_string(),
+                "flags": EPHEMERAL,
+            })),
+        }
+    }
+}
+
+/// Event action, from `/event` slash commands or buttons on event messages
+#[derive(Debug, PartialEq)]
+enum EventAction {
+    List,
+    Show {
+        event_id: String,
+    },
+    Join {
+        event_id: String,
+        job: String,
+    },
+    Leave {
+        event_id: String,
+    },
+    /// Answer a reconfirmation after a reschedule
+    Reconfirm {
+        event_id: String,
+        confirm: bool,
+    },
+}
+
+impl EventAction {
+    /// Parse `/event <subcommand>` options
+    fn from_command(options: &[CommandOption]) -> Option<Self> {
+        let subcommand = options.first()?;
+        let get = |name: &str| {
+            subcommand
+                .options
+                .iter()
+                .find(|option| option.name == name)
+                .and_then(|option| option.value.as_ref())
+                .and_then(|value| value.as_str())
+                .map(|value| value.to_string())
+        };
+
+        match subcommand.name.as_str() {
+            "list" => Some(Self::List),
+            "show" => Some(Self::Show {
+                event_id: get("event")?,
+            }),
+            "join" => Some(Self::Join {
+                event_id: get("event")?,
+                job: get("job")?,
+            }),
+            "leave" => Some(Self::Leave {
+                event_id: get("event")?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Parse button custom id, formatted as `event:<action>:<event_id>[:<job>]`
+    fn from_custom_id(custom_id: &str) -> Option<Self> {
+        let parts = custom_id.split(':').collect::<Vec<_>>();
+        match parts.as_slice() {
+            ["event", "show", event_id] => Some(Self::Show {
+                event_id: event_id.to_string(),
+            }),
+            ["event", "join", event_id, job] => Some(Self::Join {
+                event_id: event_id.to_string(),
+                job: job.to_string(),
+            }),
+            ["event", "leave", event_id] => Some(Self::Leave {
+                event_id: event_id.to_string(),
+            }),
+            ["event", "confirm", event_id] => Some(Self::Reconfirm {
+                event_id: event_id.to_string(),
+                confirm: true,
+            }),
+            ["event", "drop", event_id] => Some(Self::Reconfirm {
+                event_id: event_id.to_string(),
+                confirm: false,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Events listed by `/event list`, the message would get too long with more
+const LIST_LIMIT: usize = 10;
+
+/// One line per event, with its id to use in the other commands
+fn event_list(events: &[Event]) -> String {
+    if events.is_empty() {
+        return "No event is open for sign up.".to_string();
+    }
+
+    events
+        .iter()
+        .take(LIST_LIMIT)
+        .map(|event| {
+            format!(
+                "**{}** <t:{}:F>, {}/{} signed up, `{}`",
+                event.info.title,
+                event.schedule.start_at.timestamp(),
+                event.roster().len(),
+                event.slots.len(),
+                event.id
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn event_detail(event: &Event) -> String {
+    let mut lines = vec![format!(
+        "**{}** <t:{}:F>, {}/{} signed up",
+        event.info.title,
+        event.schedule.start_at.timestamp(),
+        event.roster().len(),
+        event.slots.len()
+    )];
+    if let Some(description) = &event.info.description {
+        lines.push(description.clone());
+    }
+    if !event.roster().is_empty() {
+        let roster = event
+            .roster()
+            .iter()
+            .map(|member_id| format!("<@{}>", member_id))
+            .collect::<Vec<_>>();
+        lines.push(format!("Roster: {}", roster.join(", ")));
+    }
+
+    lines.join("\n")
+}
+
+/// Explain why the action failed, in the words of the web UI
+fn error_message(error: &Error) -> String {
+    match error {
+        Error::ItemNotFound => "This event does not exist.".to_string(),
+        Error::Unauthenticated => "Sign in on the website first.".to_string(),
+        Error::Forbidden => "You are not allowed to do that.".to_string(),
+        Error::InvalidEventStatus => "The event is not open for sign up.".to_string(),
+        Error::Validation(errors) => errors
+            .iter()
+            .map(|error| error.message.clone())
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => "Something went wrong.".to_string(),
+    }
+}
+
+/// Run the event action with the same access as the web UI
+async fn run_event_action(
+    infra: &InfraModule,
+    access_type: &AccessType,
+    action: EventAction,
+) -> InteractionResponse {
+    let content = match action {
+        EventAction::List => {
+            use usecases::get_events::*;
+
+            let get_events = GetEvents {
+                event_repo: infra.resolve_ref(),
+                world_repo: infra.resolve_ref(),
+            };
+            get_events
+                .execute(access_type, Input::default())
+                .await
+                .map(|events| event_list(&events))
+        }
+        EventAction::Show { event_id } => {
+            use usecases::get_event::*;
+
+            let get_event = GetEvent {
+                event_repo: infra.resolve_ref(),
+            };
+            get_event
+                .execute(access_type, Input { event_id })
+                .await
+                .map(|event| event_detail(&event))
+        }
+        EventAction::Join { event_id, job } => {
+            use usecases::join_event::*;
+
+            let join_event = JoinEvent {
+                member_repo: infra.resolve_ref(),
+                event_repo: infra.resolve_ref(),
+                character_repo: infra.resolve_ref(),
+                world_repo: infra.resolve_ref(),
+            };
+            join_event
+                .execute(access_type, Input { event_id, job })
+                .await
+                .map(|status| match status {
+                    SignUpStatus::Accepted { .. } => "You joined the roster.".to_string(),
+                    SignUpStatus::Waitlisted => {
+                        "The roster is full for this job, you are on the waitlist.".to_string()
+                    }
+                })
+        }
+        EventAction::Leave { event_id } => {
+            use usecases::leave_event::*;
+
+            let leave_event = LeaveEvent {
+                event_repo: infra.resolve_ref(),
+            };
+            leave_event
+                .execute(access_type, Input { event_id })
+                .await
+                .map(|()| "You left the event.".to_string())
+        }
+        EventAction::Reconfirm { event_id, confirm } => {
+            use usecases::respond_reconfirmation::*;
+
+            let respond = RespondReconfirmation {
+                reconfirmation_repo: infra.resolve_ref(),
+                event_repo: infra.resolve_ref(),
+            };
+            match respond
+                .execute(access_type, Input { event_id, confirm })
+                .await
+            {
+                Ok(ReconfirmationStatus::Confirmed) => Ok("Thanks, your slot is kept.".to_string()),
+                Ok(ReconfirmationStatus::Dropped) => Ok("You left the roster.".to_string()),
+                Ok(ReconfirmationStatus::Expired) => Ok(
+                    "The deadline has passed, your slot was opened to the waitlist.".to_string(),
+                ),
+                Ok(ReconfirmationStatus::Pending) | Err(Error::ItemNotFound) => {
+                    Ok("Nothing to confirm for this event.".to_string())
+                }
+                Err(e) => Err(e),
+            }
+        }
+    };
+
+    match content {
+        Ok(content) => InteractionResponse::ephemeral(content),
+        Err(e) => InteractionResponse::ephemeral(error_message(&e)),
+    }
+}
+
+/// Discord interactions endpoint
+/// Discord requires every request to be verified, and rejected with 401 otherwise
+pub async fn interactions(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, StatusCode> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(StatusCode::UNAUTHORIZED)
+    };
+    let signature = header("X-Signature-Ed25519")?;
+    let timestamp = header("X-Signature-Timestamp")?;
+
+    let discord_client: &dyn DiscordClient = infra.as_ref().resolve_ref();
+    discord_client
+        .verify_interaction(signature, timestamp, &body)
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    let interaction =
+        serde_json::from_slice::<Interaction>(&body).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let action = match (interaction.kind, &interaction.data) {
+        (PING, _) => return Ok(Json(InteractionResponse::pong())),
+        (APPLICATION_COMMAND, Some(data)) if data.name.as_deref() == Some("event") => {
+            EventAction::from_command(&data.options)
+        }
+        (MESSAGE_COMPONENT, Some(data)) => data
+            .custom_id
+            .as_deref()
+            .and_then(EventAction::from_custom_id),
+        _ => None,
+    };
+
+    let Some(action) = action else {
+        return Ok(Json(InteractionResponse::ephemeral("Unknown command.")));
+    };
+    let response = match interaction.access_type(infra.as_ref().resolve_ref()).await {
+        Ok(access_type) => run_event_action(&infra, &access_type, action).await,
+        Err(e) => InteractionResponse::ephemeral(error_message(&e)),
+    };
+
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventAction, Interaction};
+
+    #[test]
+    fn parse_interactions() {
+        let command = serde_json::from_str::<Interaction>(
+            r#"{
+                "type": 2,
+                "member": { "user": { "id": "42" } },
+                "data": {
+                    "name": "event",
+                    "options": [{
+                        "name": "join",
+                        "options": [
+                            { "name": "event", "value": "abc" },
+                            { "name": "job", "value": "war" }
+                        ]
+                    }]
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(command.user_id(), Some(42));
+        assert_eq!(
+            EventAction::from_command(&command.data.unwrap().options),
+            Some(EventAction::Join {
+                event_id: "abc".to_string(),
+                job: "war".to_string(),
+            })
+        );
+
+        let button = serde_json::from_str::<Interaction>(
+            r#"{
+                "type": 3,
+                "user": { "id": "42" },
+                "data": { "custom_id": "event:drop:abc" }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(button.user_id(), Some(42));
+        assert_eq!(
+            button
+                .data
+                .and_then(|data| data.custom_id)
+                .as_deref()
+                .and_then(EventAction::from_custom_id),
+            Some(EventAction::Reconfirm {
+                event_id: "abc".to_string(),
+                confirm: false,
+            })
+        );
+        assert_eq!(EventAction::from_custom_id("event:join:abc"), None);
+    }
+}
@@ -1,10 +1,6 @@
 use std::sync::Arc;
 
-use axum::{
-    extract::{Path, Query},
-    response::IntoResponse,
-    Extension, Json,
-};
+use axum::{response::IntoResponse, Extension, Json};
 use infra::InfraModule;
 use minibell::{
     duty,
@@ -14,6 +10,11 @@ use minibell::{
 use serde::{Deserialize, Serialize};
 use shaku::HasComponent;
 
+use crate::{
+    error::ApiError,
+    extract::{ApiPath, ApiQuery},
+};
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct DutyCategoryDto {
@@ -95,8 +96,8 @@ pub struct GetDutiesQuery {
 
 pub async fn get_duties(
     Extension(infra): Extension<Arc<InfraModule>>,
-    Query(query): Query<GetDutiesQuery>,
-) -> impl IntoResponse {
+    ApiQuery(query): ApiQuery<GetDutiesQuery>,
+) -> Result<impl IntoResponse, ApiError> {
     use usecases::get_duties::*;
 
     let get_duties = GetDuties {
@@ -109,8 +110,7 @@ pub async fn get_duties(
                 category: query.category,
             },
         )
-        .await
-        .expect("Failed to get duties");
+        .await?;
 
     #[derive(Debug, Serialize)]
     #[serde(rename_all = "camelCase")]
@@ -122,26 +122,23 @@ pub async fn get_duties(
         duties: Vec<DutyDto>,
     }
 
-    Json(Response {
+    Ok(Json(Response {
         breadcrumbs: response.breadcrumbs.into_iter().map(From::from).collect(),
         categories: response.categories.into_iter().map(From::from).collect(),
         duties: response.duties.into_iter().map(From::from).collect(),
-    })
+    }))
 }
 
 pub async fn get_duty(
     Extension(infra): Extension<Arc<InfraModule>>,
-    Path(id): Path<String>,
-) -> impl IntoResponse {
+    ApiPath(id): ApiPath<String>,
+) -> Result<impl IntoResponse, ApiError> {
     use usecases::get_duty::*;
 
     let get_duty = GetDuty {
         duty_repo: infra.as_ref().resolve_ref(),
     };
-    let response = get_duty
-        .execute(&AccessType::Guest, &id)
-        .await
-        .expect("Failed to get duty");
+    let response = get_duty.execute(&AccessType::Guest, &id).await?;
 
     #[derive(Debug, Serialize)]
     #[serde(rename_all = "camelCase")]
@@ -150,8 +147,8 @@ pub async fn get_duty(
         duty: DutyDto,
     }
 
-    Json(Response {
+    Ok(Json(Response {
         breadcrumbs: response.breadcrumbs.into_iter().map(From::from).collect(),
         duty: (response.duty, response.phrases).into(),
-    })
+    }))
 }
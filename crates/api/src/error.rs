@@ -0,0 +1,188 @@
+use axum::{
+    extract::rejection::{JsonRejection, PathRejection, QueryRejection},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use minibell::{validation::FieldError, Error};
+use serde::Serialize;
+
+/// JSON error response, `{ "code": "...", "message": "..." }`
+/// The code is stable for clients, internal details are only logged
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+    /// Invalid fields of a validation error
+    fields: Vec<FieldError>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl ToString) -> Self {
+        Self {
+            status,
+            code,
+            message: message.to_string(),
+            fields: vec![],
+        }
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::ItemNotFound => Self::new(StatusCode::NOT_FOUND, "not_found", "Not found."),
+            Error::Conflict => Self::new(
+                StatusCode::CONFLICT,
+                "conflict",
+                "It was changed in the meantime, reload and try again.",
+            ),
+            Error::Unauthenticated => Self::new(
+                StatusCode::UNAUTHORIZED,
+                "unauthenticated",
+                "Sign in or provide an api key.",
+            ),
+            Error::InvalidToken => Self::new(
+                StatusCode::UNAUTHORIZED,
+                "invalid_token",
+                "The token is invalid or expired.",
+            ),
+            Error::Forbidden => Self::new(StatusCode::FORBIDDEN, "forbidden", "Forbidden."),
+            Error::InvalidEventStatus => Self::new(
+                StatusCode::CONFLICT,
+                "invalid_event_status",
+                "The event does not allow this anymore.",
+            ),
+            Error::InvalidOAuth2State => Self::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_state",
+                "The sign in request expired or was not started here, please try again.",
+            ),
+            Error::InvalidOAuth2Code => Self::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_code",
+                "The Discord authorization expired, please try again.",
+            ),
+            Error::NotGuildMember => Self::new(
+                StatusCode::FORBIDDEN,
+                "not_guild_member",
+                "Join the Discord server before signing in.",
+            ),
+            Error::MissingRequiredRole => Self::new(
+                StatusCode::FORBIDDEN,
+                "missing_required_role",
+                "Your Discord account does not have a role allowed to sign in.",
+            ),
+            Error::MemberSuspended => Self::new(
+                StatusCode::FORBIDDEN,
+                "member_suspended",
+                "Your account is suspended, contact a moderator.",
+            ),
+            Error::DiscordUnavailable => Self::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "discord_unavailable",
+                "Discord is unavailable, please try again later.",
+            ),
+            Error::Validation(fields) => Self {
+                fields,
+                ..Self::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "validation_failed",
+                    "Some fields are invalid.",
+                )
+            },
+            Error::Upstream(detail) => {
+                tracing::error!("Upstream error: {}", detail);
+                Self::new(
+                    StatusCode::BAD_GATEWAY,
+                    "upstream_failed",
+                    "A service we depend on failed, please try again later.",
+                )
+            }
+            Error::Internal(detail) => {
+                tracing::error!("Internal error: {}", detail);
+                Self::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal",
+                    "Something went wrong.",
+                )
+            }
+        }
+    }
+}
+
+/// Extractor rejections
+impl From<StatusCode> for ApiError {
+    fn from(value: StatusCode) -> Self {
+        match value {
+            StatusCode::UNAUTHORIZED => Error::Unauthenticated.into(),
+            StatusCode::FORBIDDEN => Error::Forbidden.into(),
+            _ if value.is_client_error() => Self::new(
+                value,
+                "bad_request",
+                value.canonical_reason().unwrap_or("Bad request."),
+            ),
+            _ => Self::new(
+                value,
+                "internal",
+                value.canonical_reason().unwrap_or("Something went wrong."),
+            ),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(value: JsonRejection) -> Self {
+        Self::new(value.status(), "invalid_body", value.body_text())
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(value: PathRejection) -> Self {
+        Self::new(value.status(), "invalid_path", value.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(value: QueryRejection) -> Self {
+        Self::new(value.status(), "invalid_query", value.body_text())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct Field {
+            path: String,
+            code: &'static str,
+            message: String,
+        }
+
+        #[derive(Serialize)]
+        struct Body {
+            code: &'static str,
+            message: String,
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            fields: Vec<Field>,
+        }
+
+        (
+            self.status,
+            Json(Body {
+                code: self.code,
+                message: self.message,
+                fields: self
+                    .fields
+                    .into_iter()
+                    .map(|field| Field {
+                        path: field.path,
+                        code: field.code,
+                        message: field.message,
+                    })
+                    .collect(),
+            }),
+        )
+            .into_response()
+    }
+}
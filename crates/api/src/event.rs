@@ -0,0 +1,482 @@
+use std::sync::Arc;
+
+use axum::{http::StatusCode, response::IntoResponse, Extension, Json};
+use chrono::{DateTime, Duration, Utc};
+use infra::InfraModule;
+use minibell::{
+    event::{
+        Event, EventDraft, EventInfo, EventSchedule, EventSlot, EventStatus, EventVisibility,
+        SignUpStatus,
+    },
+    usecases::{self, UseCase},
+    world::Location,
+};
+use serde::{Deserialize, Serialize};
+use shaku::HasComponent;
+
+use crate::{
+    error::ApiError,
+    extract::{ApiJson, ApiPath, ApiQuery},
+    AccessTypeHeader,
+};
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum EventStatusDto {
+    Draft,
+    Private,
+    Public,
+    InProcess,
+    Finished,
+    Cancelled,
+}
+
+impl From<EventStatus> for EventStatusDto {
+    fn from(status: EventStatus) -> Self {
+        match status {
+            EventStatus::Draft => EventStatusDto::Draft,
+            EventStatus::Private => EventStatusDto::Private,
+            EventStatus::Public => EventStatusDto::Public,
+            EventStatus::InProcess => EventStatusDto::InProcess,
+            EventStatus::Finished => EventStatusDto::Finished,
+            EventStatus::Cancelled => EventStatusDto::Cancelled,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "camelCase")]
+enum LocationDto {
+    DataCenter(String),
+    Region(String),
+}
+
+impl From<LocationDto> for Location {
+    fn from(location: LocationDto) -> Self {
+        match location {
+            LocationDto::DataCenter(id) => Location::DataCenter(id),
+            LocationDto::Region(id) => Location::Region(id),
+        }
+    }
+}
+
+impl From<Location> for LocationDto {
+    fn from(location: Location) -> Self {
+        match location {
+            Location::DataCenter(id) => LocationDto::DataCenter(id),
+            Location::Region(id) => LocationDto::Region(id),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignUpDto {
+    member_id: u64,
+    job: String,
+    world_id: String,
+    /// Slot of an accepted member, none when waitlisted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slot: Option<usize>,
+    signed_up_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EventDto {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    host: Option<u64>,
+    status: EventStatusDto,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duty_id: Option<String>,
+    start_at: DateTime<Utc>,
+    duration_minutes: i64,
+    slots: Vec<Vec<String>>,
+    location: LocationDto,
+    language: String,
+    travel_allowed: bool,
+    thread_enabled: bool,
+    sign_ups: Vec<SignUpDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    published_at: Option<DateTime<Utc>>,
+}
+
+impl From<Event> for EventDto {
+    fn from(event: Event) -> Self {
+        Self {
+            id: event.id,
+            host: event.host,
+            status: event.status.into(),
+            title: event.info.title,
+            description: event.info.description,
+            duty_id: event.duty_id,
+            start_at: event.schedule.start_at,
+            duration_minutes: event.schedule.duration.num_minutes(),
+            slots: event.slots.into_iter().map(|slot| slot.jobs).collect(),
+            location: event.location.into(),
+            language: event.language,
+            travel_allowed: event.travel_allowed,
+            thread_enabled: event.thread_enabled,
+            sign_ups: event
+                .sign_ups
+                .into_iter()
+                .map(|sign_up| SignUpDto {
+                    member_id: sign_up.member_id,
+                    job: sign_up.job,
+                    world_id: sign_up.world_id,
+                    slot: match sign_up.status {
+                        SignUpStatus::Accepted { slot } => Some(slot),
+                        SignUpStatus::Waitlisted => None,
+                    },
+                    signed_up_at: sign_up.signed_up_at,
+                })
+                .collect(),
+            published_at: event.published_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventInfoJson {
+    title: String,
+    description: Option<String>,
+}
+
+impl From<EventInfoJson> for EventInfo {
+    fn from(json: EventInfoJson) -> Self {
+        Self {
+            title: json.title,
+            description: json.description,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventScheduleJson {
+    start_at: DateTime<Utc>,
+    duration_minutes: i64,
+}
+
+impl From<EventScheduleJson> for EventSchedule {
+    fn from(json: EventScheduleJson) -> Self {
+        Self {
+            start_at: json.start_at,
+            duration: Duration::minutes(json.duration_minutes),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateEventJson {
+    #[serde(flatten)]
+    info: EventInfoJson,
+    duty_id: Option<String>,
+    #[serde(flatten)]
+    schedule: EventScheduleJson,
+    /// Accepted job ids of each slot, empty for any job
+    slots: Vec<Vec<String>>,
+    location: LocationDto,
+    language: String,
+    #[serde(default)]
+    travel_allowed: bool,
+    /// Open a discussion thread under the announcement
+    #[serde(default)]
+    thread_enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEventsQuery {
+    data_center: Option<String>,
+    language: Option<String>,
+}
+
+pub async fn get_events(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiQuery(query): ApiQuery<GetEventsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::get_events::*;
+
+    let get_events = GetEvents {
+        event_repo: infra.as_ref().resolve_ref(),
+        world_repo: infra.as_ref().resolve_ref(),
+    };
+    let events = get_events
+        .execute(
+            &access_type,
+            Input {
+                data_center: query.data_center,
+                language: query.language,
+            },
+        )
+        .await?;
+
+    Ok(Json(
+        events.into_iter().map(EventDto::from).collect::<Vec<_>>(),
+    ))
+}
+
+pub async fn create_event(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiJson(json): ApiJson<CreateEventJson>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::create_event::*;
+
+    let create_event = CreateEvent {
+        event_repo: infra.as_ref().resolve_ref(),
+        duty_repo: infra.as_ref().resolve_ref(),
+        world_repo: infra.as_ref().resolve_ref(),
+        permission_service: infra.as_ref().resolve_ref(),
+    };
+    let event = create_event
+        .execute(
+            &access_type,
+            EventDraft {
+                info: json.info.into(),
+                duty_id: json.duty_id,
+                schedule: json.schedule.into(),
+                slots: json
+                    .slots
+                    .into_iter()
+                    .map(|jobs| EventSlot { jobs })
+                    .collect(),
+                location: json.location.into(),
+                language: json.language,
+                travel_allowed: json.travel_allowed,
+                thread_enabled: json.thread_enabled,
+            },
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(EventDto::from(event))))
+}
+
+pub async fn get_event(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(event_id): ApiPath<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::get_event::*;
+
+    let get_event = GetEvent {
+        event_repo: infra.as_ref().resolve_ref(),
+    };
+    let event = get_event.execute(&access_type, Input { event_id }).await?;
+
+    Ok(Json(EventDto::from(event)))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum VisibilityJson {
+    Private,
+    Public,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishEventJson {
+    visibility: VisibilityJson,
+}
+
+pub async fn publish_event(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(event_id): ApiPath<String>,
+    ApiJson(json): ApiJson<PublishEventJson>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::publish_event::*;
+
+    let publish_event = PublishEvent {
+        event_repo: infra.as_ref().resolve_ref(),
+        permission_service: infra.as_ref().resolve_ref(),
+    };
+    let event = publish_event
+        .execute(
+            &access_type,
+            Input {
+                event_id,
+                visibility: match json.visibility {
+                    VisibilityJson::Private => EventVisibility::Private,
+                    VisibilityJson::Public => EventVisibility::Public,
+                },
+            },
+        )
+        .await?;
+
+    Ok(Json(EventDto::from(event)))
+}
+
+async fn edit_event(
+    infra: &InfraModule,
+    access_type: &minibell::AccessType,
+    event_id: String,
+    edit: usecases::edit_event::EventEdit,
+) -> Result<Json<EventDto>, ApiError> {
+    use usecases::edit_event::*;
+
+    let edit_event = EditEvent {
+        event_repo: infra.resolve_ref(),
+    };
+    let event = edit_event
+        .execute(access_type, Input { event_id, edit })
+        .await?;
+
+    Ok(Json(EventDto::from(event)))
+}
+
+pub async fn put_event_info(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(event_id): ApiPath<String>,
+    ApiJson(json): ApiJson<EventInfoJson>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::edit_event::EventEdit;
+
+    edit_event(&infra, &access_type, event_id, EventEdit::Info(json.into())).await
+}
+
+pub async fn put_event_schedule(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(event_id): ApiPath<String>,
+    ApiJson(json): ApiJson<EventScheduleJson>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::edit_event::EventEdit;
+
+    edit_event(
+        &infra,
+        &access_type,
+        event_id,
+        EventEdit::Schedule(json.into()),
+    )
+    .await
+}
+
+pub async fn cancel_event(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(event_id): ApiPath<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::cancel_event::*;
+
+    let cancel_event = CancelEvent {
+        event_repo: infra.as_ref().resolve_ref(),
+        permission_service: infra.as_ref().resolve_ref(),
+    };
+    let event = cancel_event
+        .execute(&access_type, Input { event_id })
+        .await?;
+
+    Ok(Json(EventDto::from(event)))
+}
+
+pub async fn start_event(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(event_id): ApiPath<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::start_event::*;
+
+    let start_event = StartEvent {
+        event_repo: infra.as_ref().resolve_ref(),
+    };
+    let event = start_event
+        .execute(&access_type, Input { event_id })
+        .await?;
+
+    Ok(Json(EventDto::from(event)))
+}
+
+pub async fn finish_event(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(event_id): ApiPath<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::finish_event::*;
+
+    let finish_event = FinishEvent {
+        event_repo: infra.as_ref().resolve_ref(),
+    };
+    let event = finish_event
+        .execute(&access_type, Input { event_id })
+        .await?;
+
+    Ok(Json(EventDto::from(event)))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JoinEventJson {
+    job: String,
+}
+
+pub async fn join_event(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(event_id): ApiPath<String>,
+    ApiJson(json): ApiJson<JoinEventJson>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::join_event::*;
+
+    let join_event = JoinEvent {
+        member_repo: infra.as_ref().resolve_ref(),
+        event_repo: infra.as_ref().resolve_ref(),
+        character_repo: infra.as_ref().resolve_ref(),
+        world_repo: infra.as_ref().resolve_ref(),
+    };
+    let status = join_event
+        .execute(
+            &access_type,
+            Input {
+                event_id,
+                job: json.job,
+            },
+        )
+        .await?;
+
+    #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Response {
+        accepted: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        slot: Option<usize>,
+    }
+
+    Ok(Json(match status {
+        SignUpStatus::Accepted { slot } => Response {
+            accepted: true,
+            slot: Some(slot),
+        },
+        SignUpStatus::Waitlisted => Response {
+            accepted: false,
+            slot: None,
+        },
+    }))
+}
+
+pub async fn leave_event(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(event_id): ApiPath<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::leave_event::*;
+
+    let leave_event = LeaveEvent {
+        event_repo: infra.as_ref().resolve_ref(),
+    };
+    leave_event
+        .execute(&access_type, Input { event_id })
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -2,21 +2,42 @@ use std::sync::Arc;
 
 use axum::{
     async_trait,
-    extract::{FromRequest, Query, Request},
-    http::{header, StatusCode},
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, StatusCode},
     response::IntoResponse,
-    routing::{get, post},
+    routing::{delete, get, post, put},
     Extension, Json, Router,
 };
 use infra::InfraModule;
 use minibell::{
-    usecases::{self, UseCase},
-    AccessType,
+    permission::{Permission, PermissionService},
+    usecases::{self, authorization::Authorized, UseCase},
+    AccessType, Error,
 };
 use serde::{Deserialize, Serialize};
 use shaku::HasComponent;
 
+use crate::{
+    cookie::CookieConfig,
+    error::ApiError,
+    extract::{ApiJson, ApiQuery},
+    rate_limit::{limit, RouteGroup},
+};
+
+mod admin;
+mod character;
+mod cookie;
+mod discord;
 mod duty;
+mod error;
+mod event;
+mod extract;
+mod notification;
+mod rate_limit;
+mod reconfirmation;
+mod reminder;
+mod session;
+mod world;
 
 async fn root() -> impl IntoResponse {
     #[derive(Debug, Clone, Serialize)]
@@ -36,14 +57,16 @@ struct GetAuthInfoQuery {
 
 async fn get_auth_info(
     Extension(infra): Extension<Arc<InfraModule>>,
-    Query(query): Query<GetAuthInfoQuery>,
+    ApiQuery(query): ApiQuery<GetAuthInfoQuery>,
     AccessTypeHeader(access_type): AccessTypeHeader,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, ApiError> {
     use usecases::get_auth_info::*;
 
     let get_auth_info = GetAuthInfo {
-        discord_client: infra.as_ref().resolve_ref(),
+        identity_provider: infra.as_ref().resolve_ref(),
         member_repo: infra.as_ref().resolve_ref(),
+        permission_service: infra.as_ref().resolve_ref(),
+        oauth2_state_signer: infra.as_ref().resolve_ref(),
     };
     let auth_info = get_auth_info
         .execute(
@@ -52,8 +75,7 @@ async fn get_auth_info(
                 redirect_uri: query.redirect_uri,
             },
         )
-        .await
-        .unwrap();
+        .await?;
 
     #[derive(Debug, Serialize)]
     struct Member {
@@ -66,17 +88,24 @@ async fn get_auth_info(
     #[serde(rename_all = "camelCase")]
     struct Response {
         auth_url: String,
+        /// Kept by the frontend with the verifier until discord redirects back
+        state: String,
+        code_verifier: String,
         member: Option<Member>,
+        permissions: Vec<Permission>,
     }
 
-    Json(Response {
+    Ok(Json(Response {
         auth_url: auth_info.auth_url,
+        state: auth_info.state,
+        code_verifier: auth_info.code_verifier,
         member: auth_info.member.map(|member| Member {
             id: member.id,
             name: member.display_name,
             avatar: member.avatar,
         }),
-    })
+        permissions: auth_info.permissions,
+    }))
 }
 
 #[derive(Debug, Deserialize)]
@@ -84,18 +113,26 @@ async fn get_auth_info(
 struct SignInJson {
     code: String,
     redirect_uri: String,
+    state: String,
+    code_verifier: String,
+    /// Keep the session in an HttpOnly cookie instead of returning the token
+    #[serde(default)]
+    cookie: bool,
 }
 
 async fn sign_in(
     Extension(infra): Extension<Arc<InfraModule>>,
-    Json(json): Json<SignInJson>,
-) -> impl IntoResponse {
+    cookie_config: Option<Extension<CookieConfig>>,
+    ApiJson(json): ApiJson<SignInJson>,
+) -> Result<impl IntoResponse, ApiError> {
     use usecases::sign_in::*;
 
     let sign_in = SignIn {
-        discord_client: infra.as_ref().resolve_ref(),
+        identity_provider: infra.as_ref().resolve_ref(),
         member_repo: infra.as_ref().resolve_ref(),
         member_session_signer: infra.as_ref().resolve_ref(),
+        permission_service: infra.as_ref().resolve_ref(),
+        oauth2_state_signer: infra.as_ref().resolve_ref(),
     };
     let token = sign_in
         .execute(
@@ -103,61 +140,194 @@ async fn sign_in(
             SignInInput {
                 discord_code: json.code,
                 redirect_uri: json.redirect_uri,
+                state: json.state,
+                code_verifier: json.code_verifier,
             },
         )
-        .await
-        .unwrap();
+        .await?;
 
     #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
     struct Response {
-        token: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        token: Option<String>,
+        /// Echoed in the X-CSRF-Token header by cookie sessions
+        #[serde(skip_serializing_if = "Option::is_none")]
+        csrf_token: Option<String>,
     }
 
-    Json(Response { token })
+    let mut headers = HeaderMap::new();
+    let response = match cookie_config {
+        Some(Extension(cookie_config)) if json.cookie => Response {
+            token: None,
+            csrf_token: Some(cookie_config.sign_in(&mut headers, &token)),
+        },
+        _ => Response {
+            token: Some(token),
+            csrf_token: None,
+        },
+    };
+
+    Ok((headers, Json(response)))
+}
+
+/// Bearer token of the request, a session or an access token
+fn bearer_token(headers: &header::HeaderMap) -> Option<Result<&str, StatusCode>> {
+    let header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())?;
+
+    Some(
+        header
+            .split("Bearer ")
+            .last()
+            .ok_or(StatusCode::UNAUTHORIZED),
+    )
 }
 
+/// Resolve the session from the bearer token or the session cookie, none without a token
+async fn authorize(req: &Parts) -> Result<Option<Authorized>, ApiError> {
+    use usecases::authorization::*;
+
+    if let Some(authorized) = req.extensions.get::<Authorized>() {
+        return Ok(Some(authorized.clone()));
+    }
+
+    let token = bearer_token(&req.headers).or_else(|| {
+        req.extensions
+            .get::<CookieConfig>()
+            .and_then(|cookie_config| cookie_config.session_token(&req.method, &req.headers))
+    });
+    let Some(token) = token else {
+        return Ok(None);
+    };
+    let token = token?;
+    let infra = req
+        .extensions
+        .get::<Arc<InfraModule>>()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let authorization = Authorization {
+        member_repo: infra.resolve_ref(),
+        member_session_signer: infra.resolve_ref(),
+    };
+
+    // A failing table is not reported as a bad token
+    authorization
+        .execute(&AccessType::Guest, token)
+        .await
+        .map_err(|e| match e {
+            Error::Upstream(_) | Error::Internal(_) => e.into(),
+            _ => Error::InvalidToken.into(),
+        })
+}
+
+/// Access type of a valid api key, kept in the request once resolved
+#[derive(Debug, Clone)]
+struct ApiKeyAccess(AccessType);
+
+/// Resolve the access type of the X-Api-Key header, none without the header
+async fn authorize_api_key(req: &Parts) -> Option<Result<AccessType, ApiError>> {
+    use usecases::authenticate_api_key::*;
+
+    if let Some(ApiKeyAccess(access_type)) = req.extensions.get::<ApiKeyAccess>() {
+        return Some(Ok(access_type.clone()));
+    }
+
+    let token = req
+        .headers
+        .get("X-Api-Key")
+        .map(|value| value.to_str().map_err(|_| StatusCode::UNAUTHORIZED))?;
+    let Some(infra) = req.extensions.get::<Arc<InfraModule>>() else {
+        return Some(Err(StatusCode::INTERNAL_SERVER_ERROR.into()));
+    };
+
+    let authenticate = AuthenticateApiKey {
+        api_key_repo: infra.resolve_ref(),
+    };
+    Some(match token {
+        Ok(token) => authenticate
+            .execute(&AccessType::Guest, token)
+            .await
+            .map_err(From::from),
+        Err(e) => Err(e.into()),
+    })
+}
+
+/// Access type of the api key, the member or a guest
 #[derive(Debug)]
 struct AccessTypeHeader(AccessType);
 #[async_trait]
-impl<S> FromRequest<S> for AccessTypeHeader
+impl<S> FromRequestParts<S> for AccessTypeHeader
 where
     S: Send + Sync,
 {
-    type Rejection = StatusCode;
-
-    async fn from_request(req: Request, _: &S) -> Result<Self, Self::Rejection> {
-        let auth_header = req
-            .headers()
-            .get(header::AUTHORIZATION)
-            .and_then(|value| value.to_str().ok());
-
-        if let Some(header) = auth_header {
-            use usecases::authorization::*;
-
-            let token = header
-                .split("Bearer ")
-                .last()
-                .ok_or(StatusCode::UNAUTHORIZED)?;
-            let infra = req
-                .extensions()
-                .get::<Arc<InfraModule>>()
-                .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-
-            let authorization = Authorization {
-                member_repo: infra.resolve_ref(),
-                member_session_signer: infra.resolve_ref(),
-            };
-
-            match authorization
-                .execute(&AccessType::Guest, token)
-                .await
-                .map_err(|_| StatusCode::UNAUTHORIZED)?
-            {
-                Some(session) => Ok(AccessTypeHeader(AccessType::Member(session.member_id))),
-                None => Ok(AccessTypeHeader(AccessType::Guest)),
+    type Rejection = ApiError;
+
+    async fn from_request_parts(req: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        if let Some(access_type) = authorize_api_key(req).await {
+            return Ok(AccessTypeHeader(access_type?));
+        }
+
+        match authorize(req).await? {
+            Some(session) => Ok(AccessTypeHeader(AccessType::Member(session.member_id))),
+            None => Ok(AccessTypeHeader(AccessType::Guest)),
+        }
+    }
+}
+
+/// Current session, rejected with 401 when signed out
+#[derive(Debug)]
+struct SessionHeader(Authorized);
+#[async_trait]
+impl<S> FromRequestParts<S> for SessionHeader
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(req: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        authorize(req)
+            .await?
+            .map(SessionHeader)
+            .ok_or_else(|| StatusCode::UNAUTHORIZED.into())
+    }
+}
+
+/// Admin of the request, rejected with 401 when signed out and 403 when not an admin
+/// Api keys are let through, the use cases check their scopes
+#[derive(Debug)]
+struct AdminHeader(AccessType);
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminHeader
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(req: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        if let Some(access_type) = authorize_api_key(req).await {
+            return Ok(AdminHeader(access_type?));
+        }
+
+        let authorized = authorize(req).await?.ok_or(StatusCode::UNAUTHORIZED)?;
+        let infra = req
+            .extensions
+            .get::<Arc<InfraModule>>()
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // Access tokens carry the roles, only sessions read the member
+        let permission_service: &dyn PermissionService = infra.resolve_ref();
+        let access_type = match &authorized.roles {
+            Some(roles) => {
+                Ok(permission_service.access_type_with_roles(authorized.member_id, roles))
             }
-        } else {
-            Ok(AccessTypeHeader(AccessType::Guest))
+            None => permission_service.access_type(authorized.member_id).await,
+        };
+        match access_type {
+            Ok(access_type @ AccessType::Admin(_)) => Ok(AdminHeader(access_type)),
+            Ok(_) => Err(StatusCode::FORBIDDEN.into()),
+            Err(e) => Err(e.into()),
         }
     }
 }
@@ -168,11 +338,78 @@ pub async fn app(config: infra::BootstrapConfig) -> Router {
         .expect("Failed to bootstrap infra");
 
     let infra = Arc::new(infra);
-    Router::new()
-        .route("/", get(root))
+    let auth = Router::new()
         .route("/auth", get(get_auth_info))
         .route("/auth", post(sign_in))
+        .route("/auth/sign-out", post(session::sign_out))
+        .route("/auth/token", post(session::refresh_access_token));
+    let catalog = Router::new()
         .route("/duties", get(duty::get_duties))
         .route("/duties/:duty_id", get(duty::get_duty))
-        .layer(Extension(infra))
+        .route("/events", get(event::get_events))
+        .route("/worlds", get(world::get_worlds));
+    let member = Router::new()
+        .route(
+            "/auth/sessions",
+            get(session::get_sessions).delete(session::revoke_all_sessions),
+        )
+        .route(
+            "/auth/sessions/:session_id",
+            delete(session::revoke_session),
+        )
+        .route(
+            "/notifications/preferences",
+            get(notification::get_preferences).put(notification::set_preferences),
+        )
+        .route(
+            "/character",
+            get(character::get_character).put(character::set_character),
+        )
+        .route("/events", post(event::create_event))
+        .route("/events/:event_id", get(event::get_event))
+        .route("/events/:event_id/info", put(event::put_event_info))
+        .route("/events/:event_id/schedule", put(event::put_event_schedule))
+        .route("/events/:event_id/publish", post(event::publish_event))
+        .route("/events/:event_id/cancel", post(event::cancel_event))
+        .route("/events/:event_id/start", post(event::start_event))
+        .route("/events/:event_id/finish", post(event::finish_event))
+        .route(
+            "/events/:event_id/sign-up",
+            put(event::join_event).delete(event::leave_event),
+        )
+        .route(
+            "/events/:event_id/reconfirmation",
+            put(reconfirmation::respond),
+        )
+        .route("/reminders/opt-out", put(reminder::set_opt_out));
+    let admin = Router::new()
+        .route(
+            "/admin/duty-categories/:category_id",
+            put(admin::put_duty_category),
+        )
+        .route(
+            "/admin/duties/:duty_id",
+            put(admin::put_duty).delete(admin::delete_duty),
+        )
+        .route("/admin/events/:event_id/cancel", post(admin::cancel_event))
+        .route("/admin/events/:event_id/finish", post(admin::finish_event))
+        .route(
+            "/admin/members/:member_id/suspension",
+            put(admin::suspend_member).delete(admin::lift_suspension),
+        );
+
+    // Discord signs its interactions and expects an answer within seconds, never limited
+    let router = Router::new()
+        .route("/", get(root))
+        .route("/discord/interactions", post(discord::interactions))
+        .merge(limit(auth, RouteGroup::Auth))
+        .merge(limit(catalog, RouteGroup::Catalog))
+        .merge(limit(member, RouteGroup::Member))
+        .merge(limit(admin, RouteGroup::Admin))
+        .layer(Extension(infra));
+
+    match CookieConfig::from_env() {
+        Some(cookie_config) => router.layer(Extension(cookie_config)),
+        None => router,
+    }
 }
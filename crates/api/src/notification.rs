@@ -0,0 +1,66 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{http::StatusCode, Extension, Json};
+use infra::InfraModule;
+use minibell::{
+    notification::{NotificationChannel, NotificationKind, NotificationPreferences, QuietHours},
+    usecases::{self, UseCase},
+};
+use serde::{Deserialize, Serialize};
+use shaku::HasComponent;
+
+use crate::{error::ApiError, extract::ApiJson, AccessTypeHeader};
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPreferencesJson {
+    /// Kinds not listed fall back to direct messages
+    #[serde(default)]
+    channels: HashMap<NotificationKind, NotificationChannel>,
+    /// Timezone name, with start and end as `HH:MM:SS`
+    quiet_hours: Option<QuietHours>,
+}
+
+pub async fn get_preferences(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+) -> Result<Json<NotificationPreferencesJson>, ApiError> {
+    use usecases::get_notification_preferences::*;
+
+    let get_preferences = GetNotificationPreferences {
+        preference_repo: infra.as_ref().resolve_ref(),
+    };
+    let preferences = get_preferences.execute(&access_type, ()).await?;
+
+    // List every kind, so clients see the defaults
+    Ok(Json(NotificationPreferencesJson {
+        channels: NotificationKind::ALL
+            .iter()
+            .map(|kind| (*kind, preferences.channel(*kind)))
+            .collect(),
+        quiet_hours: preferences.quiet_hours,
+    }))
+}
+
+pub async fn set_preferences(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiJson(json): ApiJson<NotificationPreferencesJson>,
+) -> Result<StatusCode, ApiError> {
+    use usecases::set_notification_preferences::*;
+
+    let set_preferences = SetNotificationPreferences {
+        preference_repo: infra.as_ref().resolve_ref(),
+    };
+    set_preferences
+        .execute(
+            &access_type,
+            NotificationPreferences {
+                channels: json.channels,
+                quiet_hours: json.quiet_hours,
+            },
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,218 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, request::Parts, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use chrono::{DateTime, Utc};
+use infra::InfraModule;
+use lambda_http::{request::RequestContext, RequestExt};
+use minibell::{
+    api_key::ApiKey,
+    rate_limit::{RateLimit, RateLimitStore},
+};
+use shaku::HasComponent;
+
+use crate::{authorize, authorize_api_key, error::ApiError, ApiKeyAccess};
+
+/// Routes sharing a limit, configured with RATE_LIMIT_<GROUP>=<guest>,<authenticated>
+/// in requests per minute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteGroup {
+    /// Sign in and tokens, each sign in calls discord
+    Auth,
+    /// Duties and worlds
+    Catalog,
+    /// Sessions, preferences and event answers of the member
+    Member,
+    Admin,
+}
+
+impl RouteGroup {
+    fn name(&self) -> &'static str {
+        match self {
+            RouteGroup::Auth => "auth",
+            RouteGroup::Catalog => "catalog",
+            RouteGroup::Member => "member",
+            RouteGroup::Admin => "admin",
+        }
+    }
+
+    fn env(&self) -> &'static str {
+        match self {
+            RouteGroup::Auth => "RATE_LIMIT_AUTH",
+            RouteGroup::Catalog => "RATE_LIMIT_CATALOG",
+            RouteGroup::Member => "RATE_LIMIT_MEMBER",
+            RouteGroup::Admin => "RATE_LIMIT_ADMIN",
+        }
+    }
+
+    /// Requests per minute of guests and authenticated callers
+    fn default_limits(&self) -> (u64, u64) {
+        match self {
+            RouteGroup::Auth => (10, 30),
+            RouteGroup::Catalog => (60, 300),
+            RouteGroup::Member => (30, 120),
+            RouteGroup::Admin => (10, 120),
+        }
+    }
+}
+
+/// Limits of a route group, guests are counted by ip
+/// and members or api keys by their id
+#[derive(Debug, Clone, Copy)]
+struct RateLimiter {
+    group: RouteGroup,
+    guest: RateLimit,
+    authenticated: RateLimit,
+}
+
+impl RateLimiter {
+    fn from_env(group: RouteGroup) -> Self {
+        let (guest, authenticated) = match std::env::var(group.env()) {
+            Ok(limits) => limits
+                .split_once(',')
+                .and_then(|(guest, authenticated)| {
+                    Some((
+                        guest.trim().parse().ok()?,
+                        authenticated.trim().parse().ok()?,
+                    ))
+                })
+                .unwrap_or_else(|| {
+                    panic!(
+                        "{} must be <guest>,<authenticated> requests per minute",
+                        group.env()
+                    )
+                }),
+            Err(_) => group.default_limits(),
+        };
+
+        Self {
+            group,
+            guest: RateLimit::per_minute(guest),
+            authenticated: RateLimit::per_minute(authenticated),
+        }
+    }
+}
+
+/// Apply the limits of the group to every route of the router
+pub fn limit(router: Router, group: RouteGroup) -> Router {
+    router.route_layer(middleware::from_fn_with_state(
+        RateLimiter::from_env(group),
+        rate_limit,
+    ))
+}
+
+/// Ip of the caller, from the function url context on Lambda
+fn client_ip(parts: &Parts) -> Option<String> {
+    if let Some(RequestContext::ApiGatewayV2(context)) = parts.extensions.request_context_ref() {
+        return context.http.source_ip.clone();
+    }
+
+    parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip().to_string())
+}
+
+/// Counter key of an authenticated caller, none for guests
+/// The authorization is kept in the request, so the extractors do not run it again
+/// Invalid credentials are counted as a guest, the extractors reject them
+async fn authenticated_caller(parts: &mut Parts) -> Option<String> {
+    let api_key_id = parts
+        .headers
+        .get("X-Api-Key")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|token| ApiKey::parse(token).ok())
+        .map(|(id, _)| id.to_string());
+    if let Some(id) = api_key_id {
+        if let Some(Ok(access_type)) = authorize_api_key(parts).await {
+            parts.extensions.insert(ApiKeyAccess(access_type));
+            return Some(format!("api_key:{}", id));
+        }
+    }
+
+    if let Ok(Some(authorized)) = authorize(parts).await {
+        let key = format!("member:{}", authorized.member_id);
+        parts.extensions.insert(authorized);
+        return Some(key);
+    }
+
+    None
+}
+
+fn too_many_requests(limit: &RateLimit, hits: u64, now: DateTime<Utc>) -> Option<Response> {
+    let retry_after = limit.retry_after(hits, now)?;
+    let seconds = (retry_after.num_milliseconds() + 999) / 1000;
+
+    Some(
+        (
+            [(header::RETRY_AFTER, seconds.max(1).to_string())],
+            ApiError::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                "rate_limited",
+                "Too many requests, please try again later.",
+            ),
+        )
+            .into_response(),
+    )
+}
+
+/// Count the request, none when the store failed
+/// Fail open, an unavailable store must not take the api down
+async fn hit(
+    store: &dyn RateLimitStore,
+    limiter: &RateLimiter,
+    key: &str,
+    limit: &RateLimit,
+    now: DateTime<Utc>,
+) -> Option<u64> {
+    match store
+        .hit(
+            &format!("{}:{}", limiter.group.name(), key),
+            limit.window_end(now),
+        )
+        .await
+    {
+        Ok(hits) => Some(hits),
+        Err(e) => {
+            tracing::warn!("Rate limit store failed: {:?}", e);
+            None
+        }
+    }
+}
+
+/// Every request counts against its ip before the credentials are resolved,
+/// so floods of invalid tokens are rejected without reading the keys or sessions
+/// An ip is capped at the authenticated limit, and at the guest limit without valid credentials
+async fn rate_limit(State(limiter): State<RateLimiter>, req: Request, next: Next) -> Response {
+    let (mut parts, body) = req.into_parts();
+    let Some(infra) = parts.extensions.get::<Arc<InfraModule>>().cloned() else {
+        return ApiError::from(StatusCode::INTERNAL_SERVER_ERROR).into_response();
+    };
+    let store: &dyn RateLimitStore = infra.resolve_ref();
+    let now = Utc::now();
+
+    let ip = client_ip(&parts).unwrap_or_else(|| "unknown".to_string());
+    let ip_hits = hit(store, &limiter, &format!("ip:{}", ip), &limiter.guest, now).await;
+    if let Some(response) =
+        ip_hits.and_then(|hits| too_many_requests(&limiter.authenticated, hits, now))
+    {
+        return response;
+    }
+
+    let rejected = match authenticated_caller(&mut parts).await {
+        Some(key) => hit(store, &limiter, &key, &limiter.authenticated, now)
+            .await
+            .and_then(|hits| too_many_requests(&limiter.authenticated, hits, now)),
+        None => ip_hits.and_then(|hits| too_many_requests(&limiter.guest, hits, now)),
+    };
+    if let Some(response) = rejected {
+        return response;
+    }
+
+    next.run(Request::from_parts(parts, body)).await
+}
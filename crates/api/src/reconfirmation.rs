@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+use infra::InfraModule;
+use minibell::{
+    reconfirmation::ReconfirmationStatus,
+    usecases::{self, UseCase},
+};
+use serde::{Deserialize, Serialize};
+use shaku::HasComponent;
+
+use crate::{
+    error::ApiError,
+    extract::{ApiJson, ApiPath},
+    AccessTypeHeader,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconfirmationJson {
+    confirm: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconfirmationDto {
+    status: &'static str,
+}
+
+pub async fn respond(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(event_id): ApiPath<String>,
+    ApiJson(json): ApiJson<ReconfirmationJson>,
+) -> Result<Json<ReconfirmationDto>, ApiError> {
+    use usecases::respond_reconfirmation::*;
+
+    let respond = RespondReconfirmation {
+        reconfirmation_repo: infra.as_ref().resolve_ref(),
+        event_repo: infra.as_ref().resolve_ref(),
+    };
+    let status = respond
+        .execute(
+            &access_type,
+            Input {
+                event_id,
+                confirm: json.confirm,
+            },
+        )
+        .await?;
+
+    Ok(Json(ReconfirmationDto {
+        status: match status {
+            ReconfirmationStatus::Pending => "pending",
+            ReconfirmationStatus::Confirmed => "confirmed",
+            ReconfirmationStatus::Dropped => "dropped",
+            ReconfirmationStatus::Expired => "expired",
+        },
+    }))
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use axum::{http::StatusCode, Extension};
+use infra::InfraModule;
+use minibell::usecases::{self, UseCase};
+use serde::Deserialize;
+use shaku::HasComponent;
+
+use crate::{error::ApiError, extract::ApiJson, AccessTypeHeader};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderOptOutJson {
+    /// Apply to all events if not set
+    event_id: Option<String>,
+    opt_out: bool,
+}
+
+pub async fn set_opt_out(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiJson(json): ApiJson<ReminderOptOutJson>,
+) -> Result<StatusCode, ApiError> {
+    use usecases::set_reminder_opt_out::*;
+
+    let set_opt_out = SetReminderOptOut {
+        reminder_repo: infra.as_ref().resolve_ref(),
+        event_repo: infra.as_ref().resolve_ref(),
+    };
+    set_opt_out
+        .execute(
+            &access_type,
+            Input {
+                event_id: json.event_id,
+                opt_out: json.opt_out,
+            },
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
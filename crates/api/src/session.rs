@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use axum::{
+    http::{HeaderMap, Method, StatusCode},
+    Extension, Json,
+};
+use infra::InfraModule;
+use minibell::{
+    usecases::{self, UseCase},
+    AccessType,
+};
+use serde::Serialize;
+use shaku::HasComponent;
+
+use crate::{
+    bearer_token, cookie::CookieConfig, error::ApiError, extract::ApiPath, AccessTypeHeader,
+    SessionHeader,
+};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDto {
+    id: String,
+    issued_at: i64,
+    expires_at: i64,
+    /// Session used by this request
+    current: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessTokenDto {
+    access_token: String,
+    expires_at: i64,
+}
+
+pub async fn get_sessions(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    SessionHeader(current): SessionHeader,
+) -> Result<Json<Vec<SessionDto>>, ApiError> {
+    use usecases::get_member_sessions::*;
+
+    let get_sessions = GetMemberSessions {
+        member_repo: infra.as_ref().resolve_ref(),
+    };
+    let sessions = get_sessions
+        .execute(&AccessType::Member(current.member_id), ())
+        .await?;
+
+    Ok(Json(
+        sessions
+            .into_iter()
+            .map(|session| SessionDto {
+                current: session.id == current.session_id,
+                id: session.id,
+                issued_at: session.issued_at.timestamp_millis(),
+                expires_at: session.expires_at.timestamp_millis(),
+            })
+            .collect(),
+    ))
+}
+
+/// Issue a short lived access token, with the session token as bearer or in the cookie
+pub async fn refresh_access_token(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    cookie_config: Option<Extension<CookieConfig>>,
+    method: Method,
+    headers: HeaderMap,
+) -> Result<Json<AccessTokenDto>, ApiError> {
+    use usecases::refresh_access_token::*;
+
+    let session_token = bearer_token(&headers)
+        .or_else(|| {
+            cookie_config
+                .and_then(|Extension(cookie_config)| cookie_config.session_token(&method, &headers))
+        })
+        .ok_or(StatusCode::UNAUTHORIZED)??;
+    let refresh = RefreshAccessToken {
+        member_repo: infra.as_ref().resolve_ref(),
+        member_session_signer: infra.as_ref().resolve_ref(),
+    };
+    let response = refresh.execute(&AccessType::Guest, session_token).await?;
+
+    Ok(Json(AccessTokenDto {
+        access_token: response.access_token,
+        expires_at: response.expires_at.timestamp_millis(),
+    }))
+}
+
+/// Sign out the current session, and clear the session cookie
+pub async fn sign_out(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    cookie_config: Option<Extension<CookieConfig>>,
+    SessionHeader(current): SessionHeader,
+) -> Result<(StatusCode, HeaderMap), ApiError> {
+    let mut headers = HeaderMap::new();
+    if let Some(Extension(cookie_config)) = cookie_config {
+        cookie_config.sign_out(&mut headers);
+    }
+
+    let status = revoke(
+        &infra,
+        &AccessType::Member(current.member_id),
+        usecases::revoke_member_session::Input::Session(current.session_id),
+    )
+    .await?;
+
+    Ok((status, headers))
+}
+
+pub async fn revoke_session(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+    ApiPath(session_id): ApiPath<String>,
+) -> Result<StatusCode, ApiError> {
+    revoke(
+        &infra,
+        &access_type,
+        usecases::revoke_member_session::Input::Session(session_id),
+    )
+    .await
+}
+
+/// Sign out everywhere
+pub async fn revoke_all_sessions(
+    Extension(infra): Extension<Arc<InfraModule>>,
+    AccessTypeHeader(access_type): AccessTypeHeader,
+) -> Result<StatusCode, ApiError> {
+    revoke(
+        &infra,
+        &access_type,
+        usecases::revoke_member_session::Input::All,
+    )
+    .await
+}
+
+async fn revoke(
+    infra: &InfraModule,
+    access_type: &AccessType,
+    input: usecases::revoke_member_session::Input,
+) -> Result<StatusCode, ApiError> {
+    use usecases::revoke_member_session::*;
+
+    let revoke = RevokeMemberSession {
+        member_repo: infra.resolve_ref(),
+    };
+    revoke.execute(access_type, input).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use axum::{response::IntoResponse, Extension, Json};
+use infra::InfraModule;
+use minibell::{
+    usecases::{self, UseCase},
+    AccessType,
+};
+use serde::Serialize;
+use shaku::HasComponent;
+
+use crate::error::ApiError;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RegionDto {
+    id: String,
+    name: String,
+    data_centers: Vec<DataCenterDto>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DataCenterDto {
+    id: String,
+    name: String,
+    worlds: Vec<WorldDto>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WorldDto {
+    id: String,
+    name: String,
+}
+
+pub async fn get_worlds(
+    Extension(infra): Extension<Arc<InfraModule>>,
+) -> Result<impl IntoResponse, ApiError> {
+    use usecases::get_worlds::*;
+
+    let get_worlds = GetWorlds {
+        world_repo: infra.as_ref().resolve_ref(),
+    };
+    let catalog = get_worlds.execute(&AccessType::Guest, ()).await?;
+
+    // Nest worlds into data centers, and data centers into regions
+    let regions = catalog
+        .regions
+        .into_iter()
+        .map(|region| RegionDto {
+            data_centers: catalog
+                .data_centers
+                .iter()
+                .filter(|data_center| data_center.region == region.id)
+                .map(|data_center| DataCenterDto {
+                    id: data_center.id.clone(),
+                    name: data_center.name.clone(),
+                    worlds: catalog
+                        .worlds
+                        .iter()
+                        .filter(|world| world.data_center == data_center.id)
+                        .map(|world| WorldDto {
+                            id: world.id.clone(),
+                            name: world.name.clone(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            id: region.id,
+            name: region.name,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Json(regions))
+}
@@ -0,0 +1,106 @@
+use chrono::{Duration, Utc};
+use clap::Subcommand;
+use infra::{BootstrapConfig, InfraModule};
+use minibell::{
+    api_key::ApiKeyScope,
+    usecases::{create_api_key, list_api_keys, revoke_api_key, UseCase},
+    AccessType,
+};
+use shaku::HasComponent;
+
+#[derive(Subcommand)]
+pub enum ApiKeyCommands {
+    /// Create a key, the token is only shown once
+    Create {
+        /// Name of the bot or script using the key
+        name: String,
+        /// read_catalog, manage_events or manage_catalog, repeat for several scopes
+        #[arg(long = "scope", required = true, value_parser = parse_scope)]
+        scopes: Vec<ApiKeyScope>,
+        /// Expire the key after this many days, never by default
+        #[arg(long)]
+        expires_days: Option<i64>,
+    },
+    /// List the keys
+    List,
+    /// Revoke a key by id
+    Revoke { id: String },
+}
+
+fn parse_scope(scope: &str) -> Result<ApiKeyScope, String> {
+    serde_yaml::from_str::<ApiKeyScope>(scope).map_err(|_| {
+        "Unknown scope, expected read_catalog, manage_events or manage_catalog".to_string()
+    })
+}
+
+async fn bootstrap(config: &str) -> InfraModule {
+    infra::bootstrap(BootstrapConfig {
+        secret_manager_key: Some(config.to_string()),
+    })
+    .await
+    .expect("Failed to bootstrap infra")
+}
+
+pub async fn run(command: ApiKeyCommands, config: &str) {
+    let infra = bootstrap(config).await;
+
+    match command {
+        ApiKeyCommands::Create {
+            name,
+            scopes,
+            expires_days,
+        } => {
+            let create = create_api_key::CreateApiKey {
+                api_key_repo: infra.resolve_ref(),
+            };
+            let response = create
+                .execute(
+                    &AccessType::System,
+                    create_api_key::Input {
+                        name,
+                        scopes,
+                        expires_at: expires_days.map(|days| Utc::now() + Duration::days(days)),
+                    },
+                )
+                .await
+                .unwrap();
+
+            println!("id: {}", response.api_key.id);
+            println!("token: {}", response.token);
+        }
+        ApiKeyCommands::List => {
+            let list = list_api_keys::ListApiKeys {
+                api_key_repo: infra.resolve_ref(),
+            };
+            let api_keys = list.execute(&AccessType::System, ()).await.unwrap();
+
+            for api_key in api_keys {
+                println!(
+                    "{}\t{}\t{:?}\texpires: {}\tlast used: {}",
+                    api_key.id,
+                    api_key.name,
+                    api_key.scopes,
+                    api_key
+                        .expires_at
+                        .map(|at| at.to_rfc3339())
+                        .unwrap_or("never".to_string()),
+                    api_key
+                        .last_used_at
+                        .map(|at| at.to_rfc3339())
+                        .unwrap_or("never".to_string()),
+                );
+            }
+        }
+        ApiKeyCommands::Revoke { id } => {
+            let revoke = revoke_api_key::RevokeApiKey {
+                api_key_repo: infra.resolve_ref(),
+            };
+            revoke
+                .execute(&AccessType::System, revoke_api_key::Input { id })
+                .await
+                .unwrap();
+
+            println!("revoked");
+        }
+    }
+}
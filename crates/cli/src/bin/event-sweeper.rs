@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use clap::Parser;
+use infra::BootstrapConfig;
+use minibell::{
+    usecases::{sweep_events, UseCase},
+    AccessType,
+};
+use shaku::HasComponent;
+
+#[derive(Parser)]
+#[command(version)]
+#[command(about = "Start and finish events on schedule, expire reconfirmations and send reminders", long_about = None)]
+struct Args {
+    /// AWS Secret Manager Key
+    #[arg(short, long)]
+    secret_manager_key: Option<String>,
+
+    /// Sweep once, then exit
+    #[arg(long)]
+    once: bool,
+
+    /// Seconds between two sweeps
+    #[arg(long, default_value_t = 60)]
+    interval: u64,
+
+    /// Minutes before start to remind the roster, comma separated
+    #[arg(long, value_delimiter = ',', default_values_t = [1440, 30])]
+    reminder_minutes: Vec<i64>,
+}
+
+#[tokio::main]
+async fn main() {
+    #[cfg(debug_assertions)]
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+
+    let secret_manager_key = args
+        .secret_manager_key
+        .or_else(|| std::env::var("SECRET_KEY").ok());
+    let infra = infra::bootstrap(BootstrapConfig { secret_manager_key })
+        .await
+        .unwrap();
+
+    let usecase = sweep_events::SweepEvents {
+        event_repo: infra.resolve_ref(),
+        reconfirmation_repo: infra.resolve_ref(),
+        reminder_repo: infra.resolve_ref(),
+        preference_repo: infra.resolve_ref(),
+        direct_messenger: infra.resolve_ref(),
+        event_thread: infra.resolve_ref(),
+    };
+    let reminder_offsets = args
+        .reminder_minutes
+        .iter()
+        .map(|minutes| chrono::Duration::minutes(*minutes))
+        .collect::<Vec<_>>();
+
+    let mut interval = tokio::time::interval(Duration::from_secs(args.interval));
+    loop {
+        interval.tick().await;
+
+        let input = sweep_events::Input {
+            now: chrono::Utc::now(),
+            reminder_offsets: reminder_offsets.clone(),
+        };
+        match usecase.execute(&AccessType::System, input).await {
+            Ok(response) => {
+                if response.started
+                    + response.finished
+                    + response.expired
+                    + response.reminders_sent
+                    + response.reminders_failed
+                    > 0
+                {
+                    println!(
+                        "started: {}, finished: {}, expired: {}, reminders sent: {}, reminders failed: {}",
+                        response.started,
+                        response.finished,
+                        response.expired,
+                        response.reminders_sent,
+                        response.reminders_failed
+                    );
+                }
+            }
+            Err(e) => eprintln!("Failed to sweep events: {:?}", e),
+        }
+
+        if args.once {
+            break;
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use clap::Parser;
+use infra::BootstrapConfig;
+use minibell::{
+    outbox::{DiscordSink, NotificationSink, OutboxRepository, OutboxSink},
+    usecases::{dispatch_outbox, UseCase},
+    AccessType,
+};
+use shaku::HasComponent;
+
+#[derive(Parser)]
+#[command(version)]
+#[command(about = "Deliver outbox messages to the sinks", long_about = None)]
+struct Args {
+    /// AWS Secret Manager Key
+    #[arg(short, long)]
+    secret_manager_key: Option<String>,
+
+    /// Dispatch the due messages once, then exit
+    #[arg(long)]
+    once: bool,
+
+    /// Seconds between two dispatches
+    #[arg(long, default_value_t = 10)]
+    interval: u64,
+
+    /// Messages per dispatch
+    #[arg(long, default_value_t = 25)]
+    limit: usize,
+
+    /// Attempts before a message is dead lettered
+    #[arg(long, default_value_t = 8)]
+    max_attempts: u32,
+}
+
+#[tokio::main]
+async fn main() {
+    #[cfg(debug_assertions)]
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+
+    let secret_manager_key = args
+        .secret_manager_key
+        .or_else(|| std::env::var("SECRET_KEY").ok());
+    let infra = infra::bootstrap(BootstrapConfig { secret_manager_key })
+        .await
+        .unwrap();
+
+    let outbox_repo: &dyn OutboxRepository = infra.resolve_ref();
+    let webhook: &dyn OutboxSink = infra.resolve_ref();
+    let discord: &dyn DiscordSink = infra.resolve_ref();
+    let notification: &dyn NotificationSink = infra.resolve_ref();
+    let usecase = dispatch_outbox::DispatchOutbox {
+        outbox_repo,
+        sinks: vec![webhook, discord, notification],
+    };
+
+    let mut interval = tokio::time::interval(Duration::from_secs(args.interval));
+    loop {
+        interval.tick().await;
+
+        let input = dispatch_outbox::Input {
+            now: chrono::Utc::now(),
+            limit: args.limit,
+            max_attempts: args.max_attempts,
+            lease: chrono::Duration::minutes(5),
+        };
+        match usecase.execute(&AccessType::System, input).await {
+            Ok(response) => {
+                if response.delivered + response.retried + response.dead_lettered > 0 {
+                    println!(
+                        "delivered: {}, retried: {}, dead lettered: {}",
+                        response.delivered, response.retried, response.dead_lettered
+                    );
+                }
+            }
+            Err(e) => eprintln!("Failed to dispatch the outbox: {:?}", e),
+        }
+
+        if args.once {
+            break;
+        }
+    }
+}
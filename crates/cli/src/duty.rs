@@ -21,13 +21,13 @@ struct CategoryManifest {
     sort: i32,
 }
 
-impl Into<duty::DutyCategory> for CategoryManifest {
-    fn into(self) -> duty::DutyCategory {
+impl From<CategoryManifest> for duty::DutyCategory {
+    fn from(value: CategoryManifest) -> Self {
         duty::DutyCategory {
-            id: self.id.to_string(),
-            name: self.name.to_string(),
-            parent: self.parent.clone(),
-            sort: self.sort,
+            id: value.id.to_string(),
+            name: value.name.to_string(),
+            parent: value.parent.clone(),
+            sort: value.sort,
         }
     }
 }
@@ -47,17 +47,17 @@ struct DutyManifest {
     phrases: Option<Vec<DutyPhraseManifest>>,
 }
 
-impl Into<duty::Duty> for DutyManifest {
-    fn into(self) -> duty::Duty {
+impl From<DutyManifest> for duty::Duty {
+    fn from(value: DutyManifest) -> Self {
         duty::Duty {
-            id: self.id,
-            category: self.category,
-            name: self.name,
-            description: self.description,
-            short_name: self.short_name,
-            patch: self.patch,
-            image: self.image,
-            sort: self.sort,
+            id: value.id,
+            category: value.category,
+            name: value.name,
+            description: value.description,
+            short_name: value.short_name,
+            patch: value.patch,
+            image: value.image,
+            sort: value.sort,
         }
     }
 }
@@ -68,11 +68,11 @@ struct DutyPhraseManifest {
     progression: f64,
 }
 
-impl Into<duty::DutyPhrase> for DutyPhraseManifest {
-    fn into(self) -> duty::DutyPhrase {
+impl From<DutyPhraseManifest> for duty::DutyPhrase {
+    fn from(value: DutyPhraseManifest) -> Self {
         duty::DutyPhrase {
-            name: self.name,
-            progression: self.progression,
+            name: value.name,
+            progression: value.progression,
         }
     }
 }
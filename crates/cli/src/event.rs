@@ -0,0 +1,25 @@
+use infra::BootstrapConfig;
+use minibell::{
+    usecases::{sync_events_discord, UseCase},
+    AccessType,
+};
+use shaku::HasComponent;
+
+pub async fn sync_discord(config: &str) {
+    let infra = infra::bootstrap(BootstrapConfig {
+        secret_manager_key: Some(config.to_string()),
+    })
+    .await
+    .expect("Failed to bootstrap infra");
+
+    let sync_events = sync_events_discord::SyncEventsDiscord {
+        event_repo: infra.resolve_ref(),
+        duty_repo: infra.resolve_ref(),
+        announcer: infra.resolve_ref(),
+        mirror: infra.resolve_ref(),
+        thread: infra.resolve_ref(),
+    };
+    let response = sync_events.execute(&AccessType::System, ()).await.unwrap();
+
+    println!("synced: {}, failed: {}", response.synced, response.failed);
+}
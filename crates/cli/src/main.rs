@@ -1,6 +1,10 @@
 use clap::{Parser, Subcommand};
 
+mod api_key;
 mod duty;
+mod event;
+mod member;
+mod world;
 
 #[derive(Parser)]
 #[command(version)]
@@ -16,11 +20,32 @@ struct Args {
 
 #[derive(Subcommand)]
 enum Commands {
+    /// Manage api keys for bots and scripts
+    ApiKey {
+        #[command(subcommand)]
+        command: api_key::ApiKeyCommands,
+    },
     /// Run duty related commands
     Duty {
         /// Manifest file
         file: String,
     },
+    /// Refresh stale member profiles from discord
+    RefreshMembers {
+        /// Refresh members not updated for this many hours
+        #[arg(long, default_value_t = 24)]
+        stale_hours: i64,
+        /// Members per run, keep it low to stay under discord rate limits
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Sync public and running events with discord, recreating what was deleted there
+    SyncDiscord,
+    /// Upload the world and data center catalog
+    World {
+        /// Manifest file
+        file: String,
+    },
 }
 
 #[tokio::main]
@@ -36,10 +61,26 @@ async fn main() -> std::io::Result<()> {
     };
 
     match args.command {
+        Some(Commands::ApiKey { command }) => {
+            api_key::run(command, &secret_manager_key).await;
+            Ok(())
+        }
         Some(Commands::Duty { file }) => {
             duty::upload_duty(&file, &secret_manager_key).await;
             Ok(())
         }
+        Some(Commands::RefreshMembers { stale_hours, limit }) => {
+            member::refresh_members(stale_hours, limit, &secret_manager_key).await;
+            Ok(())
+        }
+        Some(Commands::SyncDiscord) => {
+            event::sync_discord(&secret_manager_key).await;
+            Ok(())
+        }
+        Some(Commands::World { file }) => {
+            world::upload_world(&file, &secret_manager_key).await;
+            Ok(())
+        }
         None => Ok(()),
     }
 }
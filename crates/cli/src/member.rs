@@ -0,0 +1,37 @@
+use chrono::{Duration, Utc};
+use infra::BootstrapConfig;
+use minibell::{
+    usecases::{refresh_members, UseCase},
+    AccessType,
+};
+use shaku::HasComponent;
+
+pub async fn refresh_members(stale_hours: i64, limit: usize, config: &str) {
+    let infra = infra::bootstrap(BootstrapConfig {
+        secret_manager_key: Some(config.to_string()),
+    })
+    .await
+    .expect("Failed to bootstrap infra");
+
+    let refresh_members = refresh_members::RefreshMembers {
+        discord_client: infra.resolve_ref(),
+        member_repo: infra.resolve_ref(),
+    };
+    let now = Utc::now();
+    let response = refresh_members
+        .execute(
+            &AccessType::System,
+            refresh_members::Input {
+                now,
+                updated_before: now - Duration::hours(stale_hours),
+                limit,
+            },
+        )
+        .await
+        .unwrap();
+
+    println!(
+        "refreshed: {}, left: {}, failed: {}",
+        response.refreshed, response.left, response.failed
+    );
+}
@@ -0,0 +1,85 @@
+use infra::BootstrapConfig;
+use minibell::{
+    usecases::{insert_worlds, UseCase},
+    world, AccessType,
+};
+use serde::Deserialize;
+use shaku::HasComponent;
+
+#[derive(Debug, Deserialize)]
+struct RegionManifest {
+    id: String,
+    name: String,
+    data_centers: Vec<DataCenterManifest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DataCenterManifest {
+    id: String,
+    name: String,
+    worlds: Vec<WorldManifest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorldManifest {
+    id: String,
+    name: String,
+}
+
+/// Flatten the manifest, the sort follow the order in the file
+fn flatten(manifest: Vec<RegionManifest>) -> insert_worlds::Input {
+    let mut input = insert_worlds::Input {
+        regions: vec![],
+        data_centers: vec![],
+        worlds: vec![],
+    };
+
+    for region in manifest {
+        for data_center in region.data_centers {
+            for world in data_center.worlds {
+                input.worlds.push(world::World {
+                    id: world.id,
+                    data_center: data_center.id.clone(),
+                    name: world.name,
+                    sort: input.worlds.len() as i32,
+                });
+            }
+
+            input.data_centers.push(world::DataCenter {
+                id: data_center.id,
+                region: region.id.clone(),
+                name: data_center.name,
+                sort: input.data_centers.len() as i32,
+            });
+        }
+
+        input.regions.push(world::Region {
+            id: region.id,
+            name: region.name,
+            sort: input.regions.len() as i32,
+        });
+    }
+
+    input
+}
+
+pub async fn upload_world(file: &str, config: &str) {
+    let infra = infra::bootstrap(BootstrapConfig {
+        secret_manager_key: Some(config.to_string()),
+    })
+    .await
+    .expect("Failed to bootstrap infra");
+
+    // Read file
+    let file = std::fs::read_to_string(file).expect("Unable to read file");
+    // Parse file (yml)
+    let manifest: Vec<RegionManifest> = serde_yaml::from_str(&file).expect("Unable to parse file");
+
+    let insert_worlds = insert_worlds::InsertWorlds {
+        world_repo: infra.resolve_ref(),
+    };
+    insert_worlds
+        .execute(&AccessType::System, flatten(manifest))
+        .await
+        .unwrap();
+}
@@ -1,8 +1,12 @@
-use super::member::MemberId;
+use super::{api_key::ApiKeyScope, member::MemberId};
 
 #[derive(Debug, Clone)]
 pub enum AccessType {
     System,
     Member(MemberId),
+    /// Trusted moderator, from the configured user ids or an admin role
+    Admin(MemberId),
+    /// Bot or script authenticated by an api key, the system limited to the scopes
+    ApiKey(Vec<ApiKeyScope>),
     Guest,
 }
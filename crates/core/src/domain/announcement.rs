@@ -0,0 +1,188 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shaku::Interface;
+
+use crate::{
+    event::{Event, EventStatus},
+    member::MemberId,
+    outbox::DomainEvent,
+    Error,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnnouncementStatus {
+    /// Published and open for sign up
+    Open,
+    Started,
+    Finished,
+    Cancelled,
+}
+
+/// Event announcement posted to the community
+#[derive(Debug, Clone)]
+pub struct Announcement {
+    pub event_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// Duty image
+    pub image: Option<String>,
+    pub start_at: DateTime<Utc>,
+
+    pub open_slots: usize,
+    pub total_slots: usize,
+
+    pub status: AnnouncementStatus,
+}
+
+impl Announcement {
+    /// Announcement of the event, none for drafts
+    pub fn of(event: &Event, image: Option<String>) -> Option<Self> {
+        let status = match event.status {
+            EventStatus::Draft => return None,
+            EventStatus::Private | EventStatus::Public => AnnouncementStatus::Open,
+            EventStatus::InProcess => AnnouncementStatus::Started,
+            EventStatus::Finished => AnnouncementStatus::Finished,
+            EventStatus::Cancelled => AnnouncementStatus::Cancelled,
+        };
+
+        Some(Self {
+            event_id: event.id.clone(),
+            title: event.info.title.clone(),
+            description: event.info.description.clone(),
+            image,
+            start_at: event.schedule.start_at,
+
+            open_slots: event.open_slots(),
+            total_slots: event.slots.len(),
+
+            status,
+        })
+    }
+}
+
+/// Post event announcements and keep them up to date
+#[async_trait]
+pub trait EventAnnouncer: Interface {
+    /// Post the announcement and return the message id
+    /// Return none if announcements are not configured
+    async fn post(&self, announcement: &Announcement) -> Result<Option<String>, Error>;
+
+    /// Edit a posted announcement in place
+    async fn edit(&self, message_id: &str, announcement: &Announcement) -> Result<(), Error>;
+}
+
+/// System message posted to the event thread
+#[derive(Debug, Clone)]
+pub enum ThreadMessage {
+    /// Members joined or left the roster
+    RosterChanged {
+        joined: Vec<MemberId>,
+        left: Vec<MemberId>,
+    },
+    Rescheduled {
+        old_start_at: DateTime<Utc>,
+        new_start_at: DateTime<Utc>,
+    },
+    /// Ping the roster when the event starts
+    Starting { roster: Vec<MemberId> },
+    /// Ask the roster to confirm after a reschedule, for the members who prefer a mention
+    ReconfirmationRequested {
+        deadline: DateTime<Utc>,
+        members: Vec<MemberId>,
+    },
+    /// Reminder for the members who prefer a mention over a direct message
+    Reminder {
+        start_at: DateTime<Utc>,
+        members: Vec<MemberId>,
+    },
+}
+
+impl ThreadMessage {
+    /// Message posted to the thread for the change, none if the thread is not told
+    pub fn for_change(change: &DomainEvent, roster: &[MemberId]) -> Option<Self> {
+        match change {
+            DomainEvent::SignUpAccepted { member_id, .. } => Some(ThreadMessage::RosterChanged {
+                joined: vec![*member_id],
+                left: vec![],
+            }),
+            DomainEvent::SignUpWithdrawn {
+                member_id,
+                accepted: true,
+                ..
+            } => Some(ThreadMessage::RosterChanged {
+                joined: vec![],
+                left: vec![*member_id],
+            }),
+            DomainEvent::ScheduleChanged {
+                old_start_at,
+                new_start_at,
+                ..
+            } => Some(ThreadMessage::Rescheduled {
+                old_start_at: *old_start_at,
+                new_start_at: *new_start_at,
+            }),
+            DomainEvent::EventStarted { .. } => Some(ThreadMessage::Starting {
+                roster: roster.to_vec(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Discussion thread opened under the event announcement
+#[async_trait]
+pub trait EventThread: Interface {
+    /// Open a thread under the announcement message and return the thread id
+    /// Return none if announcements are not configured
+    async fn open(&self, message_id: &str, name: &str) -> Result<Option<String>, Error>;
+    /// Add an accepted member to the thread
+    async fn add_member(&self, thread_id: &str, member_id: MemberId) -> Result<(), Error>;
+    /// Post a system message
+    async fn post(&self, thread_id: &str, message: &ThreadMessage) -> Result<(), Error>;
+    /// Archive and lock the thread, once the event is finished or cancelled
+    async fn archive(&self, thread_id: &str) -> Result<(), Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ThreadMessage;
+    use crate::outbox::DomainEvent;
+
+    #[test]
+    fn thread_message_for_change() {
+        let joined = ThreadMessage::for_change(
+            &DomainEvent::SignUpAccepted {
+                event_id: "event".to_string(),
+                member_id: 2,
+                promoted: true,
+            },
+            &[1, 2],
+        );
+        assert!(matches!(
+            joined,
+            Some(ThreadMessage::RosterChanged { joined, left }) if joined == [2] && left.is_empty()
+        ));
+
+        let starting = ThreadMessage::for_change(
+            &DomainEvent::EventStarted {
+                event_id: "event".to_string(),
+            },
+            &[1, 2],
+        );
+        assert!(matches!(
+            starting,
+            Some(ThreadMessage::Starting { roster }) if roster == [1, 2]
+        ));
+
+        // Leaving the waitlist does not change the roster
+        let waitlist_left = ThreadMessage::for_change(
+            &DomainEvent::SignUpWithdrawn {
+                event_id: "event".to_string(),
+                member_id: 3,
+                accepted: false,
+            },
+            &[1, 2],
+        );
+        assert!(waitlist_left.is_none());
+    }
+}
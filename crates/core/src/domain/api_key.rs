@@ -0,0 +1,186 @@
+use async_trait::async_trait;
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use shaku::Interface;
+
+use crate::{
+    validation::{Validate, Validator},
+    Error,
+};
+
+/// Prefix of api keys, to recognize them in logs and secret scanners
+const API_KEY_PREFIX: &str = "mb";
+const NAME_MAX_LENGTH: usize = 64;
+/// Last used time is only written when older than this, in minutes
+const LAST_USED_RESOLUTION_MINUTES: i64 = 5;
+
+/// Use cases an api key may run, as the system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Read the duty and world catalog
+    ReadCatalog,
+    /// Run event jobs, e.g. reminders and reconfirmations
+    ManageEvents,
+    /// Change the duty and world catalog
+    ManageCatalog,
+}
+
+impl ApiKeyScope {
+    pub const ALL: [ApiKeyScope; 3] = [
+        ApiKeyScope::ReadCatalog,
+        ApiKeyScope::ManageEvents,
+        ApiKeyScope::ManageCatalog,
+    ];
+}
+
+/// Api key for bots and scripts, only the hash of the secret is stored
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    pub secret_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl Validate for ApiKey {
+    fn validate(&self, validator: &mut Validator) {
+        validator.required("name", &self.name);
+        validator.max_length("name", &self.name, NAME_MAX_LENGTH);
+        validator.check(
+            !self.scopes.is_empty(),
+            "scopes",
+            "required",
+            "Grant at least one scope.",
+        );
+        validator.check(
+            self.expires_at
+                .is_none_or(|expires_at| expires_at > self.created_at),
+            "expiresAt",
+            "out_of_range",
+            "Must be in the future.",
+        );
+    }
+}
+
+fn random_string(len: usize) -> String {
+    let mut rand_bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut rand_bytes);
+
+    BASE64_URL_SAFE_NO_PAD.encode(rand_bytes)
+}
+
+fn hex_id() -> String {
+    format!("{:016x}", OsRng.next_u64())
+}
+
+fn hash_secret(secret: &str) -> String {
+    BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(secret))
+}
+
+impl ApiKey {
+    /// Generate a key, return it with the `mb_<id>_<secret>` token shown only once
+    pub fn generate(
+        name: String,
+        scopes: Vec<ApiKeyScope>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> (Self, String) {
+        // Base64 url may contain `_`, ids use hex
+        let id = hex_id();
+        let secret = random_string(32);
+        let token = format!("{}_{}_{}", API_KEY_PREFIX, id, secret);
+
+        let api_key = Self {
+            id,
+            name,
+            secret_hash: hash_secret(&secret),
+            scopes,
+            created_at: Utc::now(),
+            expires_at,
+            last_used_at: None,
+        };
+
+        (api_key, token)
+    }
+
+    /// Split a token into the key id and the secret
+    pub fn parse(token: &str) -> Result<(&str, &str), Error> {
+        match token.splitn(3, '_').collect::<Vec<_>>().as_slice() {
+            [API_KEY_PREFIX, id, secret] => Ok((id, secret)),
+            _ => Err(Error::InvalidToken),
+        }
+    }
+
+    /// Check the secret and the expiry
+    pub fn verify(&self, secret: &str, now: DateTime<Utc>) -> Result<(), Error> {
+        let hash = hash_secret(secret);
+        let matches = hash.len() == self.secret_hash.len()
+            && hash
+                .bytes()
+                .zip(self.secret_hash.bytes())
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0;
+        if !matches || self.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(Error::InvalidToken);
+        }
+
+        Ok(())
+    }
+
+    /// Record the use, return false when the last used time is recent enough
+    pub fn touch(&mut self, now: DateTime<Utc>) -> bool {
+        if self.last_used_at.is_some_and(|last_used_at| {
+            now - last_used_at < Duration::minutes(LAST_USED_RESOLUTION_MINUTES)
+        }) {
+            return false;
+        }
+
+        self.last_used_at = Some(now);
+        true
+    }
+}
+
+#[async_trait]
+pub trait ApiKeyRepository: Interface {
+    async fn insert(&self, api_key: &ApiKey) -> Result<(), Error>;
+    async fn get(&self, id: &str) -> Result<ApiKey, Error>;
+    async fn list(&self) -> Result<Vec<ApiKey>, Error>;
+    /// Save the last used time
+    async fn update_last_used(&self, api_key: &ApiKey) -> Result<(), Error>;
+    /// Delete the key, return item not found if it does not exist
+    async fn delete(&self, id: &str) -> Result<(), Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::{ApiKey, ApiKeyScope};
+
+    #[test]
+    fn api_key() {
+        let now = Utc::now();
+        let (mut api_key, token) = ApiKey::generate(
+            "bot".to_string(),
+            vec![ApiKeyScope::ReadCatalog],
+            Some(now + Duration::days(1)),
+        );
+
+        let (id, secret) = ApiKey::parse(&token).unwrap();
+        assert_eq!(id, api_key.id);
+        assert!(api_key.verify(secret, now).is_ok());
+        assert!(api_key.verify("wrong", now).is_err());
+        assert!(api_key.verify(secret, now + Duration::days(2)).is_err());
+
+        assert!(api_key.touch(now));
+        assert!(!api_key.touch(now + Duration::minutes(1)));
+        assert!(api_key.touch(now + Duration::minutes(10)));
+    }
+}
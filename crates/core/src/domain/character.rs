@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use shaku::Interface;
+
+use crate::{
+    member::MemberId,
+    validation::{Validate, Validator},
+    Error,
+};
+
+/// Character names are two words of up to 15 letters
+const NAME_MAX_LENGTH: usize = 32;
+
+/// In game character of a member, sign ups are checked against its home world
+#[derive(Debug, Clone)]
+pub struct Character {
+    pub member_id: MemberId,
+    pub name: String,
+    pub world_id: String,
+}
+
+impl Validate for Character {
+    fn validate(&self, validator: &mut Validator) {
+        validator.required("name", &self.name);
+        validator.max_length("name", &self.name, NAME_MAX_LENGTH);
+        validator.required("worldId", &self.world_id);
+    }
+}
+
+#[async_trait]
+pub trait CharacterRepository: Interface {
+    /// Return item not found if the member did not set a character
+    async fn get_character(&self, member_id: MemberId) -> Result<Character, Error>;
+    /// Create or replace the character of the member
+    async fn set_character(&self, character: &Character) -> Result<(), Error>;
+}
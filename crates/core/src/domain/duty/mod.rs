@@ -2,6 +2,11 @@ mod repo;
 
 pub use repo::*;
 
+use crate::validation::{Validate, Validator};
+
+/// Longest name shown in the catalog
+pub const NAME_MAX_LENGTH: usize = 100;
+
 #[derive(Debug, Clone)]
 pub struct DutyCategory {
     pub id: String,
@@ -28,3 +33,50 @@ pub struct DutyPhrase {
     pub name: String,
     pub progression: f64,
 }
+
+impl Validate for DutyCategory {
+    fn validate(&self, validator: &mut Validator) {
+        validator.required("id", &self.id);
+        validator.required("name", &self.name);
+        validator.max_length("name", &self.name, NAME_MAX_LENGTH);
+        validator.check(
+            self.parent.as_deref() != Some(self.id.as_str()),
+            "parent",
+            "invalid",
+            "A category cannot be its own parent.",
+        );
+    }
+}
+
+impl Validate for Duty {
+    fn validate(&self, validator: &mut Validator) {
+        validator.required("id", &self.id);
+        validator.required("category", &self.category);
+        validator.required("name", &self.name);
+        validator.max_length("name", &self.name, NAME_MAX_LENGTH);
+        if let Some(short_name) = &self.short_name {
+            validator.max_length("shortName", short_name, NAME_MAX_LENGTH);
+        }
+        validator.required("image", &self.image);
+        validator.check(
+            self.patch.is_finite() && self.patch >= 0.0,
+            "patch",
+            "out_of_range",
+            "Must be a positive number.",
+        );
+    }
+}
+
+/// Phrases are sorted by progression, so it must be a number
+impl Validate for DutyPhrase {
+    fn validate(&self, validator: &mut Validator) {
+        validator.required("name", &self.name);
+        validator.max_length("name", &self.name, NAME_MAX_LENGTH);
+        validator.check(
+            self.progression.is_finite() && self.progression >= 0.0,
+            "progression",
+            "out_of_range",
+            "Must be a positive number.",
+        );
+    }
+}
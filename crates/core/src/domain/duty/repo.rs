@@ -27,7 +27,7 @@ pub trait DutyRepository: Interface {
     /// Create or update if exists
     async fn insert_categories(&self, categories: &[DutyCategory]) -> Result<(), Error>;
     /// Insert a duty
-    /// Create or update if exists
+    /// Create or update if exists, replacing its phrases
     async fn insert_duties(&self, duties: &[(Duty, &[DutyPhrase])]) -> Result<(), Error>;
 
     /// List all categories or list categories by parent
@@ -39,6 +39,10 @@ pub trait DutyRepository: Interface {
     /// Return the parent category, all sub categories and all duties
     async fn list_categories_and_duties(&self, parent: &str) -> Result<CategoriesAndDuties, Error>;
 
+    /// Delete a duty and its phrases
+    /// Return item not found if the duty does not exist
+    async fn delete_duty(&self, duty_id: &str) -> Result<(), Error>;
+
     /// Get a duty will pharse and breadcrumbs categories
     async fn get_duty(&self, duty_id: &str) -> Result<DutyDetail, Error>;
 }
@@ -1,16 +1,57 @@
+use crate::validation::FieldError;
+
 #[derive(Debug, Clone)]
 pub enum Error {
     ItemNotFound,
+    /// Item changed since it was read, reload and retry
+    Conflict,
 
+    /// Caller must sign in
+    Unauthenticated,
     InvalidToken,
-
+    /// Caller is known but not allowed
     Forbidden,
 
+    /// Event status does not allow the change, e.g. joining a finished event
+    InvalidEventStatus,
+
+    /// Oauth2 state is missing, expired or does not match the PKCE verifier
+    InvalidOAuth2State,
+    /// Discord rejected the authorization code, expired or already used
+    InvalidOAuth2Code,
+
+    /// Discord user is not in the guild
+    NotGuildMember,
+    /// Guild member lacks the roles required to sign in
+    MissingRequiredRole,
+    /// Member suspended by an admin
+    MemberSuspended,
+    /// Discord is down or rate limiting us
+    DiscordUnavailable,
+
+    /// Input rejected by the domain rules, every invalid field is listed
+    Validation(Vec<FieldError>),
+
+    /// External service call failed, like DynamoDB or a webhook
+    Upstream(String),
     Internal(String),
 }
 
 impl Error {
+    pub fn upstream(msg: impl ToString) -> Self {
+        Self::Upstream(msg.to_string())
+    }
+
     pub fn internal(msg: impl ToString) -> Self {
         Self::Internal(msg.to_string())
     }
+
+    /// Validation error of a single field
+    pub fn invalid_field(path: &str, code: &'static str, message: impl ToString) -> Self {
+        Self::Validation(vec![FieldError {
+            path: path.to_string(),
+            code,
+            message: message.to_string(),
+        }])
+    }
 }
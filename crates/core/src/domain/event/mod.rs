@@ -0,0 +1,708 @@
+mod repo;
+
+pub use repo::*;
+
+use chrono::{DateTime, Duration, Utc};
+use rand::{rngs::OsRng, RngCore};
+
+use crate::{
+    member::MemberId,
+    validation::{Validate, Validator},
+    world::{DataCenter, Location, WorldDetail},
+    Error,
+};
+
+pub const TITLE_MAX_LENGTH: usize = 100;
+pub const DESCRIPTION_MAX_LENGTH: usize = 2000;
+/// From a light party to an alliance
+const SLOTS_MIN: usize = 2;
+const SLOTS_MAX: usize = 24;
+/// Job ids of the jobs catalog, resources/jobs.yml
+pub const JOBS: &[&str] = &[
+    "pld", "war", "drk", "gnb", "whm", "ast", "sch", "sge", "mnk", "drg", "nin", "sam", "rpr",
+    "vpr", "brd", "mch", "dnc", "blm", "smn", "rdm", "pct",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventStatus {
+    Draft,
+    /// Published, only reachable by its link
+    Private,
+    /// Published and listed
+    Public,
+    InProcess,
+    Finished,
+    Cancelled,
+}
+
+impl EventStatus {
+    pub const ALL: [EventStatus; 6] = [
+        EventStatus::Draft,
+        EventStatus::Private,
+        EventStatus::Public,
+        EventStatus::InProcess,
+        EventStatus::Finished,
+        EventStatus::Cancelled,
+    ];
+
+    pub fn is_draft(&self) -> bool {
+        self == &EventStatus::Draft
+    }
+
+    pub fn is_published(&self) -> bool {
+        self != &EventStatus::Draft
+    }
+
+    /// Published and not started yet, members can sign up
+    pub fn is_open(&self) -> bool {
+        matches!(self, EventStatus::Private | EventStatus::Public)
+    }
+
+    /// Finished or cancelled, the event does not change anymore
+    pub fn is_closed(&self) -> bool {
+        matches!(self, EventStatus::Finished | EventStatus::Cancelled)
+    }
+}
+
+/// Who can find the event once published
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventVisibility {
+    Private,
+    Public,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventInfo {
+    pub title: String,
+    pub description: Option<String>,
+}
+
+impl Validate for EventInfo {
+    fn validate(&self, validator: &mut Validator) {
+        validator.required("title", &self.title);
+        validator.max_length("title", &self.title, TITLE_MAX_LENGTH);
+        if let Some(description) = &self.description {
+            validator.max_length("description", description, DESCRIPTION_MAX_LENGTH);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSchedule {
+    pub start_at: DateTime<Utc>,
+    pub duration: Duration,
+}
+
+impl EventSchedule {
+    pub fn end_at(&self) -> DateTime<Utc> {
+        self.start_at + self.duration
+    }
+}
+
+impl Validate for EventSchedule {
+    fn validate(&self, validator: &mut Validator) {
+        validator.check(
+            self.start_at >= Utc::now() + Duration::minutes(15),
+            "startAt",
+            "out_of_range",
+            "Must be at least 15 minutes in the future.",
+        );
+        validator.check(
+            self.duration >= Duration::minutes(15) && self.duration <= Duration::hours(24),
+            "duration",
+            "out_of_range",
+            "Must be between 15 minutes and 24 hours.",
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSlot {
+    /// Job ids accepted in the slot, any job if empty
+    pub jobs: Vec<String>,
+}
+
+impl EventSlot {
+    pub fn accepts(&self, job: &str) -> bool {
+        self.jobs.is_empty() || self.jobs.iter().any(|slot_job| slot_job == job)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignUpStatus {
+    /// In the roster, holding the slot at the index
+    Accepted { slot: usize },
+    /// Promoted in order when a slot accepting the job opens
+    Waitlisted,
+}
+
+#[derive(Debug, Clone)]
+pub struct SignUp {
+    pub member_id: MemberId,
+    pub job: String,
+    /// Home world of the character at sign up
+    pub world_id: String,
+    pub status: SignUpStatus,
+    pub signed_up_at: DateTime<Utc>,
+}
+
+impl SignUp {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self.status, SignUpStatus::Accepted { .. })
+    }
+}
+
+/// Everything the host sets before publishing
+#[derive(Debug, Clone)]
+pub struct EventDraft {
+    pub info: EventInfo,
+    pub duty_id: Option<String>,
+    pub schedule: EventSchedule,
+    pub slots: Vec<EventSlot>,
+
+    /// Data center or region the party plays on
+    pub location: Location,
+    /// Language spoken in the party, e.g. `en` or `ja`
+    pub language: String,
+    /// Accept characters from other data centers, who travel for the event
+    pub travel_allowed: bool,
+    /// Open a discussion thread under the announcement, with the roster in it
+    pub thread_enabled: bool,
+}
+
+impl Validate for EventDraft {
+    fn validate(&self, validator: &mut Validator) {
+        validator.nested("info", &self.info);
+        validator.nested("schedule", &self.schedule);
+        validator.check(
+            (SLOTS_MIN..=SLOTS_MAX).contains(&self.slots.len()),
+            "slots",
+            "out_of_range",
+            format!("Must have between {} and {} slots.", SLOTS_MIN, SLOTS_MAX),
+        );
+        validator.check(
+            self.language.len() == 2 && self.language.chars().all(|c| c.is_ascii_lowercase()),
+            "language",
+            "invalid",
+            "Must be a two letter language code, like en or ja.",
+        );
+    }
+}
+
+/// Event hosted by a member, or by the system for api keys
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub id: String,
+    pub host: Option<MemberId>,
+
+    pub status: EventStatus,
+    pub info: EventInfo,
+    pub duty_id: Option<String>,
+    pub schedule: EventSchedule,
+    pub slots: Vec<EventSlot>,
+
+    pub location: Location,
+    pub language: String,
+    pub travel_allowed: bool,
+    pub thread_enabled: bool,
+
+    /// Accepted and waitlisted members, in sign up order
+    pub sign_ups: Vec<SignUp>,
+
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Message id of the Discord announcement, once posted
+    pub announcement_id: Option<String>,
+    /// Id of the guild scheduled event mirroring it
+    pub scheduled_event_id: Option<String>,
+    /// Id of the discussion thread, once opened
+    pub thread_id: Option<String>,
+    /// Bumped on each save, to detect concurrent changes
+    pub version: u64,
+}
+
+impl Event {
+    /// Create a draft event
+    pub fn new(host: Option<MemberId>, draft: EventDraft) -> Result<Self, Error> {
+        draft.validated()?;
+
+        let now = Utc::now();
+        let id = format!("{:0>13}{:08x}", now.timestamp_millis(), OsRng.next_u32());
+
+        Ok(Self {
+            id,
+            host,
+
+            status: EventStatus::Draft,
+            info: draft.info,
+            duty_id: draft.duty_id,
+            schedule: draft.schedule,
+            slots: draft.slots,
+
+            location: draft.location,
+            language: draft.language,
+            travel_allowed: draft.travel_allowed,
+            thread_enabled: draft.thread_enabled,
+
+            sign_ups: vec![],
+
+            created_at: now,
+            updated_at: now,
+            published_at: None,
+            announcement_id: None,
+            scheduled_event_id: None,
+            thread_id: None,
+            version: 0,
+        })
+    }
+
+    pub fn is_host(&self, member_id: MemberId) -> bool {
+        self.host == Some(member_id)
+    }
+
+    /// Return forbidden unless the member hosts the event
+    pub fn require_host(&self, member_id: MemberId) -> Result<(), Error> {
+        if self.is_host(member_id) {
+            Ok(())
+        } else {
+            Err(Error::Forbidden)
+        }
+    }
+
+    /// Members in the roster, in sign up order
+    pub fn roster(&self) -> Vec<MemberId> {
+        self.sign_ups
+            .iter()
+            .filter(|sign_up| sign_up.is_accepted())
+            .map(|sign_up| sign_up.member_id)
+            .collect()
+    }
+
+    pub fn open_slots(&self) -> usize {
+        self.slots.len().saturating_sub(self.roster().len())
+    }
+
+    pub fn sign_up_of(&self, member_id: MemberId) -> Option<&SignUp> {
+        self.sign_ups
+            .iter()
+            .find(|sign_up| sign_up.member_id == member_id)
+    }
+
+    /// Check if the event is listed for the data center and language filters
+    pub fn matches(&self, data_center: Option<&DataCenter>, language: Option<&str>) -> bool {
+        data_center.is_none_or(|data_center| self.location.includes(data_center))
+            && language.is_none_or(|language| self.language == language)
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+
+    fn require_status(&self, allowed: bool) -> Result<(), Error> {
+        if allowed {
+            Ok(())
+        } else {
+            Err(Error::InvalidEventStatus)
+        }
+    }
+
+    /// Replace the draft, only before publishing
+    pub fn update(&mut self, draft: EventDraft) -> Result<(), Error> {
+        self.require_status(self.status.is_draft())?;
+        draft.validated()?;
+
+        self.info = draft.info;
+        self.duty_id = draft.duty_id;
+        self.schedule = draft.schedule;
+        self.slots = draft.slots;
+        self.location = draft.location;
+        self.language = draft.language;
+        self.travel_allowed = draft.travel_allowed;
+        self.thread_enabled = draft.thread_enabled;
+        self.touch();
+
+        Ok(())
+    }
+
+    pub fn publish(&mut self, visibility: EventVisibility) -> Result<(), Error> {
+        self.require_status(self.status.is_draft())?;
+        // The start may have passed since the draft was saved
+        self.schedule.validated()?;
+
+        self.status = match visibility {
+            EventVisibility::Private => EventStatus::Private,
+            EventVisibility::Public => EventStatus::Public,
+        };
+        self.touch();
+        self.published_at = Some(self.updated_at);
+
+        Ok(())
+    }
+
+    /// Edit the info of a published event, return the previous info
+    pub fn edit_info(&mut self, info: EventInfo) -> Result<EventInfo, Error> {
+        self.require_status(self.status.is_open())?;
+        info.validated()?;
+
+        let old = std::mem::replace(&mut self.info, info);
+        self.touch();
+        Ok(old)
+    }
+
+    /// Reschedule a published event, return the previous schedule
+    pub fn edit_schedule(&mut self, schedule: EventSchedule) -> Result<EventSchedule, Error> {
+        self.require_status(self.status.is_open())?;
+        schedule.validated()?;
+
+        let old = std::mem::replace(&mut self.schedule, schedule);
+        self.touch();
+        Ok(old)
+    }
+
+    /// Cancel a published event before it starts
+    pub fn cancel(&mut self) -> Result<(), Error> {
+        self.require_status(self.status.is_open())?;
+
+        self.status = EventStatus::Cancelled;
+        self.touch();
+        Ok(())
+    }
+
+    /// Cancel an event that is not over, even once started, for admins fixing a broken event
+    pub fn force_cancel(&mut self) -> Result<(), Error> {
+        self.require_status(!self.status.is_closed())?;
+
+        self.status = EventStatus::Cancelled;
+        self.touch();
+        Ok(())
+    }
+
+    /// Start the event, automatic starts wait for the scheduled start
+    pub fn start(&mut self, manually: bool, now: DateTime<Utc>) -> Result<(), Error> {
+        self.require_status(self.status.is_open() && (manually || self.schedule.start_at <= now))?;
+
+        self.status = EventStatus::InProcess;
+        self.touch();
+        Ok(())
+    }
+
+    /// Finish the event, automatic finishes wait for the scheduled end
+    pub fn finish(&mut self, manually: bool, now: DateTime<Utc>) -> Result<(), Error> {
+        self.require_status(
+            self.status == EventStatus::InProcess && (manually || self.schedule.end_at() <= now),
+        )?;
+
+        self.status = EventStatus::Finished;
+        self.touch();
+        Ok(())
+    }
+
+    /// Finish a published event, even before it started, for admins fixing a broken event
+    pub fn force_finish(&mut self) -> Result<(), Error> {
+        self.require_status(self.status.is_open() || self.status == EventStatus::InProcess)?;
+
+        self.status = EventStatus::Finished;
+        self.touch();
+        Ok(())
+    }
+
+    /// Free slot accepting the job, slots reserved to some jobs are filled first
+    fn free_slot(&self, job: &str) -> Option<usize> {
+        let taken = self
+            .sign_ups
+            .iter()
+            .filter_map(|sign_up| match sign_up.status {
+                SignUpStatus::Accepted { slot } => Some(slot),
+                SignUpStatus::Waitlisted => None,
+            })
+            .collect::<Vec<_>>();
+
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(i, slot)| !taken.contains(i) && slot.accepts(job))
+            .min_by_key(|(i, slot)| (slot.jobs.is_empty(), *i))
+            .map(|(i, _)| i)
+    }
+
+    /// Sign up with the job, checked against the home world of the character
+    /// Accepted if a slot is free for the job, waitlisted otherwise
+    pub fn sign_up(
+        &mut self,
+        member_id: MemberId,
+        job: &str,
+        home: &WorldDetail,
+        now: DateTime<Utc>,
+    ) -> Result<SignUpStatus, Error> {
+        self.require_status(self.status.is_open())?;
+
+        let mut validator = Validator::default();
+        validator.check(
+            self.sign_up_of(member_id).is_none(),
+            "event",
+            "already_signed_up",
+            "You already signed up for this event.",
+        );
+        if job.trim().is_empty() {
+            validator.error("job", "required", "Choose a job.");
+        } else if !JOBS.contains(&job) {
+            validator.error("job", "invalid", "Unknown job.");
+        } else {
+            validator.check(
+                self.slots.iter().any(|slot| slot.accepts(job)),
+                "job",
+                "not_accepted",
+                "No slot of the event accepts this job.",
+            );
+        }
+        validator.check(
+            self.location.admits(home, self.travel_allowed),
+            "character",
+            "location",
+            "Your character's data center cannot join this event.",
+        );
+        validator.finish()?;
+
+        let status = match self.free_slot(job) {
+            Some(slot) => SignUpStatus::Accepted { slot },
+            None => SignUpStatus::Waitlisted,
+        };
+        self.sign_ups.push(SignUp {
+            member_id,
+            job: job.to_string(),
+            world_id: home.world.id.clone(),
+            status,
+            signed_up_at: now,
+        });
+        self.touch();
+
+        Ok(status)
+    }
+
+    /// Leave the roster or the waitlist
+    /// The freed slot goes to the first waitlisted member with a job it accepts, who is returned
+    pub fn leave(&mut self, member_id: MemberId) -> Result<Option<MemberId>, Error> {
+        self.require_status(self.status.is_open())?;
+
+        let index = self
+            .sign_ups
+            .iter()
+            .position(|sign_up| sign_up.member_id == member_id)
+            .ok_or(Error::ItemNotFound)?;
+        let sign_up = self.sign_ups.remove(index);
+        self.touch();
+
+        let SignUpStatus::Accepted { slot } = sign_up.status else {
+            return Ok(None);
+        };
+        let promoted = self.sign_ups.iter_mut().find(|sign_up| {
+            sign_up.status == SignUpStatus::Waitlisted && self.slots[slot].accepts(&sign_up.job)
+        });
+
+        Ok(promoted.map(|promoted| {
+            promoted.status = SignUpStatus::Accepted { slot };
+            promoted.member_id
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::{
+        Event, EventDraft, EventInfo, EventSchedule, EventSlot, EventStatus, EventVisibility,
+        SignUpStatus,
+    };
+    use crate::{
+        world::{DataCenter, Location, World, WorldDetail},
+        Error,
+    };
+
+    fn home(data_center: &str) -> WorldDetail {
+        WorldDetail {
+            world: World {
+                id: "world".to_string(),
+                data_center: data_center.to_string(),
+                name: "World".to_string(),
+                sort: 0,
+            },
+            data_center: DataCenter {
+                id: data_center.to_string(),
+                region: "eu".to_string(),
+                name: data_center.to_string(),
+                sort: 0,
+            },
+        }
+    }
+
+    fn event(slots: Vec<Vec<&str>>, travel_allowed: bool) -> Event {
+        let mut event = Event::new(
+            Some(1),
+            EventDraft {
+                info: EventInfo {
+                    title: "UWU prog".to_string(),
+                    description: None,
+                },
+                duty_id: None,
+                schedule: EventSchedule {
+                    start_at: Utc::now() + Duration::days(1),
+                    duration: Duration::hours(2),
+                },
+                slots: slots
+                    .into_iter()
+                    .map(|jobs| EventSlot {
+                        jobs: jobs.into_iter().map(str::to_string).collect(),
+                    })
+                    .collect(),
+                location: Location::DataCenter("light".to_string()),
+                language: "en".to_string(),
+                travel_allowed,
+                thread_enabled: false,
+            },
+        )
+        .unwrap();
+        event.publish(EventVisibility::Public).unwrap();
+        event
+    }
+
+    #[test]
+    fn draft_is_validated() {
+        let mut draft = EventDraft {
+            info: EventInfo {
+                title: " ".to_string(),
+                description: None,
+            },
+            duty_id: None,
+            schedule: EventSchedule {
+                start_at: Utc::now(),
+                duration: Duration::hours(2),
+            },
+            slots: vec![],
+            location: Location::Region("eu".to_string()),
+            language: "EN".to_string(),
+            travel_allowed: false,
+            thread_enabled: false,
+        };
+        let Err(Error::Validation(errors)) = Event::new(None, draft.clone()) else {
+            panic!("Expected validation errors");
+        };
+        let paths: Vec<_> = errors.iter().map(|error| error.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["info.title", "schedule.startAt", "slots", "language"]
+        );
+
+        draft.info.title = "Title".to_string();
+        draft.schedule.start_at = Utc::now() + Duration::hours(1);
+        draft.slots = vec![EventSlot { jobs: vec![] }; 8];
+        draft.language = "ja".to_string();
+        assert!(Event::new(None, draft).is_ok());
+    }
+
+    #[test]
+    fn sign_up_checks_location() {
+        let mut event = event(vec![vec![], vec![]], false);
+
+        let Err(Error::Validation(errors)) = event.sign_up(2, "pld", &home("chaos"), Utc::now())
+        else {
+            panic!("Expected validation errors");
+        };
+        assert_eq!(errors[0].path, "character");
+        assert_eq!(
+            event.sign_up(2, "pld", &home("light"), Utc::now()).unwrap(),
+            SignUpStatus::Accepted { slot: 0 }
+        );
+
+        let mut event = self::event(vec![vec![], vec![]], true);
+        assert!(event.sign_up(2, "pld", &home("chaos"), Utc::now()).is_ok());
+    }
+
+    #[test]
+    fn waitlist_promotion() {
+        let mut event = event(vec![vec!["pld", "war"], vec![]], false);
+        let light = home("light");
+        let now = Utc::now();
+
+        assert_eq!(
+            event.sign_up(2, "whm", &light, now).unwrap(),
+            SignUpStatus::Accepted { slot: 1 }
+        );
+        assert_eq!(
+            event.sign_up(3, "pld", &light, now).unwrap(),
+            SignUpStatus::Accepted { slot: 0 }
+        );
+        assert_eq!(
+            event.sign_up(4, "sch", &light, now).unwrap(),
+            SignUpStatus::Waitlisted
+        );
+        assert_eq!(
+            event.sign_up(5, "war", &light, now).unwrap(),
+            SignUpStatus::Waitlisted
+        );
+        assert!(event.sign_up(2, "whm", &light, now).is_err());
+        for (job, code) in [("", "required"), ("xyz", "invalid")] {
+            let Err(Error::Validation(errors)) = event.sign_up(6, job, &light, now) else {
+                panic!("Expected validation errors");
+            };
+            assert_eq!((errors[0].path.as_str(), errors[0].code), ("job", code));
+        }
+        assert!(event.sign_up(6, "blm", &light, now).is_ok());
+
+        // The tank slot skips the healer waiting first
+        assert_eq!(event.leave(3).unwrap(), Some(5));
+        assert_eq!(event.leave(2).unwrap(), Some(4));
+        assert_eq!(event.leave(4).unwrap(), Some(6));
+        assert_eq!(event.roster(), vec![5, 6]);
+        assert!(matches!(event.leave(3), Err(Error::ItemNotFound)));
+    }
+
+    #[test]
+    fn lifecycle() {
+        let mut event = event(vec![vec![], vec![]], false);
+        let now = Utc::now();
+
+        assert!(matches!(
+            event.publish(EventVisibility::Private),
+            Err(Error::InvalidEventStatus)
+        ));
+        assert!(event.start(false, now).is_err());
+        event.start(true, now).unwrap();
+        assert!(event.cancel().is_err());
+        assert!(event.finish(false, now).is_err());
+        event.finish(false, event.schedule.end_at()).unwrap();
+        assert_eq!(event.status, EventStatus::Finished);
+    }
+
+    #[test]
+    fn forced_close() {
+        let mut event = event(vec![vec![], vec![]], false);
+        let mut published = event.clone();
+
+        event.start(true, Utc::now()).unwrap();
+        event.force_cancel().unwrap();
+        assert_eq!(event.status, EventStatus::Cancelled);
+        assert!(event.force_cancel().is_err());
+        assert!(event.force_finish().is_err());
+
+        published.force_finish().unwrap();
+        assert_eq!(published.status, EventStatus::Finished);
+    }
+
+    #[test]
+    fn listing_filters() {
+        let event = event(vec![vec![], vec![]], false);
+        let data_center = |id: &str, region: &str| DataCenter {
+            id: id.to_string(),
+            region: region.to_string(),
+            name: id.to_string(),
+            sort: 0,
+        };
+
+        assert!(event.matches(None, None));
+        assert!(event.matches(Some(&data_center("light", "eu")), Some("en")));
+        assert!(!event.matches(Some(&data_center("chaos", "eu")), None));
+        assert!(!event.matches(None, Some("ja")));
+    }
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shaku::Interface;
+
+use crate::{outbox::OutboxMessage, Error};
+
+use super::{Event, EventStatus};
+
+#[async_trait]
+pub trait EventRepository: Interface {
+    /// Insert a new event, with the outbox messages of the change
+    async fn insert_event(&self, event: &Event, outbox: &[OutboxMessage]) -> Result<(), Error>;
+
+    async fn get_event(&self, event_id: &str) -> Result<Event, Error>;
+
+    /// Save the event with the outbox messages of the change, and bump its version
+    /// Return conflict if it was saved by someone else since it was read
+    async fn update_event(&self, event: &mut Event, outbox: &[OutboxMessage]) -> Result<(), Error>;
+
+    /// List events with the status, ordered by start
+    /// Only the events starting before the given time if set
+    async fn list_events(
+        &self,
+        status: EventStatus,
+        starting_before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Event>, Error>;
+}
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use shaku::Interface;
+
+use crate::Error;
+
+use super::Member;
+
+/// Sign in through an Oauth2 identity provider, discord unless overridden for development
+#[async_trait]
+pub trait IdentityProvider: Interface {
+    /// Get the Oauth2 URL, with the state and the S256 PKCE challenge
+    fn get_oauth2_url(&self, redirect_uri: &str, state: &str, code_challenge: &str) -> String;
+
+    /// Sign in with the Oauth2 code and the PKCE verifier
+    /// Return not guild member if the user is not in the guild
+    async fn sign_in(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<Member, Error>;
+}
@@ -1,24 +1,35 @@
 use chrono::{DateTime, Utc};
 
 // mod discord;
+mod identity;
+mod oauth2;
 mod repo;
 mod session;
 
 // pub use discord::DiscordClient;
+pub use identity::*;
+pub use oauth2::*;
 pub use repo::*;
 pub use session::*;
 
 pub type MemberId = u64;
+pub type RoleId = u64;
 
 #[derive(Debug, Clone)]
 pub struct Member {
     pub id: MemberId,
     pub display_name: String,
     pub avatar: String,
+    /// Guild role ids, synced from discord
+    pub roles: Vec<RoleId>,
 
     /// Last time the member profile was updated
     pub updated_at: DateTime<Utc>,
     pub joined_at: DateTime<Utc>,
+    /// Set when the member left the guild
+    pub left_at: Option<DateTime<Utc>>,
+    /// Set by an admin, the member cannot sign in until lifted
+    pub suspended_at: Option<DateTime<Utc>>,
 }
 
 impl Member {
@@ -26,6 +37,7 @@ impl Member {
         id: MemberId,
         display_name: String,
         avatar: String,
+        roles: Vec<RoleId>,
         joined_at: DateTime<Utc>,
     ) -> Self {
         let now = Utc::now();
@@ -33,8 +45,19 @@ impl Member {
             id,
             display_name,
             avatar,
+            roles,
             updated_at: now,
             joined_at,
+            left_at: None,
+            suspended_at: None,
         }
     }
+
+    pub fn is_active(&self) -> bool {
+        self.left_at.is_none()
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.suspended_at.is_some()
+    }
 }
@@ -0,0 +1,66 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use shaku::Interface;
+
+use crate::Error;
+
+/// Time to complete the discord authorization, in minutes
+pub const OAUTH2_STATE_LIFETIME_MINUTES: i64 = 10;
+
+/// PKCE pair of a sign in attempt, the verifier stays with the client
+#[derive(Debug, Clone)]
+pub struct Pkce {
+    pub code_verifier: String,
+    pub code_challenge: String,
+}
+
+impl Pkce {
+    pub fn new() -> Self {
+        let mut rand_bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut rand_bytes);
+
+        Self::from_verifier(BASE64_URL_SAFE_NO_PAD.encode(rand_bytes))
+    }
+
+    /// S256 challenge of the verifier
+    pub fn from_verifier(code_verifier: String) -> Self {
+        let code_challenge = BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(&code_verifier));
+
+        Self {
+            code_verifier,
+            code_challenge,
+        }
+    }
+}
+
+impl Default for Pkce {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sign the Oauth2 state, bound to the PKCE challenge of the attempt
+pub trait OAuth2StateSigner: Interface {
+    /// Sign a state valid until the given time
+    fn sign(&self, code_challenge: &str, expires_at: DateTime<Utc>) -> Result<String, Error>;
+    /// Verify the state was issued for the challenge and is not expired
+    /// Return invalid oauth2 state otherwise
+    fn verify(&self, state: &str, code_challenge: &str, now: DateTime<Utc>) -> Result<(), Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pkce;
+
+    #[test]
+    fn code_challenge() {
+        // RFC 7636 appendix B
+        let pkce = Pkce::from_verifier("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string());
+        assert_eq!(
+            pkce.code_challenge,
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+}
@@ -1,23 +1,42 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use shaku::Interface;
 
-use crate::Error;
+use crate::{outbox::OutboxMessage, Error};
 
-use super::{Member, MemberId, MemberSession};
+use super::{AccessToken, Member, MemberId, MemberSession};
 
 #[async_trait]
 pub trait MemberRepository: Interface {
-    /// Insert member and session
+    /// Insert member and session, with the outbox messages in the same transaction
     async fn insert_member_and_session(
         &self,
         member: &Member,
         session: &MemberSession,
+        outbox: &[OutboxMessage],
     ) -> Result<(), Error>;
 
     /// Get member by given id
     async fn get_member(&self, member_id: MemberId) -> Result<Member, Error>;
+    /// Update the member profile
+    async fn update_member(&self, member: &Member) -> Result<(), Error>;
+    /// List members not updated since the given time, oldest first
+    async fn list_stale_members(
+        &self,
+        updated_before: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<Member>, Error>;
     /// Get member session by given id
     async fn get_member_session(&self, session_id: &str) -> Result<MemberSession, Error>;
+    /// Save the session after a renewal
+    /// Return item not found if it was signed out in the meantime
+    async fn update_member_session(&self, session: &MemberSession) -> Result<(), Error>;
+    /// List the sessions of the member
+    async fn list_member_sessions(&self, member_id: MemberId) -> Result<Vec<MemberSession>, Error>;
+    /// Delete the session, signing it out
+    async fn delete_member_session(&self, session_id: &str) -> Result<(), Error>;
+    /// Delete every session of the member
+    async fn revoke_member_sessions(&self, member_id: MemberId) -> Result<(), Error>;
 }
 
 pub trait DemoResponsity: Interface {
@@ -30,16 +49,24 @@ pub trait MemberSessionSigner: Interface {
     fn sign(&self, session_id: &str) -> Result<String, Error>;
     /// Verify token
     fn verify(&self, token: &str) -> Result<String, Error>;
+    /// Sign a self contained access token
+    fn sign_access_token(&self, access_token: &AccessToken) -> Result<String, Error>;
+    /// Verify an access token, return invalid token once expired
+    fn verify_access_token(&self, token: &str, now: DateTime<Utc>) -> Result<AccessToken, Error>;
 }
 
 /// Handling discord related operations
 #[async_trait]
 pub trait DiscordClient: Interface {
-    /// Sign in with discord Oauth2 code
-    async fn sign_in(&self, code: &str, redirect_uri: &str) -> Result<Member, Error>;
     /// Get member by given id
+    /// Return item not found if the member is not in the guild
     async fn get_member(&self, member_id: MemberId) -> Result<Member, Error>;
 
-    /// Get discord Oauth2 URL
-    fn get_oauth2_url(&self, redirect_uri: &str) -> String;
+    /// Verify the Ed25519 signature of an interaction request
+    fn verify_interaction(
+        &self,
+        signature: &str,
+        timestamp: &str,
+        body: &[u8],
+    ) -> Result<(), Error>;
 }
@@ -1,8 +1,26 @@
 use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use rand::{rngs::OsRng, RngCore};
 use sha2::{Digest, Sha256};
 
+/// Lifetime of a new or renewed session, in days
+pub const SESSION_LIFETIME_DAYS: i64 = 30;
+/// Sessions used within this many days of expiry are renewed
+pub const SESSION_RENEW_WITHIN_DAYS: i64 = 7;
+/// Renewal never extends a session past this many days after sign in
+pub const SESSION_MAX_LIFETIME_DAYS: i64 = 90;
+/// Lifetime of an access token, a revoked session stops working within it
+pub const ACCESS_TOKEN_LIFETIME_MINUTES: i64 = 5;
+
+/// Self contained access token, accepted without reading the session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessToken {
+    pub member_id: u64,
+    pub session_id: String,
+    pub roles: Vec<u64>,
+    pub expires_at: DateTime<Utc>,
+}
+
 /// Sign in session
 #[derive(Debug, Clone)]
 pub struct MemberSession {
@@ -20,14 +38,14 @@ impl MemberSession {
         OsRng.fill_bytes(&mut rand_bytes);
 
         let mut hasher = Sha256::new();
-        hasher.update(&rand_bytes);
+        hasher.update(rand_bytes);
         hasher.update(now.timestamp_millis().to_be_bytes());
         let hash = hasher.finalize();
 
-        (BASE64_URL_SAFE_NO_PAD.encode(&hash), now)
+        (BASE64_URL_SAFE_NO_PAD.encode(hash), now)
     }
 
-    pub fn new(member_id: u64, duration: chrono::Duration) -> Self {
+    pub fn new(member_id: u64, duration: Duration) -> Self {
         let (id, now) = Self::gen_id();
 
         Self {
@@ -38,42 +56,54 @@ impl MemberSession {
         }
     }
 
-    // fn sign_signature(session_id: &str, secret: &str) -> Result<String, Error> {
-    //     type HmacSha256 = hmac::Hmac<Sha256>;
-
-    //     let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
-    //         .map_err(|_| Error::internal("Sign signature failed, secret is invalid."))?;
-    //     mac.update(session_id.as_bytes());
-
-    //     Ok(URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()))
-    // }
-
-    // fn verify_signature(session_id: &str, token: &str, secret: &str) -> Result<(), Error> {
-    //     Self::sign_signature(session_id, secret).and_then(|signature| {
-    //         if signature == token {
-    //             Ok(())
-    //         } else {
-    //             Err(Error::InvalidToken)
-    //         }
-    //     })
-    // }
-
-    // pub fn tokenize<S: MemberSessionSigner>(&self, signer: S) -> Result<String, Error> {
-    //     let signature = signer.sign_signature(&self.id)?;
-    //     Ok(format!("{}.{}", self.id, signature))
-    // }
-
-    // /// Verify the token, and return the session id
-    // pub fn verify<S: MemberSessionSigner>(token: &str, signer: S) -> Result<String, Error> {
-    //     let parts: Vec<&str> = token.split('.').collect();
-    //     if parts.len() != 2 {
-    //         return Err(Error::InvalidToken);
-    //     }
-
-    //     let session_id = parts[0];
-    //     let signature = parts[1];
-
-    //     signer.verify_signature(session_id, signature)?;
-    //     Ok(session_id.to_string())
-    // }
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    /// Extend a session used close to its expiry, within the maximum lifetime
+    /// Return false if the session was not extended
+    pub fn renew(&mut self, now: DateTime<Utc>) -> bool {
+        if self.is_expired(now) || self.expires_at - now > Duration::days(SESSION_RENEW_WITHIN_DAYS)
+        {
+            return false;
+        }
+
+        let expires_at = (now + Duration::days(SESSION_LIFETIME_DAYS))
+            .min(self.issued_at + Duration::days(SESSION_MAX_LIFETIME_DAYS));
+        if expires_at <= self.expires_at {
+            return false;
+        }
+
+        self.expires_at = expires_at;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::MemberSession;
+
+    #[test]
+    fn renew() {
+        let now = Utc::now();
+        let mut session = MemberSession::new(1, Duration::days(30));
+
+        // Far from expiry
+        assert!(!session.renew(now + Duration::days(1)));
+
+        // Close to expiry
+        assert!(session.renew(now + Duration::days(25)));
+        assert!(session.expires_at > now + Duration::days(54));
+
+        // Capped by the maximum lifetime
+        assert!(session.renew(now + Duration::days(50)));
+        assert!(session.renew(now + Duration::days(75)));
+        assert_eq!(session.expires_at, session.issued_at + Duration::days(90));
+        assert!(!session.renew(now + Duration::days(85)));
+
+        assert!(session.is_expired(now + Duration::days(91)));
+        assert!(!session.renew(now + Duration::days(91)));
+    }
 }
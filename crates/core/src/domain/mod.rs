@@ -1,7 +1,20 @@
 pub mod access_type;
+pub mod announcement;
+pub mod api_key;
+pub mod character;
 pub mod duty;
 pub mod errors;
+pub mod event;
 pub mod member;
+pub mod notification;
+pub mod outbox;
+pub mod permission;
+pub mod rate_limit;
+pub mod reconfirmation;
+pub mod reminder;
+pub mod scheduled_event;
+pub mod validation;
+pub mod world;
 
 pub use access_type::AccessType;
 pub use errors::Error;
@@ -0,0 +1,425 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use shaku::Interface;
+
+use crate::{
+    event::Event,
+    member::MemberId,
+    outbox::DomainEvent,
+    validation::{Validate, Validator},
+    Error,
+};
+
+/// Direct message sent to a member
+#[derive(Debug, Clone)]
+pub enum DirectMessage {
+    /// Event is starting soon
+    Reminder {
+        event_id: String,
+        title: String,
+        start_at: DateTime<Utc>,
+    },
+    /// Event is starting soon and the roster is not full, sent to the host
+    HostReminder {
+        event_id: String,
+        title: String,
+        start_at: DateTime<Utc>,
+        open_slots: usize,
+    },
+    /// Event moved, the member must confirm before the deadline
+    ReconfirmationRequested {
+        event_id: String,
+        title: String,
+        old_start_at: DateTime<Utc>,
+        new_start_at: DateTime<Utc>,
+        deadline: DateTime<Utc>,
+    },
+    /// A member joined or left the roster, sent to the host
+    RosterChanged {
+        event_id: String,
+        title: String,
+        member_id: MemberId,
+        joined: bool,
+    },
+    /// A slot freed up and the member left the waitlist
+    WaitlistPromoted {
+        event_id: String,
+        title: String,
+        start_at: DateTime<Utc>,
+    },
+    EventCancelled {
+        event_id: String,
+        title: String,
+        start_at: DateTime<Utc>,
+    },
+}
+
+/// Direct message owed to a member for an event change
+#[derive(Debug, Clone)]
+pub struct EventNotice {
+    pub member_id: MemberId,
+    pub kind: NotificationKind,
+    pub message: DirectMessage,
+    /// The event thread already posts the change, members preferring a mention skip the message
+    pub posted_in_thread: bool,
+}
+
+impl EventNotice {
+    /// Notices for the change, the host is not told about their own sign up
+    pub fn for_change(change: &DomainEvent, event: &Event) -> Vec<Self> {
+        let has_thread = event.thread_id.is_some();
+        let roster_change = |member_id: MemberId, joined: bool| {
+            event
+                .host
+                .filter(|host| *host != member_id)
+                .map(|host| EventNotice {
+                    member_id: host,
+                    kind: NotificationKind::RosterChange,
+                    message: DirectMessage::RosterChanged {
+                        event_id: event.id.clone(),
+                        title: event.info.title.clone(),
+                        member_id,
+                        joined,
+                    },
+                    posted_in_thread: has_thread,
+                })
+        };
+
+        match change {
+            DomainEvent::SignUpAccepted {
+                member_id,
+                promoted,
+                ..
+            } => {
+                let mut notices = roster_change(*member_id, true)
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                if *promoted {
+                    notices.push(EventNotice {
+                        member_id: *member_id,
+                        kind: NotificationKind::WaitlistPromotion,
+                        message: DirectMessage::WaitlistPromoted {
+                            event_id: event.id.clone(),
+                            title: event.info.title.clone(),
+                            start_at: event.schedule.start_at,
+                        },
+                        posted_in_thread: has_thread,
+                    });
+                }
+                notices
+            }
+            DomainEvent::SignUpWithdrawn {
+                member_id,
+                accepted: true,
+                ..
+            } => roster_change(*member_id, false).into_iter().collect(),
+            // The thread is archived, so everyone signed up gets the message
+            DomainEvent::EventCancelled { .. } => event
+                .sign_ups
+                .iter()
+                .filter(|sign_up| Some(sign_up.member_id) != event.host)
+                .map(|sign_up| EventNotice {
+                    member_id: sign_up.member_id,
+                    kind: NotificationKind::EventCancelled,
+                    message: DirectMessage::EventCancelled {
+                        event_id: event.id.clone(),
+                        title: event.info.title.clone(),
+                        start_at: event.schedule.start_at,
+                    },
+                    posted_in_thread: false,
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// Send direct messages to members
+#[async_trait]
+pub trait DirectMessenger: Interface {
+    async fn send(&self, member_id: MemberId, message: &DirectMessage) -> Result<(), Error>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    Reminder,
+    RosterChange,
+    Reschedule,
+    WaitlistPromotion,
+    EventCancelled,
+}
+
+impl NotificationKind {
+    pub const ALL: [NotificationKind; 5] = [
+        NotificationKind::Reminder,
+        NotificationKind::RosterChange,
+        NotificationKind::Reschedule,
+        NotificationKind::WaitlistPromotion,
+        NotificationKind::EventCancelled,
+    ];
+}
+
+/// Where a member receives a kind of notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    DirectMessage,
+    /// Mention in the event thread
+    ChannelMention,
+    None,
+}
+
+impl NotificationChannel {
+    /// Whether a direct message is sent, a mention falls back to one without a thread post
+    pub fn sends_direct_message(self, posted_in_thread: bool) -> bool {
+        match self {
+            NotificationChannel::DirectMessage => true,
+            NotificationChannel::ChannelMention => !posted_in_thread,
+            NotificationChannel::None => false,
+        }
+    }
+}
+
+/// Daily range without notifications, in the member timezone
+/// The range wraps around midnight when start is after end
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub timezone: Tz,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        let time = at.with_timezone(&self.timezone).time();
+
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+}
+
+impl Validate for QuietHours {
+    fn validate(&self, validator: &mut Validator) {
+        validator.check(
+            self.start != self.end,
+            "end",
+            "invalid",
+            "Quiet hours must not start and end at the same time.",
+        );
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotificationPreferences {
+    /// Kinds not listed use the default channel
+    pub channels: HashMap<NotificationKind, NotificationChannel>,
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl NotificationPreferences {
+    pub fn channel(&self, kind: NotificationKind) -> NotificationChannel {
+        self.channels
+            .get(&kind)
+            .copied()
+            .unwrap_or(NotificationChannel::DirectMessage)
+    }
+
+    /// Channel to notify on at the given time, none during quiet hours
+    pub fn route(&self, kind: NotificationKind, at: DateTime<Utc>) -> NotificationChannel {
+        match &self.quiet_hours {
+            Some(quiet_hours) if quiet_hours.contains(at) => NotificationChannel::None,
+            _ => self.channel(kind),
+        }
+    }
+
+    /// Channel for a notice the member must act on, it is never dropped
+    /// Quiet hours and opting out of the kind fall back to a direct message
+    pub fn route_required(&self, kind: NotificationKind, at: DateTime<Utc>) -> NotificationChannel {
+        match self.route(kind, at) {
+            NotificationChannel::None => NotificationChannel::DirectMessage,
+            channel => channel,
+        }
+    }
+}
+
+impl Validate for NotificationPreferences {
+    fn validate(&self, validator: &mut Validator) {
+        if let Some(quiet_hours) = &self.quiet_hours {
+            validator.nested("quietHours", quiet_hours);
+        }
+    }
+}
+
+#[async_trait]
+pub trait NotificationPreferenceRepository: Interface {
+    /// Return the default preferences if the member never set them
+    async fn get_preferences(&self, member_id: MemberId) -> Result<NotificationPreferences, Error>;
+    async fn set_preferences(
+        &self,
+        member_id: MemberId,
+        preferences: &NotificationPreferences,
+    ) -> Result<(), Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveTime, TimeZone, Utc};
+
+    use super::{
+        EventNotice, NotificationChannel, NotificationKind, NotificationPreferences, QuietHours,
+    };
+    use crate::{
+        event::{Event, EventDraft, EventInfo, EventSchedule, EventSlot, SignUp, SignUpStatus},
+        outbox::DomainEvent,
+        world::Location,
+    };
+
+    fn event(host: u64, sign_ups: &[u64]) -> Event {
+        let mut event = Event::new(
+            Some(host),
+            EventDraft {
+                info: EventInfo {
+                    title: "UWU prog".to_string(),
+                    description: None,
+                },
+                duty_id: None,
+                schedule: EventSchedule {
+                    start_at: Utc::now() + Duration::days(1),
+                    duration: Duration::hours(2),
+                },
+                slots: vec![EventSlot { jobs: vec![] }; 8],
+                location: Location::DataCenter("light".to_string()),
+                language: "en".to_string(),
+                travel_allowed: false,
+                thread_enabled: false,
+            },
+        )
+        .unwrap();
+        event.sign_ups = sign_ups
+            .iter()
+            .enumerate()
+            .map(|(slot, member_id)| SignUp {
+                member_id: *member_id,
+                job: "war".to_string(),
+                world_id: "world".to_string(),
+                status: SignUpStatus::Accepted { slot },
+                signed_up_at: Utc::now(),
+            })
+            .collect();
+        event
+    }
+
+    #[test]
+    fn notices_for_change() {
+        let event = event(1, &[1, 2, 3]);
+
+        let promoted = EventNotice::for_change(
+            &DomainEvent::SignUpAccepted {
+                event_id: event.id.clone(),
+                member_id: 3,
+                promoted: true,
+            },
+            &event,
+        );
+        let promoted: Vec<_> = promoted
+            .iter()
+            .map(|notice| (notice.member_id, notice.kind))
+            .collect();
+        assert_eq!(
+            promoted,
+            [
+                (1, NotificationKind::RosterChange),
+                (3, NotificationKind::WaitlistPromotion)
+            ]
+        );
+
+        // The host joining their own event
+        let host_joined = EventNotice::for_change(
+            &DomainEvent::SignUpAccepted {
+                event_id: event.id.clone(),
+                member_id: 1,
+                promoted: false,
+            },
+            &event,
+        );
+        assert!(host_joined.is_empty());
+
+        let cancelled = EventNotice::for_change(
+            &DomainEvent::EventCancelled {
+                event_id: event.id.clone(),
+            },
+            &event,
+        );
+        let cancelled: Vec<_> = cancelled.iter().map(|notice| notice.member_id).collect();
+        assert_eq!(cancelled, [2, 3]);
+    }
+
+    #[test]
+    fn mention_falls_back_to_direct_message() {
+        assert!(NotificationChannel::ChannelMention.sends_direct_message(false));
+        assert!(!NotificationChannel::ChannelMention.sends_direct_message(true));
+        assert!(NotificationChannel::DirectMessage.sends_direct_message(true));
+        assert!(!NotificationChannel::None.sends_direct_message(false));
+    }
+
+    #[test]
+    fn required_notices_ignore_quiet_hours() {
+        let preferences = NotificationPreferences {
+            channels: [
+                (
+                    NotificationKind::Reschedule,
+                    NotificationChannel::ChannelMention,
+                ),
+                (NotificationKind::Reminder, NotificationChannel::None),
+            ]
+            .into(),
+            quiet_hours: Some(QuietHours {
+                timezone: chrono_tz::UTC,
+                start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            }),
+        };
+        let night = Utc.with_ymd_and_hms(2024, 7, 1, 23, 0, 0).unwrap();
+        let day = Utc.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            preferences.route(NotificationKind::Reschedule, night),
+            NotificationChannel::None
+        );
+        assert_eq!(
+            preferences.route_required(NotificationKind::Reschedule, night),
+            NotificationChannel::DirectMessage
+        );
+        assert_eq!(
+            preferences.route_required(NotificationKind::Reschedule, day),
+            NotificationChannel::ChannelMention
+        );
+        assert_eq!(
+            preferences.route_required(NotificationKind::Reminder, day),
+            NotificationChannel::DirectMessage
+        );
+    }
+
+    #[test]
+    fn quiet_hours_wrap_midnight() {
+        let quiet_hours = QuietHours {
+            timezone: chrono_tz::Europe::Paris,
+            start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+        };
+
+        // 23:30 and 07:30 in Paris, summer time
+        assert!(quiet_hours.contains(Utc.with_ymd_and_hms(2024, 7, 1, 21, 30, 0).unwrap()));
+        assert!(quiet_hours.contains(Utc.with_ymd_and_hms(2024, 7, 1, 5, 30, 0).unwrap()));
+        // 12:00 in Paris
+        assert!(!quiet_hours.contains(Utc.with_ymd_and_hms(2024, 7, 1, 10, 0, 0).unwrap()));
+    }
+}
@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use rand::{rngs::OsRng, Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use shaku::Interface;
+
+use crate::{member::MemberId, Error};
+
+/// Something that happened in the domain, delivered to the sinks after commit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DomainEvent {
+    MemberSignedIn {
+        member_id: MemberId,
+    },
+    EventPublished {
+        event_id: String,
+    },
+    /// Title or description changed
+    EventEdited {
+        event_id: String,
+    },
+    EventCancelled {
+        event_id: String,
+    },
+    EventStarted {
+        event_id: String,
+    },
+    EventFinished {
+        event_id: String,
+    },
+    ScheduleChanged {
+        event_id: String,
+        old_start_at: DateTime<Utc>,
+        new_start_at: DateTime<Utc>,
+    },
+    /// The member got a slot, on sign up or promoted from the waitlist
+    SignUpAccepted {
+        event_id: String,
+        member_id: MemberId,
+        promoted: bool,
+    },
+    /// The member left the roster, or the waitlist when not accepted
+    SignUpWithdrawn {
+        event_id: String,
+        member_id: MemberId,
+        accepted: bool,
+    },
+}
+
+impl DomainEvent {
+    /// Event the change is about, none for member changes
+    pub fn event_id(&self) -> Option<&str> {
+        match self {
+            DomainEvent::MemberSignedIn { .. } => None,
+            DomainEvent::EventPublished { event_id }
+            | DomainEvent::EventEdited { event_id }
+            | DomainEvent::EventCancelled { event_id }
+            | DomainEvent::EventStarted { event_id }
+            | DomainEvent::EventFinished { event_id }
+            | DomainEvent::ScheduleChanged { event_id, .. }
+            | DomainEvent::SignUpAccepted { event_id, .. }
+            | DomainEvent::SignUpWithdrawn { event_id, .. } => Some(event_id),
+        }
+    }
+}
+
+/// Domain event waiting in the outbox
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    /// Time ordered id
+    pub id: String,
+    pub event: DomainEvent,
+    pub created_at: DateTime<Utc>,
+
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    /// Sinks that already received the message, skipped on retry
+    pub delivered_sinks: Vec<String>,
+    pub last_error: Option<String>,
+}
+
+impl OutboxMessage {
+    pub fn new(event: DomainEvent) -> Self {
+        let now = Utc::now();
+        let id = format!("{:0>13}{:016x}", now.timestamp_millis(), OsRng.next_u64());
+
+        Self {
+            id,
+            event,
+            created_at: now,
+
+            attempts: 0,
+            next_attempt_at: now,
+            delivered_sinks: vec![],
+            last_error: None,
+        }
+    }
+}
+
+/// Delay before the next attempt, doubles each attempt up to an hour, with up to 25% jitter
+pub fn backoff(attempts: u32) -> Duration {
+    let base = Duration::seconds(30 * 2i64.pow(attempts.saturating_sub(1).min(7)));
+    let base = base.min(Duration::hours(1));
+    let jitter = OsRng.gen_range(0..=base.num_milliseconds() / 4);
+
+    base + Duration::milliseconds(jitter)
+}
+
+#[async_trait]
+pub trait OutboxRepository: Interface {
+    /// List messages due for delivery, oldest first
+    async fn list_due(&self, now: DateTime<Utc>, limit: usize)
+        -> Result<Vec<OutboxMessage>, Error>;
+    /// Lease the message until the given time, so other dispatchers skip it
+    /// Return false if the message was leased or updated by another dispatcher
+    async fn lease(&self, message: &OutboxMessage, until: DateTime<Utc>) -> Result<bool, Error>;
+    /// Save the message after an attempt
+    async fn update(&self, message: &OutboxMessage) -> Result<(), Error>;
+    /// Remove a delivered message
+    async fn delete(&self, message_id: &str) -> Result<(), Error>;
+    /// Move the message to the dead letters
+    async fn dead_letter(&self, message: &OutboxMessage) -> Result<(), Error>;
+}
+
+/// Destination of outbox messages, e.g. Discord or a webhook
+#[async_trait]
+pub trait OutboxSink: Interface {
+    /// Unique name, recorded once the sink received a message
+    fn name(&self) -> &str;
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), Error>;
+}
+
+/// Sink keeping the Discord side of events in sync
+/// A separate interface, so it is registered next to the webhook sink
+pub trait DiscordSink: OutboxSink {}
+
+/// Sink sending the direct messages owed to members for event changes
+pub trait NotificationSink: OutboxSink {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::{backoff, DomainEvent};
+
+    #[test]
+    fn backoff_doubles_up_to_an_hour() {
+        for (attempts, base) in [(1, 30), (2, 60), (3, 120), (8, 3600), (30, 3600)] {
+            let delay = backoff(attempts);
+            let base = Duration::seconds(base);
+
+            assert!(delay >= base);
+            assert!(delay <= base + base / 4);
+        }
+    }
+
+    #[test]
+    fn event_changes_carry_the_event_id() {
+        let event = DomainEvent::SignUpAccepted {
+            event_id: "event".to_string(),
+            member_id: 1,
+            promoted: true,
+        };
+
+        assert_eq!(event.event_id(), Some("event"));
+        assert_eq!(
+            DomainEvent::MemberSignedIn { member_id: 1 }.event_id(),
+            None
+        );
+    }
+}
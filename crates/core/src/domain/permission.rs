@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use shaku::Interface;
+
+use crate::{
+    member::{MemberId, RoleId},
+    AccessType, Error,
+};
+
+/// Application permission, granted by guild roles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    CreateEvent,
+    CreatePublicEvent,
+    Moderate,
+    /// Grant every other permission
+    Admin,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 4] = [
+        Permission::CreateEvent,
+        Permission::CreatePublicEvent,
+        Permission::Moderate,
+        Permission::Admin,
+    ];
+}
+
+/// Mapping from discord role id to the permissions it grants
+/// Deserialized from `{ "<role id>": ["create_event", ...] }`
+#[serde_as]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RolePermissions(
+    #[serde_as(as = "HashMap<DisplayFromStr, _>")] HashMap<RoleId, Vec<Permission>>,
+);
+
+impl RolePermissions {
+    pub fn new(permissions: HashMap<RoleId, Vec<Permission>>) -> Self {
+        Self(permissions)
+    }
+
+    /// Permissions granted by the roles, without duplicates
+    pub fn permissions(&self, roles: &[RoleId]) -> Vec<Permission> {
+        let granted = roles
+            .iter()
+            .filter_map(|role| self.0.get(role))
+            .flatten()
+            .collect::<Vec<_>>();
+        if granted.contains(&&Permission::Admin) {
+            return Permission::ALL.to_vec();
+        }
+
+        Permission::ALL
+            .into_iter()
+            .filter(|permission| granted.contains(&permission))
+            .collect()
+    }
+}
+
+/// Check permissions in use cases, instead of ad-hoc checks
+#[async_trait]
+pub trait PermissionService: Interface {
+    /// Permissions of the access type, system and admins have every permission and guest none
+    async fn permissions(&self, access_type: &AccessType) -> Result<Vec<Permission>, Error>;
+
+    /// Access type of a signed in member, admin from the configured user ids or an admin role
+    async fn access_type(&self, member_id: MemberId) -> Result<AccessType, Error>;
+
+    /// Access type of a member with the given roles, e.g. from an access token
+    fn access_type_with_roles(&self, member_id: MemberId, roles: &[RoleId]) -> AccessType;
+
+    /// Check the guild roles allow signing in
+    /// Any of the required roles is enough, everyone is allowed if none are required
+    fn check_sign_in(&self, roles: &[RoleId]) -> Result<(), Error>;
+
+    /// Return forbidden if the access type lacks the permission
+    async fn require(&self, access_type: &AccessType, permission: Permission) -> Result<(), Error> {
+        if self.permissions(access_type).await?.contains(&permission) {
+            Ok(())
+        } else {
+            Err(Error::Forbidden)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{Permission, RolePermissions};
+
+    #[test]
+    fn role_permissions() {
+        let role_permissions = RolePermissions::new(HashMap::from([
+            (1, vec![Permission::CreateEvent]),
+            (2, vec![Permission::CreateEvent, Permission::Moderate]),
+            (3, vec![Permission::Admin]),
+        ]));
+
+        assert_eq!(
+            role_permissions.permissions(&[1, 2, 4]),
+            vec![Permission::CreateEvent, Permission::Moderate]
+        );
+        assert_eq!(role_permissions.permissions(&[3]), Permission::ALL.to_vec());
+        assert!(role_permissions.permissions(&[]).is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use shaku::Interface;
+
+use crate::Error;
+
+/// Requests allowed per fixed window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u64,
+    pub window_seconds: i64,
+}
+
+impl RateLimit {
+    pub fn per_minute(requests: u64) -> Self {
+        Self {
+            requests,
+            window_seconds: 60,
+        }
+    }
+
+    /// End of the window containing the time, counters reset there
+    pub fn window_end(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let window = self.window_seconds.max(1);
+        let start = now.timestamp().div_euclid(window) * window;
+
+        DateTime::from_timestamp(start + window, 0).unwrap_or(now)
+    }
+
+    /// Time to wait before the next request is allowed, none while under the limit
+    pub fn retry_after(&self, hits: u64, now: DateTime<Utc>) -> Option<Duration> {
+        if hits <= self.requests {
+            return None;
+        }
+
+        Some(self.window_end(now) - now)
+    }
+}
+
+/// Counters of the rate limits, shared by every instance of the api
+#[async_trait]
+pub trait RateLimitStore: Interface {
+    /// Count a request for the key in the window ending at the given time
+    /// Return the number of requests in the window, this one included
+    async fn hit(&self, key: &str, window_end: DateTime<Utc>) -> Result<u64, Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::RateLimit;
+
+    #[test]
+    fn retry_after() {
+        let limit = RateLimit::per_minute(2);
+        let now = Utc.with_ymd_and_hms(2024, 7, 1, 12, 0, 45).unwrap();
+
+        assert_eq!(
+            limit.window_end(now),
+            Utc.with_ymd_and_hms(2024, 7, 1, 12, 1, 0).unwrap()
+        );
+        assert_eq!(limit.retry_after(2, now), None);
+        assert_eq!(limit.retry_after(3, now), Some(Duration::seconds(15)));
+    }
+}
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use shaku::Interface;
+
+use crate::{member::MemberId, Error};
+
+/// Check if a schedule change moves the start far enough to ask the roster again
+pub fn needs_reconfirmation(
+    old_start_at: DateTime<Utc>,
+    new_start_at: DateTime<Utc>,
+    threshold: Duration,
+) -> bool {
+    (new_start_at - old_start_at).abs() > threshold
+}
+
+/// Deadline to answer, the window after the change but never after the new start
+pub fn reconfirmation_deadline(
+    changed_at: DateTime<Utc>,
+    new_start_at: DateTime<Utc>,
+    window: Duration,
+) -> DateTime<Utc> {
+    (changed_at + window).min(new_start_at)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconfirmationStatus {
+    Pending,
+    Confirmed,
+    /// Member left the roster
+    Dropped,
+    /// Deadline passed without answer, the slot opens to the waitlist
+    Expired,
+}
+
+/// Accepted member asked to confirm after the event was rescheduled
+#[derive(Debug, Clone)]
+pub struct Reconfirmation {
+    pub event_id: String,
+    pub member_id: MemberId,
+    pub new_start_at: DateTime<Utc>,
+    pub deadline: DateTime<Utc>,
+    pub status: ReconfirmationStatus,
+}
+
+impl Reconfirmation {
+    /// Answer the request, the answer is ignored once it is no longer pending
+    pub fn respond(&mut self, confirm: bool, now: DateTime<Utc>) {
+        if self.status != ReconfirmationStatus::Pending {
+            return;
+        }
+
+        self.status = if now > self.deadline {
+            ReconfirmationStatus::Expired
+        } else if confirm {
+            ReconfirmationStatus::Confirmed
+        } else {
+            ReconfirmationStatus::Dropped
+        };
+    }
+}
+
+#[async_trait]
+pub trait ReconfirmationRepository: Interface {
+    /// Insert the requests, replacing the previous ones for the same members
+    async fn insert(&self, reconfirmations: &[Reconfirmation]) -> Result<(), Error>;
+    async fn get(&self, event_id: &str, member_id: MemberId) -> Result<Reconfirmation, Error>;
+    /// Update a pending request
+    /// Return conflict if it was answered or expired in the meantime
+    async fn update(&self, reconfirmation: &Reconfirmation) -> Result<(), Error>;
+    /// List pending requests with the deadline passed
+    async fn list_expired(&self, now: DateTime<Utc>) -> Result<Vec<Reconfirmation>, Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::{needs_reconfirmation, Reconfirmation, ReconfirmationStatus};
+
+    #[test]
+    fn reconfirmation() {
+        let now = Utc::now();
+        let threshold = Duration::hours(1);
+
+        assert!(!needs_reconfirmation(
+            now,
+            now + Duration::minutes(30),
+            threshold
+        ));
+        assert!(needs_reconfirmation(
+            now,
+            now - Duration::hours(2),
+            threshold
+        ));
+
+        let mut reconfirmation = Reconfirmation {
+            event_id: "event".to_string(),
+            member_id: 1,
+            new_start_at: now + Duration::days(1),
+            deadline: now + Duration::hours(12),
+            status: ReconfirmationStatus::Pending,
+        };
+        reconfirmation.respond(true, now + Duration::hours(13));
+        assert_eq!(reconfirmation.status, ReconfirmationStatus::Expired);
+
+        // Answer is final
+        reconfirmation.respond(true, now);
+        assert_eq!(reconfirmation.status, ReconfirmationStatus::Expired);
+    }
+}
@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use shaku::Interface;
+
+use crate::{member::MemberId, Error};
+
+/// Get the reminder offset due for an event starting at `start_at`
+/// Only the closest passed offset is due, so a late sweep does not send a "24h" reminder 10 minutes before start
+pub fn due_offset(
+    start_at: DateTime<Utc>,
+    offsets: &[Duration],
+    now: DateTime<Utc>,
+) -> Option<Duration> {
+    if now >= start_at {
+        return None;
+    }
+
+    offsets
+        .iter()
+        .filter(|offset| now >= start_at - **offset)
+        .min()
+        .copied()
+}
+
+#[async_trait]
+pub trait ReminderRepository: Interface {
+    /// Mark the reminder as sent
+    /// Return false if it was already claimed, e.g. by an overlapping sweep
+    async fn claim(
+        &self,
+        event_id: &str,
+        member_id: MemberId,
+        offset: Duration,
+    ) -> Result<bool, Error>;
+    /// Release a claimed reminder, so the next sweep can retry it
+    async fn release(
+        &self,
+        event_id: &str,
+        member_id: MemberId,
+        offset: Duration,
+    ) -> Result<(), Error>;
+
+    /// Opt out of reminders for an event, or for all events if none
+    /// The event is given with its end, its opt out can be dropped some time after
+    async fn set_opt_out(
+        &self,
+        member_id: MemberId,
+        event: Option<(&str, DateTime<Utc>)>,
+        opt_out: bool,
+    ) -> Result<(), Error>;
+    /// Check if the member opted out for the event, or for all events
+    async fn is_opted_out(&self, member_id: MemberId, event_id: &str) -> Result<bool, Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use super::due_offset;
+
+    #[test]
+    fn due_offset_picks_closest() {
+        let now = Utc::now();
+        let offsets = [Duration::hours(24), Duration::minutes(30)];
+
+        assert_eq!(due_offset(now + Duration::hours(25), &offsets, now), None);
+        assert_eq!(
+            due_offset(now + Duration::hours(23), &offsets, now),
+            Some(Duration::hours(24))
+        );
+        assert_eq!(
+            due_offset(now + Duration::minutes(10), &offsets, now),
+            Some(Duration::minutes(30))
+        );
+        assert_eq!(due_offset(now - Duration::minutes(1), &offsets, now), None);
+    }
+}
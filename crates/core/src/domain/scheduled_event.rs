@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shaku::Interface;
+
+use crate::{
+    event::{Event, EventStatus},
+    world::Location,
+    Error,
+};
+
+/// Event mirrored to the guild scheduled events
+#[derive(Debug, Clone)]
+pub struct ScheduledEvent {
+    pub event_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Where the event takes place, e.g. the data center
+    pub location: String,
+    pub start_at: DateTime<Utc>,
+    pub end_at: DateTime<Utc>,
+}
+
+impl ScheduledEvent {
+    pub fn of(event: &Event) -> Self {
+        Self {
+            event_id: event.id.clone(),
+            name: event.info.title.clone(),
+            description: event.info.description.clone(),
+            location: match &event.location {
+                Location::DataCenter(id) | Location::Region(id) => id.clone(),
+            },
+            start_at: event.schedule.start_at,
+            end_at: event.schedule.end_at(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduledEventStatus {
+    Scheduled,
+    Active,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduledEventEnd {
+    Finished,
+    Cancelled,
+}
+
+/// Change bringing the scheduled event in line with the event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MirrorChange {
+    Create,
+    Update,
+    End(ScheduledEventEnd),
+    Keep,
+}
+
+impl MirrorChange {
+    /// Compare the event with its scheduled event, none if it was never created or was deleted
+    /// Only public events are mirrored, one ended or deleted on discord is created again
+    pub fn plan(status: EventStatus, mirrored: Option<&ScheduledEventStatus>) -> Self {
+        let live = matches!(
+            mirrored,
+            Some(ScheduledEventStatus::Scheduled | ScheduledEventStatus::Active)
+        );
+
+        match status {
+            EventStatus::Public if live => MirrorChange::Update,
+            EventStatus::Public => MirrorChange::Create,
+            EventStatus::Finished if live => MirrorChange::End(ScheduledEventEnd::Finished),
+            EventStatus::Cancelled if live => MirrorChange::End(ScheduledEventEnd::Cancelled),
+            _ => MirrorChange::Keep,
+        }
+    }
+}
+
+/// Mirror events as guild scheduled events
+#[async_trait]
+pub trait ScheduledEventMirror: Interface {
+    /// Create the scheduled event and return its id
+    async fn create(&self, event: &ScheduledEvent) -> Result<String, Error>;
+    /// Update name, description and schedule
+    async fn update(&self, id: &str, event: &ScheduledEvent) -> Result<(), Error>;
+    /// End the scheduled event
+    async fn end(&self, id: &str, end: ScheduledEventEnd) -> Result<(), Error>;
+
+    /// Get the status of the scheduled event
+    /// Return none if it was deleted on discord, so the sync can recreate it
+    async fn status(&self, id: &str) -> Result<Option<ScheduledEventStatus>, Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MirrorChange, ScheduledEventEnd, ScheduledEventStatus};
+    use crate::event::EventStatus;
+
+    #[test]
+    fn plan_recovers_drift() {
+        use ScheduledEventStatus::*;
+
+        assert_eq!(
+            MirrorChange::plan(EventStatus::Public, None),
+            MirrorChange::Create
+        );
+        assert_eq!(
+            MirrorChange::plan(EventStatus::Public, Some(&Scheduled)),
+            MirrorChange::Update
+        );
+        // Ended or deleted on discord while the event is still open
+        assert_eq!(
+            MirrorChange::plan(EventStatus::Public, Some(&Cancelled)),
+            MirrorChange::Create
+        );
+        assert_eq!(
+            MirrorChange::plan(EventStatus::Cancelled, Some(&Scheduled)),
+            MirrorChange::End(ScheduledEventEnd::Cancelled)
+        );
+        assert_eq!(
+            MirrorChange::plan(EventStatus::Finished, Some(&Active)),
+            MirrorChange::End(ScheduledEventEnd::Finished)
+        );
+        assert_eq!(
+            MirrorChange::plan(EventStatus::Finished, Some(&Completed)),
+            MirrorChange::Keep
+        );
+        assert_eq!(
+            MirrorChange::plan(EventStatus::Private, None),
+            MirrorChange::Keep
+        );
+        assert_eq!(
+            MirrorChange::plan(EventStatus::InProcess, None),
+            MirrorChange::Keep
+        );
+    }
+}
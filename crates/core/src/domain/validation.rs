@@ -0,0 +1,147 @@
+use crate::Error;
+
+/// Invalid field of an input, the path locates it like `duties[0].phrases[1].name`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub path: String,
+    /// Stable code for clients, like `required` or `too_long`
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// Domain rules of an input
+pub trait Validate {
+    fn validate(&self, validator: &mut Validator);
+
+    /// Return a validation error with every invalid field
+    fn validated(&self) -> Result<(), Error> {
+        let mut validator = Validator::default();
+        self.validate(&mut validator);
+        validator.finish()
+    }
+}
+
+/// Collect the field errors of an input, instead of stopping at the first one
+#[derive(Debug, Default)]
+pub struct Validator {
+    prefix: String,
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    fn path(&self, field: &str) -> String {
+        match (self.prefix.is_empty(), field.starts_with('[')) {
+            (true, _) | (false, true) => format!("{}{}", self.prefix, field),
+            (false, false) => format!("{}.{}", self.prefix, field),
+        }
+    }
+
+    pub fn error(&mut self, field: &str, code: &'static str, message: impl ToString) {
+        let path = self.path(field);
+        self.errors.push(FieldError {
+            path,
+            code,
+            message: message.to_string(),
+        });
+    }
+
+    /// Add the error when the rule does not hold
+    pub fn check(&mut self, valid: bool, field: &str, code: &'static str, message: impl ToString) {
+        if !valid {
+            self.error(field, code, message);
+        }
+    }
+
+    pub fn required(&mut self, field: &str, value: &str) {
+        self.check(
+            !value.trim().is_empty(),
+            field,
+            "required",
+            "Must not be empty.",
+        );
+    }
+
+    pub fn max_length(&mut self, field: &str, value: &str, max: usize) {
+        self.check(
+            value.chars().count() <= max,
+            field,
+            "too_long",
+            format!("Must be at most {} characters.", max),
+        );
+    }
+
+    /// Validate a nested value, its errors are prefixed by the field
+    pub fn nested(&mut self, field: &str, value: &impl Validate) {
+        let path = self.path(field);
+        let prefix = std::mem::replace(&mut self.prefix, path);
+        value.validate(self);
+        self.prefix = prefix;
+    }
+
+    /// Validate each item, prefixed by the field and the index
+    pub fn each<'v, V: Validate + 'v>(
+        &mut self,
+        field: &str,
+        values: impl IntoIterator<Item = &'v V>,
+    ) {
+        for (i, value) in values.into_iter().enumerate() {
+            self.nested(&format!("{}[{}]", field, i), value);
+        }
+    }
+
+    pub fn finish(self) -> Result<(), Error> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(self.errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Validate, Validator};
+    use crate::Error;
+
+    struct Phrase(&'static str);
+
+    impl Validate for Phrase {
+        fn validate(&self, validator: &mut Validator) {
+            validator.required("name", self.0);
+        }
+    }
+
+    struct Duty(&'static str, Vec<Phrase>);
+
+    impl Validate for Duty {
+        fn validate(&self, validator: &mut Validator) {
+            validator.max_length("name", self.0, 4);
+            validator.each("phrases", &self.1);
+        }
+    }
+
+    #[test]
+    fn field_errors() {
+        assert!(Duty("duty", vec![Phrase("p1")]).validated().is_ok());
+
+        let mut validator = Validator::default();
+        validator.each(
+            "duties",
+            &[Duty("too long", vec![Phrase("p1"), Phrase(" ")])],
+        );
+        let Err(Error::Validation(errors)) = validator.finish() else {
+            panic!("Expected validation errors");
+        };
+        let paths: Vec<_> = errors
+            .iter()
+            .map(|error| (error.path.as_str(), error.code))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                ("duties[0].name", "too_long"),
+                ("duties[0].phrases[1].name", "required")
+            ]
+        );
+    }
+}
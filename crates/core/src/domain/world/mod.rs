@@ -0,0 +1,84 @@
+mod repo;
+
+pub use repo::*;
+
+/// Game region, e.g. North America or Japan
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub id: String,
+    pub name: String,
+    pub sort: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct DataCenter {
+    pub id: String,
+    pub region: String,
+    pub name: String,
+    pub sort: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct World {
+    pub id: String,
+    pub data_center: String,
+    pub name: String,
+    pub sort: i32,
+}
+
+/// Where an event takes place
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Location {
+    DataCenter(String),
+    Region(String),
+}
+
+impl Location {
+    /// Check if the data center is the location or part of its region
+    pub fn includes(&self, data_center: &DataCenter) -> bool {
+        match self {
+            Location::DataCenter(id) => &data_center.id == id,
+            Location::Region(region) => &data_center.region == region,
+        }
+    }
+
+    /// Check if a character from the given home world can join
+    /// Travel allowed accepts characters from anywhere
+    pub fn admits(&self, home: &WorldDetail, travel_allowed: bool) -> bool {
+        travel_allowed || self.includes(&home.data_center)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DataCenter, Location, World, WorldDetail};
+
+    fn home(data_center: &str, region: &str) -> WorldDetail {
+        WorldDetail {
+            world: World {
+                id: "world".to_string(),
+                data_center: data_center.to_string(),
+                name: "World".to_string(),
+                sort: 0,
+            },
+            data_center: DataCenter {
+                id: data_center.to_string(),
+                region: region.to_string(),
+                name: data_center.to_string(),
+                sort: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn location_admits() {
+        let light = Location::DataCenter("light".to_string());
+        let europe = Location::Region("eu".to_string());
+
+        assert!(light.admits(&home("light", "eu"), false));
+        assert!(!light.admits(&home("chaos", "eu"), false));
+        assert!(light.admits(&home("aether", "na"), true));
+        assert!(europe.admits(&home("chaos", "eu"), false));
+        assert!(!europe.admits(&home("aether", "na"), false));
+    }
+}
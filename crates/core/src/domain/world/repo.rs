@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use shaku::Interface;
+
+use crate::Error;
+
+use super::{DataCenter, Region, World};
+
+/// The whole world catalog, each list ordered by sort
+#[derive(Debug)]
+pub struct WorldCatalog {
+    pub regions: Vec<Region>,
+    pub data_centers: Vec<DataCenter>,
+    pub worlds: Vec<World>,
+}
+
+/// World with its data center
+#[derive(Debug, Clone)]
+pub struct WorldDetail {
+    pub world: World,
+    pub data_center: DataCenter,
+}
+
+#[async_trait]
+pub trait WorldRepository: Interface {
+    /// Insert regions, data centers and worlds
+    /// Create or update if exists
+    async fn insert_catalog(
+        &self,
+        regions: &[Region],
+        data_centers: &[DataCenter],
+        worlds: &[World],
+    ) -> Result<(), Error>;
+
+    /// Get all regions, data centers and worlds
+    async fn get_catalog(&self) -> Result<WorldCatalog, Error>;
+
+    /// Get a world with its data center
+    async fn get_world(&self, world_id: &str) -> Result<WorldDetail, Error>;
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api_key::{ApiKey, ApiKeyRepository},
+    AccessType, Error,
+};
+
+use super::UseCase;
+
+/// Resolve the access type of an api key
+pub struct AuthenticateApiKey<'a> {
+    pub api_key_repo: &'a dyn ApiKeyRepository,
+}
+
+impl<'a> AuthenticateApiKey<'a> {
+    async fn run(&self, token: &str) -> Result<AccessType, Error> {
+        let (id, secret) = ApiKey::parse(token)?;
+        let mut api_key = match self.api_key_repo.get(id).await {
+            Ok(api_key) => api_key,
+            Err(Error::ItemNotFound) => return Err(Error::InvalidToken),
+            Err(e) => return Err(e),
+        };
+
+        let now = Utc::now();
+        api_key.verify(secret, now)?;
+        if api_key.touch(now) {
+            self.api_key_repo.update_last_used(&api_key).await?;
+        }
+
+        Ok(AccessType::ApiKey(api_key.scopes))
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for AuthenticateApiKey<'a> {
+    type Input = &'a str;
+    type Response = AccessType;
+
+    async fn guest_execute(&self, token: Self::Input) -> Result<Self::Response, Error> {
+        self.run(token).await
+    }
+}
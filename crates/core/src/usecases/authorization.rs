@@ -1,7 +1,8 @@
 use async_trait::async_trait;
+use chrono::Utc;
 
 use crate::{
-    member::{MemberRepository, MemberSession, MemberSessionSigner},
+    member::{MemberId, MemberRepository, MemberSessionSigner, RoleId},
     Error,
 };
 
@@ -12,23 +13,60 @@ pub struct Authorization<'a> {
     pub member_session_signer: &'a dyn MemberSessionSigner,
 }
 
+/// Signed in member of the request
+#[derive(Debug, Clone)]
+pub struct Authorized {
+    pub member_id: MemberId,
+    pub session_id: String,
+    /// Guild roles carried by an access token, none for a session token
+    pub roles: Option<Vec<RoleId>>,
+}
+
 impl<'a> Authorization<'a> {
-    async fn run(&self, token: &str) -> Result<Option<MemberSession>, Error> {
+    async fn run(&self, token: &str) -> Result<Option<Authorized>, Error> {
+        let now = Utc::now();
+
+        // Access tokens are trusted until they expire, without reading the session
+        if let Ok(access_token) = self.member_session_signer.verify_access_token(token, now) {
+            return Ok(Some(Authorized {
+                member_id: access_token.member_id,
+                session_id: access_token.session_id,
+                roles: Some(access_token.roles),
+            }));
+        }
+
         let session_id = self.member_session_signer.verify(token)?;
-        let session = match self.member_repo.get_member_session(&session_id).await {
-            Ok(session) => Some(session),
-            Err(Error::ItemNotFound) => None,
+        let mut session = match self.member_repo.get_member_session(&session_id).await {
+            Ok(session) => session,
+            Err(Error::ItemNotFound) => return Ok(None),
             Err(e) => return Err(e),
         };
 
-        Ok(session)
+        // Expired items stay in the table until the TTL removes them
+        if session.is_expired(now) {
+            return Ok(None);
+        }
+        if session.renew(now) {
+            match self.member_repo.update_member_session(&session).await {
+                Ok(()) => {}
+                // Signed out while the request was running
+                Err(Error::ItemNotFound) => return Ok(None),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(Some(Authorized {
+            member_id: session.member_id,
+            session_id: session.id,
+            roles: None,
+        }))
     }
 }
 
 #[async_trait]
 impl<'a> UseCase for Authorization<'a> {
     type Input = &'a str;
-    type Response = Option<MemberSession>;
+    type Response = Option<Authorized>;
 
     async fn guest_execute(&self, token: Self::Input) -> Result<Self::Response, Error> {
         self.run(token).await
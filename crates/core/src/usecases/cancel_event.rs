@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+
+use crate::{
+    api_key::ApiKeyScope,
+    event::{Event, EventRepository},
+    member::MemberId,
+    outbox::{DomainEvent, OutboxMessage},
+    permission::{Permission, PermissionService},
+    AccessType, Error,
+};
+
+use super::UseCase;
+
+/// Cancel a published event before it starts
+/// Moderators can cancel events they do not host, admins can cancel started events
+pub struct CancelEvent<'a> {
+    pub event_repo: &'a dyn EventRepository,
+    pub permission_service: &'a dyn PermissionService,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub event_id: String,
+}
+
+impl<'a> CancelEvent<'a> {
+    async fn run(&self, mut event: Event, forced: bool) -> Result<Event, Error> {
+        if forced {
+            event.force_cancel()?;
+        } else {
+            event.cancel()?;
+        }
+
+        let message = OutboxMessage::new(DomainEvent::EventCancelled {
+            event_id: event.id.clone(),
+        });
+        self.event_repo.update_event(&mut event, &[message]).await?;
+        Ok(event)
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for CancelEvent<'a> {
+    type Input = Input;
+    type Response = Event;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageEvents)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        self.run(event, false).await
+    }
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        if !event.is_host(member_id) {
+            self.permission_service
+                .require(&AccessType::Member(member_id), Permission::Moderate)
+                .await?;
+        }
+
+        self.run(event, false).await
+    }
+
+    async fn admin_execute(
+        &self,
+        _member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        self.run(event, true).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CancelEvent, Input};
+    use crate::{
+        event::EventVisibility,
+        permission::Permission,
+        usecases::{
+            test_support::{block_on, event, StubEventRepo, StubPermissions},
+            UseCase,
+        },
+        AccessType, Error,
+    };
+
+    #[test]
+    fn moderators_cancel_events_they_do_not_host() {
+        let mut event = event(1);
+        event.publish(EventVisibility::Private).unwrap();
+        let event_repo = StubEventRepo(event);
+        let run = |member_id, permissions: Vec<Permission>| {
+            let permission_service = StubPermissions(permissions);
+            let cancel_event = CancelEvent {
+                event_repo: &event_repo,
+                permission_service: &permission_service,
+            };
+            block_on(cancel_event.execute(
+                &AccessType::Member(member_id),
+                Input {
+                    event_id: event_repo.0.id.clone(),
+                },
+            ))
+        };
+
+        assert!(matches!(
+            run(2, vec![Permission::CreateEvent]),
+            Err(Error::Forbidden)
+        ));
+        assert!(run(2, vec![Permission::Moderate]).is_ok());
+        assert!(run(1, vec![]).is_ok());
+    }
+}
@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api_key::{ApiKey, ApiKeyRepository, ApiKeyScope},
+    member::MemberId,
+    validation::Validate,
+    Error,
+};
+
+use super::UseCase;
+
+pub struct CreateApiKey<'a> {
+    pub api_key_repo: &'a dyn ApiKeyRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub api_key: ApiKey,
+    /// Shown once, only the hash is stored
+    pub token: String,
+}
+
+#[async_trait]
+impl<'a> UseCase for CreateApiKey<'a> {
+    type Input = Input;
+    type Response = Response;
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let (api_key, token) = ApiKey::generate(input.name, input.scopes, input.expires_at);
+        api_key.validated()?;
+        self.api_key_repo.insert(&api_key).await?;
+
+        Ok(Response { api_key, token })
+    }
+
+    async fn admin_execute(
+        &self,
+        _member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.system_execute(input).await
+    }
+}
@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+
+use crate::{
+    api_key::ApiKeyScope,
+    duty::DutyRepository,
+    event::{Event, EventDraft, EventRepository},
+    member::MemberId,
+    permission::{Permission, PermissionService},
+    validation::Validator,
+    world::{Location, WorldRepository},
+    AccessType, Error,
+};
+
+use super::UseCase;
+
+/// Create a draft event, hosted by the member or by the system for api keys
+/// Members need the create event permission
+pub struct CreateEvent<'a> {
+    pub event_repo: &'a dyn EventRepository,
+    pub duty_repo: &'a dyn DutyRepository,
+    pub world_repo: &'a dyn WorldRepository,
+    pub permission_service: &'a dyn PermissionService,
+}
+
+impl<'a> CreateEvent<'a> {
+    /// Check the duty and the location exist
+    async fn check_references(&self, draft: &EventDraft) -> Result<(), Error> {
+        let mut validator = Validator::default();
+
+        if let Some(duty_id) = &draft.duty_id {
+            match self.duty_repo.get_duty(duty_id).await {
+                Ok(_) => {}
+                Err(Error::ItemNotFound) => {
+                    validator.error("dutyId", "not_found", "The duty does not exist.")
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let catalog = self.world_repo.get_catalog().await?;
+        let exists = match &draft.location {
+            Location::DataCenter(id) => catalog.data_centers.iter().any(|dc| &dc.id == id),
+            Location::Region(id) => catalog.regions.iter().any(|region| &region.id == id),
+        };
+        validator.check(
+            exists,
+            "location",
+            "not_found",
+            "The data center or region does not exist.",
+        );
+
+        validator.finish()
+    }
+
+    async fn run(&self, host: Option<MemberId>, draft: EventDraft) -> Result<Event, Error> {
+        let event = Event::new(host, draft.clone())?;
+        self.check_references(&draft).await?;
+
+        self.event_repo.insert_event(&event, &[]).await?;
+        Ok(event)
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for CreateEvent<'a> {
+    type Input = EventDraft;
+    type Response = Event;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageEvents)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        self.run(None, input).await
+    }
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.permission_service
+            .require(&AccessType::Member(member_id), Permission::CreateEvent)
+            .await?;
+
+        self.run(Some(member_id), input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CreateEvent;
+    use crate::{
+        permission::Permission,
+        usecases::{
+            test_support::{block_on, draft, event, EmptyCatalog, StubEventRepo, StubPermissions},
+            UseCase,
+        },
+        AccessType, Error,
+    };
+
+    #[test]
+    fn members_need_the_create_event_permission() {
+        let event_repo = StubEventRepo(event(1));
+        let run = |permissions: Vec<Permission>| {
+            let permission_service = StubPermissions(permissions);
+            let create_event = CreateEvent {
+                event_repo: &event_repo,
+                duty_repo: &EmptyCatalog,
+                world_repo: &EmptyCatalog,
+                permission_service: &permission_service,
+            };
+            block_on(create_event.execute(&AccessType::Member(2), draft()))
+        };
+
+        assert!(matches!(run(vec![]), Err(Error::Forbidden)));
+        // Allowed through, the location is not in the empty catalog
+        assert!(matches!(
+            run(vec![Permission::CreateEvent]),
+            Err(Error::Validation(_))
+        ));
+    }
+}
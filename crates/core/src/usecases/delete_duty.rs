@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+
+use crate::{api_key::ApiKeyScope, duty::DutyRepository, member::MemberId, Error};
+
+use super::UseCase;
+
+pub struct DeleteDuty<'a> {
+    pub duty_repo: &'a dyn DutyRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub duty_id: String,
+}
+
+#[async_trait]
+impl<'a> UseCase for DeleteDuty<'a> {
+    type Input = Input;
+    type Response = ();
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageCatalog)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        self.duty_repo.delete_duty(&input.duty_id).await
+    }
+
+    async fn admin_execute(
+        &self,
+        _member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.system_execute(input).await
+    }
+}
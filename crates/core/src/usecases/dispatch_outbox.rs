@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    outbox::{backoff, OutboxMessage, OutboxRepository, OutboxSink},
+    Error,
+};
+
+use super::UseCase;
+
+/// Deliver due outbox messages to every sink
+/// Failed messages are retried with backoff, then moved to the dead letters
+pub struct DispatchOutbox<'a> {
+    pub outbox_repo: &'a dyn OutboxRepository,
+    pub sinks: Vec<&'a dyn OutboxSink>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub now: DateTime<Utc>,
+    pub limit: usize,
+    /// Give up after this many attempts
+    pub max_attempts: u32,
+    /// How long a dispatcher keeps a message before others can pick it up
+    pub lease: Duration,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Response {
+    pub delivered: usize,
+    pub retried: usize,
+    pub dead_lettered: usize,
+}
+
+impl<'a> DispatchOutbox<'a> {
+    /// Deliver to the sinks that did not receive the message yet
+    async fn deliver(&self, message: &mut OutboxMessage) -> Result<(), Error> {
+        for sink in &self.sinks {
+            if message
+                .delivered_sinks
+                .iter()
+                .any(|name| name == sink.name())
+            {
+                continue;
+            }
+
+            sink.deliver(message).await?;
+            message.delivered_sinks.push(sink.name().to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for DispatchOutbox<'a> {
+    type Input = Input;
+    type Response = Response;
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let mut response = Response::default();
+
+        let messages = self.outbox_repo.list_due(input.now, input.limit).await?;
+        for mut message in messages {
+            let lease_until = input.now + input.lease;
+            if !self.outbox_repo.lease(&message, lease_until).await? {
+                continue;
+            }
+            message.next_attempt_at = lease_until;
+
+            match self.deliver(&mut message).await {
+                Ok(()) => {
+                    self.outbox_repo.delete(&message.id).await?;
+                    response.delivered += 1;
+                }
+                Err(e) => {
+                    message.attempts += 1;
+                    message.last_error = Some(format!("{:?}", e));
+
+                    if message.attempts >= input.max_attempts {
+                        self.outbox_repo.dead_letter(&message).await?;
+                        response.dead_lettered += 1;
+                    } else {
+                        message.next_attempt_at = input.now + backoff(message.attempts);
+                        self.outbox_repo.update(&message).await?;
+                        response.retried += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+
+use crate::{
+    api_key::ApiKeyScope,
+    event::{Event, EventInfo, EventRepository, EventSchedule},
+    member::MemberId,
+    outbox::{DomainEvent, OutboxMessage},
+    Error,
+};
+
+use super::UseCase;
+
+/// Edit a published event before it starts
+pub struct EditEvent<'a> {
+    pub event_repo: &'a dyn EventRepository,
+}
+
+#[derive(Debug, Clone)]
+pub enum EventEdit {
+    Info(EventInfo),
+    Schedule(EventSchedule),
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub event_id: String,
+    pub edit: EventEdit,
+}
+
+impl<'a> EditEvent<'a> {
+    async fn run(&self, mut event: Event, edit: EventEdit) -> Result<Event, Error> {
+        let change = match edit {
+            EventEdit::Info(info) => {
+                event.edit_info(info)?;
+                DomainEvent::EventEdited {
+                    event_id: event.id.clone(),
+                }
+            }
+            EventEdit::Schedule(schedule) => {
+                let old = event.edit_schedule(schedule)?;
+                DomainEvent::ScheduleChanged {
+                    event_id: event.id.clone(),
+                    old_start_at: old.start_at,
+                    new_start_at: event.schedule.start_at,
+                }
+            }
+        };
+
+        self.event_repo
+            .update_event(&mut event, &[OutboxMessage::new(change)])
+            .await?;
+        Ok(event)
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for EditEvent<'a> {
+    type Input = Input;
+    type Response = Event;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageEvents)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        self.run(event, input.edit).await
+    }
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        event.require_host(member_id)?;
+
+        self.run(event, input.edit).await
+    }
+}
@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api_key::ApiKeyScope,
+    event::EventRepository,
+    reconfirmation::{Reconfirmation, ReconfirmationRepository, ReconfirmationStatus},
+    Error,
+};
+
+use super::{respond_reconfirmation::leave_roster, UseCase};
+
+/// Expire the unanswered reconfirmations and open their slots to the waitlist
+pub struct ExpireReconfirmations<'a> {
+    pub reconfirmation_repo: &'a dyn ReconfirmationRepository,
+    pub event_repo: &'a dyn EventRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub now: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub expired: Vec<Reconfirmation>,
+}
+
+#[async_trait]
+impl<'a> UseCase for ExpireReconfirmations<'a> {
+    type Input = Input;
+    type Response = Response;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageEvents)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let mut expired = vec![];
+
+        for mut reconfirmation in self.reconfirmation_repo.list_expired(input.now).await? {
+            // Left first, so a failure keeps the request pending for the next run
+            leave_roster(
+                self.event_repo,
+                &reconfirmation.event_id,
+                reconfirmation.member_id,
+            )
+            .await?;
+            reconfirmation.status = ReconfirmationStatus::Expired;
+            match self.reconfirmation_repo.update(&reconfirmation).await {
+                Ok(()) => expired.push(reconfirmation),
+                // Answered just before the deadline
+                Err(Error::Conflict) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(Response { expired })
+    }
+}
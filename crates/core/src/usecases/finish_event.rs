@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    api_key::ApiKeyScope,
+    event::{Event, EventRepository},
+    member::MemberId,
+    outbox::{DomainEvent, OutboxMessage},
+    Error,
+};
+
+use super::UseCase;
+
+/// Finish a started event
+/// The host can finish early, the system only once the scheduled end passed
+/// Admins can finish any published event, even before it started
+pub struct FinishEvent<'a> {
+    pub event_repo: &'a dyn EventRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub event_id: String,
+}
+
+impl<'a> FinishEvent<'a> {
+    async fn run(&self, mut event: Event, manually: bool) -> Result<Event, Error> {
+        event.finish(manually, Utc::now())?;
+        self.save(event).await
+    }
+
+    async fn save(&self, mut event: Event) -> Result<Event, Error> {
+        let message = OutboxMessage::new(DomainEvent::EventFinished {
+            event_id: event.id.clone(),
+        });
+        self.event_repo.update_event(&mut event, &[message]).await?;
+        Ok(event)
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for FinishEvent<'a> {
+    type Input = Input;
+    type Response = Event;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageEvents)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        self.run(event, false).await
+    }
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        event.require_host(member_id)?;
+
+        self.run(event, true).await
+    }
+
+    async fn admin_execute(
+        &self,
+        _member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let mut event = self.event_repo.get_event(&input.event_id).await?;
+        event.force_finish()?;
+
+        self.save(event).await
+    }
+}
@@ -1,15 +1,22 @@
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
 
 use crate::{
-    member::{DiscordClient, Member, MemberId, MemberRepository},
+    member::{
+        IdentityProvider, Member, MemberId, MemberRepository, OAuth2StateSigner, Pkce,
+        OAUTH2_STATE_LIFETIME_MINUTES,
+    },
+    permission::{Permission, PermissionService},
     Error,
 };
 
 use super::UseCase;
 
 pub struct GetAuthInfo<'a> {
-    pub discord_client: &'a dyn DiscordClient,
+    pub identity_provider: &'a dyn IdentityProvider,
     pub member_repo: &'a dyn MemberRepository,
+    pub permission_service: &'a dyn PermissionService,
+    pub oauth2_state_signer: &'a dyn OAuth2StateSigner,
 }
 
 #[derive(Debug, Clone)]
@@ -20,7 +27,13 @@ pub struct GetAuthInfoInput {
 #[derive(Debug, Clone)]
 pub struct GetAuthInfoResponse {
     pub auth_url: String,
+    /// State in the auth url, to compare with the one returned by discord
+    pub state: String,
+    /// Kept by the client until the sign in, never sent to discord
+    pub code_verifier: String,
     pub member: Option<Member>,
+    /// Permissions of the member, to show the allowed features
+    pub permissions: Vec<Permission>,
 }
 
 impl<'a> GetAuthInfo<'a> {
@@ -29,13 +42,35 @@ impl<'a> GetAuthInfo<'a> {
         member_id: Option<MemberId>,
         input: GetAuthInfoInput,
     ) -> Result<GetAuthInfoResponse, Error> {
-        let auth_url = self.discord_client.get_oauth2_url(&input.redirect_uri);
+        let pkce = Pkce::new();
+        let state = self.oauth2_state_signer.sign(
+            &pkce.code_challenge,
+            Utc::now() + Duration::minutes(OAUTH2_STATE_LIFETIME_MINUTES),
+        )?;
+        let auth_url = self.identity_provider.get_oauth2_url(
+            &input.redirect_uri,
+            &state,
+            &pkce.code_challenge,
+        );
         let member = match member_id {
             Some(member_id) => self.member_repo.get_member(member_id).await.ok(),
             None => None,
         };
+        let permissions = match &member {
+            Some(member) => {
+                let access_type = self.permission_service.access_type(member.id).await?;
+                self.permission_service.permissions(&access_type).await?
+            }
+            None => vec![],
+        };
 
-        Ok(GetAuthInfoResponse { auth_url, member })
+        Ok(GetAuthInfoResponse {
+            auth_url,
+            state,
+            code_verifier: pkce.code_verifier,
+            member,
+            permissions,
+        })
     }
 }
 
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+
+use crate::{
+    character::{Character, CharacterRepository},
+    member::MemberId,
+    Error,
+};
+
+use super::UseCase;
+
+/// Get the character of the member, item not found if not set
+pub struct GetCharacter<'a> {
+    pub character_repo: &'a dyn CharacterRepository,
+}
+
+#[async_trait]
+impl<'a> UseCase for GetCharacter<'a> {
+    type Input = ();
+    type Response = Character;
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        _input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.character_repo.get_character(member_id).await
+    }
+}
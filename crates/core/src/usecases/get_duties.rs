@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 
 use crate::{
+    api_key::ApiKeyScope,
     duty::{CategoriesAndDuties, Duty, DutyCategory, DutyRepository},
     Error,
 };
@@ -53,6 +54,14 @@ impl<'a> UseCase for GetDuties<'a> {
     type Input = Input;
     type Response = Response;
 
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ReadCatalog)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        self.run(input).await
+    }
+
     async fn guest_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
         self.run(input).await
     }
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+
+use crate::{
+    api_key::ApiKeyScope,
+    event::{Event, EventRepository},
+    member::MemberId,
+    Error,
+};
+
+use super::UseCase;
+
+/// Get an event, drafts are only shown to their host
+pub struct GetEvent<'a> {
+    pub event_repo: &'a dyn EventRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub event_id: String,
+}
+
+#[async_trait]
+impl<'a> UseCase for GetEvent<'a> {
+    type Input = Input;
+    type Response = Event;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageEvents)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        self.event_repo.get_event(&input.event_id).await
+    }
+
+    async fn guest_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        if event.status.is_draft() {
+            return Err(Error::ItemNotFound);
+        }
+
+        Ok(event)
+    }
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        if event.status.is_draft() && !event.is_host(member_id) {
+            return Err(Error::ItemNotFound);
+        }
+
+        Ok(event)
+    }
+}
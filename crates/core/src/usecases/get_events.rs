@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+
+use crate::{
+    api_key::ApiKeyScope,
+    event::{Event, EventRepository, EventStatus},
+    member::MemberId,
+    world::WorldRepository,
+    Error,
+};
+
+use super::UseCase;
+
+/// List the public events open for sign up, filtered by data center and language
+pub struct GetEvents<'a> {
+    pub event_repo: &'a dyn EventRepository,
+    pub world_repo: &'a dyn WorldRepository,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Input {
+    /// Events on the data center or its region
+    pub data_center: Option<String>,
+    pub language: Option<String>,
+}
+
+impl<'a> GetEvents<'a> {
+    async fn run(&self, input: Input) -> Result<Vec<Event>, Error> {
+        let data_center = match &input.data_center {
+            Some(id) => {
+                let catalog = self.world_repo.get_catalog().await?;
+                let data_center = catalog
+                    .data_centers
+                    .into_iter()
+                    .find(|data_center| &data_center.id == id)
+                    .ok_or_else(|| {
+                        Error::invalid_field(
+                            "dataCenter",
+                            "not_found",
+                            "The data center does not exist.",
+                        )
+                    })?;
+                Some(data_center)
+            }
+            None => None,
+        };
+
+        let events = self
+            .event_repo
+            .list_events(EventStatus::Public, None)
+            .await?;
+        Ok(events
+            .into_iter()
+            .filter(|event| event.matches(data_center.as_ref(), input.language.as_deref()))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for GetEvents<'a> {
+    type Input = Input;
+    type Response = Vec<Event>;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ReadCatalog)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        self.run(input).await
+    }
+
+    async fn guest_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        self.run(input).await
+    }
+
+    async fn member_execute(
+        &self,
+        _member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.run(input).await
+    }
+}
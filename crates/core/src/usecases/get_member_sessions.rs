@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    member::{MemberId, MemberRepository, MemberSession},
+    Error,
+};
+
+use super::UseCase;
+
+/// List the active sessions of the member, newest first
+pub struct GetMemberSessions<'a> {
+    pub member_repo: &'a dyn MemberRepository,
+}
+
+#[async_trait]
+impl<'a> UseCase for GetMemberSessions<'a> {
+    type Input = ();
+    type Response = Vec<MemberSession>;
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        _input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let now = Utc::now();
+        let mut sessions = self
+            .member_repo
+            .list_member_sessions(member_id)
+            .await?
+            .into_iter()
+            .filter(|session| !session.is_expired(now))
+            .collect::<Vec<_>>();
+        sessions.sort_by_key(|session| std::cmp::Reverse(session.issued_at));
+
+        Ok(sessions)
+    }
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+
+use crate::{
+    member::MemberId,
+    notification::{NotificationPreferenceRepository, NotificationPreferences},
+    Error,
+};
+
+use super::UseCase;
+
+/// Get the notification preferences of the member
+pub struct GetNotificationPreferences<'a> {
+    pub preference_repo: &'a dyn NotificationPreferenceRepository,
+}
+
+#[async_trait]
+impl<'a> UseCase for GetNotificationPreferences<'a> {
+    type Input = ();
+    type Response = NotificationPreferences;
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        _input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.preference_repo.get_preferences(member_id).await
+    }
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+
+use crate::{
+    api_key::ApiKeyScope,
+    world::{WorldCatalog, WorldRepository},
+    Error,
+};
+
+use super::UseCase;
+
+/// Get all regions, data centers and worlds
+pub struct GetWorlds<'a> {
+    pub world_repo: &'a dyn WorldRepository,
+}
+
+#[async_trait]
+impl<'a> UseCase for GetWorlds<'a> {
+    type Input = ();
+    type Response = WorldCatalog;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ReadCatalog)
+    }
+
+    async fn system_execute(&self, _input: Self::Input) -> Result<Self::Response, Error> {
+        self.world_repo.get_catalog().await
+    }
+
+    async fn guest_execute(&self, _input: Self::Input) -> Result<Self::Response, Error> {
+        self.world_repo.get_catalog().await
+    }
+
+    async fn member_execute(
+        &self,
+        _member_id: crate::member::MemberId,
+        _input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.world_repo.get_catalog().await
+    }
+}
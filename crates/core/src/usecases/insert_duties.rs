@@ -1,7 +1,10 @@
 use async_trait::async_trait;
 
 use crate::{
+    api_key::ApiKeyScope,
     duty::{Duty, DutyPhrase, DutyRepository},
+    member::MemberId,
+    validation::{Validate, Validator},
     Error,
 };
 
@@ -16,15 +19,30 @@ pub struct Input {
     pub duties: Vec<(Duty, Vec<DutyPhrase>)>,
 }
 
+impl Validate for Input {
+    fn validate(&self, validator: &mut Validator) {
+        for (i, (duty, phrases)) in self.duties.iter().enumerate() {
+            let field = format!("duties[{}]", i);
+            validator.nested(&field, duty);
+            validator.each(&format!("{}.phrases", field), phrases);
+        }
+    }
+}
+
 #[async_trait]
 impl<'a> UseCase for InsertDuties<'a> {
     type Input = Input;
     type Response = ();
 
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageCatalog)
+    }
+
     async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
         if input.duties.is_empty() {
             return Ok(());
         }
+        input.validated()?;
 
         self.duty_repo
             .insert_duties(
@@ -36,4 +54,12 @@ impl<'a> UseCase for InsertDuties<'a> {
             )
             .await
     }
+
+    async fn admin_execute(
+        &self,
+        _member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.system_execute(input).await
+    }
 }
@@ -1,7 +1,10 @@
 use async_trait::async_trait;
 
 use crate::{
+    api_key::ApiKeyScope,
     duty::{DutyCategory, DutyRepository},
+    member::MemberId,
+    validation::{Validate, Validator},
     Error,
 };
 
@@ -16,16 +19,35 @@ pub struct Input {
     pub categories: Vec<DutyCategory>,
 }
 
+impl Validate for Input {
+    fn validate(&self, validator: &mut Validator) {
+        validator.each("categories", &self.categories);
+    }
+}
+
 #[async_trait]
 impl<'a> UseCase for InsertDutyCategories<'a> {
     type Input = Input;
     type Response = ();
 
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageCatalog)
+    }
+
     async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
         if input.categories.is_empty() {
-            Ok(())
-        } else {
-            self.duty_repo.insert_categories(&input.categories).await
+            return Ok(());
         }
+        input.validated()?;
+
+        self.duty_repo.insert_categories(&input.categories).await
+    }
+
+    async fn admin_execute(
+        &self,
+        _member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.system_execute(input).await
     }
 }
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+
+use crate::{
+    api_key::ApiKeyScope,
+    world::{DataCenter, Region, World, WorldRepository},
+    Error,
+};
+
+use super::UseCase;
+
+pub struct InsertWorlds<'a> {
+    pub world_repo: &'a dyn WorldRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub regions: Vec<Region>,
+    pub data_centers: Vec<DataCenter>,
+    pub worlds: Vec<World>,
+}
+
+#[async_trait]
+impl<'a> UseCase for InsertWorlds<'a> {
+    type Input = Input;
+    type Response = ();
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageCatalog)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        if input.regions.is_empty() && input.data_centers.is_empty() && input.worlds.is_empty() {
+            return Ok(());
+        }
+
+        self.world_repo
+            .insert_catalog(&input.regions, &input.data_centers, &input.worlds)
+            .await
+    }
+}
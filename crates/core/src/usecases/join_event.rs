@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    character::CharacterRepository,
+    event::{EventRepository, SignUpStatus},
+    member::{MemberId, MemberRepository},
+    outbox::{DomainEvent, OutboxMessage},
+    world::WorldRepository,
+    Error,
+};
+
+use super::UseCase;
+
+/// Sign up for an event with a job, checked against the character of the member
+/// Suspended members and members who left the guild cannot sign up
+pub struct JoinEvent<'a> {
+    pub member_repo: &'a dyn MemberRepository,
+    pub event_repo: &'a dyn EventRepository,
+    pub character_repo: &'a dyn CharacterRepository,
+    pub world_repo: &'a dyn WorldRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub event_id: String,
+    pub job: String,
+}
+
+#[async_trait]
+impl<'a> UseCase for JoinEvent<'a> {
+    type Input = Input;
+    type Response = SignUpStatus;
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        match self.member_repo.get_member(member_id).await {
+            Ok(member) if member.is_active() && !member.is_suspended() => {}
+            Ok(_) | Err(Error::ItemNotFound) => return Err(Error::Forbidden),
+            Err(e) => return Err(e),
+        }
+
+        let character = match self.character_repo.get_character(member_id).await {
+            Ok(character) => character,
+            Err(Error::ItemNotFound) => {
+                return Err(Error::invalid_field(
+                    "character",
+                    "required",
+                    "Set your character before signing up.",
+                ))
+            }
+            Err(e) => return Err(e),
+        };
+        let home = self.world_repo.get_world(&character.world_id).await?;
+
+        let mut event = self.event_repo.get_event(&input.event_id).await?;
+        let status = event.sign_up(member_id, &input.job, &home, Utc::now())?;
+
+        let outbox = match status {
+            SignUpStatus::Accepted { .. } => {
+                vec![OutboxMessage::new(DomainEvent::SignUpAccepted {
+                    event_id: event.id.clone(),
+                    member_id,
+                    promoted: false,
+                })]
+            }
+            SignUpStatus::Waitlisted => vec![],
+        };
+        self.event_repo.update_event(&mut event, &outbox).await?;
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::{Input, JoinEvent};
+    use crate::{
+        event::EventVisibility,
+        usecases::{
+            test_support::{block_on, event, member, EmptyCatalog, StubEventRepo, StubMemberRepo},
+            UseCase,
+        },
+        AccessType, Error,
+    };
+
+    #[test]
+    fn suspended_members_cannot_sign_up() {
+        let mut event = event(1);
+        event.publish(EventVisibility::Private).unwrap();
+        let event_repo = StubEventRepo(event);
+        let run = |member_repo: StubMemberRepo| {
+            let join_event = JoinEvent {
+                member_repo: &member_repo,
+                event_repo: &event_repo,
+                character_repo: &member_repo,
+                world_repo: &EmptyCatalog,
+            };
+            block_on(join_event.execute(
+                &AccessType::Member(2),
+                Input {
+                    event_id: event_repo.0.id.clone(),
+                    job: "war".to_string(),
+                },
+            ))
+        };
+
+        let mut suspended = member(2);
+        suspended.suspended_at = Some(Utc::now());
+        assert!(matches!(
+            run(StubMemberRepo(suspended)),
+            Err(Error::Forbidden)
+        ));
+
+        let mut left = member(2);
+        left.left_at = Some(Utc::now());
+        assert!(matches!(run(StubMemberRepo(left)), Err(Error::Forbidden)));
+
+        // Active members get as far as the character check
+        assert!(matches!(
+            run(StubMemberRepo(member(2))),
+            Err(Error::Validation(errors)) if errors[0].path == "character"
+        ));
+    }
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+
+use crate::{
+    event::EventRepository,
+    member::MemberId,
+    outbox::{DomainEvent, OutboxMessage},
+    Error,
+};
+
+use super::UseCase;
+
+/// Leave the roster or the waitlist of an event
+/// The slot goes to the first waitlisted member it accepts
+pub struct LeaveEvent<'a> {
+    pub event_repo: &'a dyn EventRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub event_id: String,
+}
+
+#[async_trait]
+impl<'a> UseCase for LeaveEvent<'a> {
+    type Input = Input;
+    type Response = ();
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let mut event = self.event_repo.get_event(&input.event_id).await?;
+        let accepted = event
+            .sign_up_of(member_id)
+            .is_some_and(|sign_up| sign_up.is_accepted());
+        let promoted = event.leave(member_id)?;
+
+        let mut outbox = vec![OutboxMessage::new(DomainEvent::SignUpWithdrawn {
+            event_id: event.id.clone(),
+            member_id,
+            accepted,
+        })];
+        if let Some(promoted) = promoted {
+            outbox.push(OutboxMessage::new(DomainEvent::SignUpAccepted {
+                event_id: event.id.clone(),
+                member_id: promoted,
+                promoted: true,
+            }));
+        }
+        self.event_repo.update_event(&mut event, &outbox).await
+    }
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+
+use crate::{
+    api_key::{ApiKey, ApiKeyRepository},
+    member::MemberId,
+    Error,
+};
+
+use super::UseCase;
+
+pub struct ListApiKeys<'a> {
+    pub api_key_repo: &'a dyn ApiKeyRepository,
+}
+
+#[async_trait]
+impl<'a> UseCase for ListApiKeys<'a> {
+    type Input = ();
+    type Response = Vec<ApiKey>;
+
+    async fn system_execute(&self, _input: Self::Input) -> Result<Self::Response, Error> {
+        let mut api_keys = self.api_key_repo.list().await?;
+        api_keys.sort_by_key(|api_key| api_key.created_at);
+
+        Ok(api_keys)
+    }
+
+    async fn admin_execute(
+        &self,
+        _member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.system_execute(input).await
+    }
+}
@@ -1,18 +1,74 @@
 use async_trait::async_trait;
 
-use crate::{member::MemberId, AccessType, Error};
+use crate::{api_key::ApiKeyScope, member::MemberId, AccessType, Error};
 
 // Member
+pub mod authenticate_api_key;
 pub mod authorization;
 pub mod get_auth_info;
+pub mod get_member_sessions;
+pub mod refresh_access_token;
+pub mod refresh_members;
+pub mod revoke_member_session;
 pub mod sign_in;
+pub mod suspend_member;
+
+// Character
+pub mod get_character;
+pub mod set_character;
+
+// Api key
+pub mod create_api_key;
+pub mod list_api_keys;
+pub mod revoke_api_key;
 
 // Duty
+pub mod delete_duty;
 pub mod get_duties;
 pub mod get_duty;
 pub mod insert_duties;
 pub mod insert_duty_categories;
 
+// Event
+pub mod cancel_event;
+pub mod create_event;
+pub mod edit_event;
+pub mod finish_event;
+pub mod get_event;
+pub mod get_events;
+pub mod join_event;
+pub mod leave_event;
+pub mod publish_event;
+pub mod start_event;
+pub mod sweep_events;
+pub mod sync_event_discord;
+pub mod sync_events_discord;
+
+// Notification
+pub mod get_notification_preferences;
+pub mod notify_event_change;
+pub mod set_notification_preferences;
+
+// Outbox
+pub mod dispatch_outbox;
+
+// Reconfirmation
+pub mod expire_reconfirmations;
+pub mod request_reconfirmation;
+pub mod respond_reconfirmation;
+
+// Reminder
+pub mod send_reminders;
+pub mod set_reminder_opt_out;
+
+// World
+pub mod get_worlds;
+pub mod insert_worlds;
+
+/// Stubs to run use cases in tests, without a runtime or a database
+#[cfg(test)]
+mod test_support;
+
 #[async_trait]
 pub trait UseCase: Send {
     type Input: Send;
@@ -33,9 +89,36 @@ pub trait UseCase: Send {
         Err(Error::Forbidden)
     }
 
+    /// Admins are members, unless the use case grants them more
+    async fn admin_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.member_execute(member_id, input).await
+    }
+
+    /// Guests are asked to sign in, unless the use case is public
     async fn guest_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
         let _ = input;
-        Err(Error::Forbidden)
+        Err(Error::Unauthenticated)
+    }
+
+    /// Scope an api key needs to run the use case, none when api keys are not allowed
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        None
+    }
+
+    /// Api keys run as the system, limited to the use cases of their scopes
+    async fn api_key_execute(
+        &self,
+        scopes: &[ApiKeyScope],
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        match self.api_key_scope() {
+            Some(scope) if scopes.contains(&scope) => self.system_execute(input).await,
+            _ => Err(Error::Forbidden),
+        }
     }
 
     async fn execute(
@@ -46,6 +129,8 @@ pub trait UseCase: Send {
         match access_type {
             AccessType::System => self.system_execute(input).await,
             AccessType::Member(member_id) => self.member_execute(*member_id, input).await,
+            AccessType::Admin(member_id) => self.admin_execute(*member_id, input).await,
+            AccessType::ApiKey(scopes) => self.api_key_execute(scopes, input).await,
             AccessType::Guest => self.guest_execute(input).await,
         }
     }
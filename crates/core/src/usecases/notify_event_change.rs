@@ -0,0 +1,149 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    announcement::EventThread,
+    event::{Event, EventRepository},
+    notification::{DirectMessenger, EventNotice, NotificationPreferenceRepository},
+    outbox::DomainEvent,
+    reconfirmation::ReconfirmationRepository,
+    Error,
+};
+
+use super::{request_reconfirmation::RequestReconfirmation, UseCase};
+
+/// Moves up to this many hours keep the roster as is
+const RECONFIRMATION_THRESHOLD_HOURS: i64 = 1;
+/// Hours given to the roster to confirm after a larger move
+const RECONFIRMATION_WINDOW_HOURS: i64 = 12;
+
+/// Send the direct messages owed for an event change, routed by the member preferences
+/// Run by the outbox. A member whose message fails is skipped, so the others are not sent twice
+/// Schedule changes ask the roster to confirm they can still make it
+pub struct NotifyEventChange<'a> {
+    pub event_repo: &'a dyn EventRepository,
+    pub preference_repo: &'a dyn NotificationPreferenceRepository,
+    pub direct_messenger: &'a dyn DirectMessenger,
+    pub reconfirmation_repo: &'a dyn ReconfirmationRepository,
+    pub event_thread: &'a dyn EventThread,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub change: DomainEvent,
+    pub now: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Response {
+    pub sent: usize,
+    pub failed: usize,
+}
+
+impl<'a> NotifyEventChange<'a> {
+    /// Ask the roster to confirm the new start, the host moved it so they are not asked
+    async fn request_reconfirmation(
+        &self,
+        event: &Event,
+        old_start_at: DateTime<Utc>,
+        new_start_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<Response, Error> {
+        use super::request_reconfirmation::Input;
+
+        if !event.status.is_open() {
+            return Ok(Response::default());
+        }
+
+        let request = RequestReconfirmation {
+            reconfirmation_repo: self.reconfirmation_repo,
+            preference_repo: self.preference_repo,
+            direct_messenger: self.direct_messenger,
+            event_thread: self.event_thread,
+        };
+        let response = request
+            .system_execute(Input {
+                event_id: event.id.clone(),
+                title: event.info.title.clone(),
+                old_start_at,
+                new_start_at,
+                thread_id: event.thread_id.clone(),
+                roster: event
+                    .roster()
+                    .into_iter()
+                    .filter(|member_id| Some(*member_id) != event.host)
+                    .collect(),
+                now,
+                threshold: Duration::hours(RECONFIRMATION_THRESHOLD_HOURS),
+                window: Duration::hours(RECONFIRMATION_WINDOW_HOURS),
+            })
+            .await?;
+
+        Ok(Response {
+            sent: response.sent,
+            failed: response.failed,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for NotifyEventChange<'a> {
+    type Input = Input;
+    type Response = Response;
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let mut response = Response::default();
+
+        let Some(event_id) = input.change.event_id() else {
+            return Ok(response);
+        };
+        let event = self.event_repo.get_event(event_id).await?;
+
+        if let DomainEvent::ScheduleChanged {
+            old_start_at,
+            new_start_at,
+            ..
+        } = &input.change
+        {
+            return self
+                .request_reconfirmation(&event, *old_start_at, *new_start_at, input.now)
+                .await;
+        }
+
+        let mut unavailable = false;
+        for notice in EventNotice::for_change(&input.change, &event) {
+            let preferences = self
+                .preference_repo
+                .get_preferences(notice.member_id)
+                .await?;
+            if !preferences
+                .route(notice.kind, input.now)
+                .sends_direct_message(notice.posted_in_thread)
+            {
+                continue;
+            }
+
+            match self
+                .direct_messenger
+                .send(notice.member_id, &notice.message)
+                .await
+            {
+                Ok(()) => response.sent += 1,
+                Err(Error::DiscordUnavailable) => {
+                    unavailable = true;
+                    response.failed += 1;
+                }
+                // e.g. the member closed their direct messages
+                Err(Error::Upstream(_)) => response.failed += 1,
+                Err(e) => return Err(e),
+            }
+        }
+
+        // Nothing went out, the outbox can safely retry the whole change
+        if unavailable && response.sent == 0 {
+            return Err(Error::DiscordUnavailable);
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+
+use crate::{
+    api_key::ApiKeyScope,
+    event::{Event, EventRepository, EventVisibility},
+    member::MemberId,
+    outbox::{DomainEvent, OutboxMessage},
+    permission::{Permission, PermissionService},
+    AccessType, Error,
+};
+
+use super::UseCase;
+
+/// Publish a draft event, members can sign up from then on
+/// Hosts need the create public event permission to list it publicly
+pub struct PublishEvent<'a> {
+    pub event_repo: &'a dyn EventRepository,
+    pub permission_service: &'a dyn PermissionService,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub event_id: String,
+    pub visibility: EventVisibility,
+}
+
+impl<'a> PublishEvent<'a> {
+    async fn run(&self, mut event: Event, visibility: EventVisibility) -> Result<Event, Error> {
+        event.publish(visibility)?;
+
+        let message = OutboxMessage::new(DomainEvent::EventPublished {
+            event_id: event.id.clone(),
+        });
+        self.event_repo.update_event(&mut event, &[message]).await?;
+        Ok(event)
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for PublishEvent<'a> {
+    type Input = Input;
+    type Response = Event;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageEvents)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        self.run(event, input.visibility).await
+    }
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let event = self.event_repo.get_event(&input.event_id).await?;
+        event.require_host(member_id)?;
+        if input.visibility == EventVisibility::Public {
+            self.permission_service
+                .require(
+                    &AccessType::Member(member_id),
+                    Permission::CreatePublicEvent,
+                )
+                .await?;
+        }
+
+        self.run(event, input.visibility).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Input, PublishEvent};
+    use crate::{
+        event::EventVisibility,
+        permission::Permission,
+        usecases::{
+            test_support::{block_on, event, StubEventRepo, StubPermissions},
+            UseCase,
+        },
+        AccessType, Error,
+    };
+
+    #[test]
+    fn public_events_need_the_create_public_event_permission() {
+        let event_repo = StubEventRepo(event(1));
+        let permission_service = StubPermissions(vec![Permission::CreateEvent]);
+        let publish_event = PublishEvent {
+            event_repo: &event_repo,
+            permission_service: &permission_service,
+        };
+        let run = |visibility| {
+            block_on(publish_event.execute(
+                &AccessType::Member(1),
+                Input {
+                    event_id: event_repo.0.id.clone(),
+                    visibility,
+                },
+            ))
+        };
+
+        assert!(matches!(
+            run(EventVisibility::Public),
+            Err(Error::Forbidden)
+        ));
+        assert!(run(EventVisibility::Private).is_ok());
+    }
+}
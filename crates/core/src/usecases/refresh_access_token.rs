@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    member::{AccessToken, MemberRepository, MemberSessionSigner, ACCESS_TOKEN_LIFETIME_MINUTES},
+    Error,
+};
+
+use super::UseCase;
+
+/// Issue an access token from a session token
+/// The only place the session is checked, so revocation applies on the next refresh
+pub struct RefreshAccessToken<'a> {
+    pub member_repo: &'a dyn MemberRepository,
+    pub member_session_signer: &'a dyn MemberSessionSigner,
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub access_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl<'a> RefreshAccessToken<'a> {
+    async fn run(&self, session_token: &str) -> Result<Response, Error> {
+        let session_id = self.member_session_signer.verify(session_token)?;
+        let mut session = match self.member_repo.get_member_session(&session_id).await {
+            Ok(session) => session,
+            Err(Error::ItemNotFound) => return Err(Error::InvalidToken),
+            Err(e) => return Err(e),
+        };
+
+        let now = Utc::now();
+        if session.is_expired(now) {
+            return Err(Error::InvalidToken);
+        }
+        if session.renew(now) {
+            match self.member_repo.update_member_session(&session).await {
+                Ok(()) => {}
+                Err(Error::ItemNotFound) => return Err(Error::InvalidToken),
+                Err(e) => return Err(e),
+            }
+        }
+
+        let member = self.member_repo.get_member(session.member_id).await?;
+        if !member.is_active() || member.is_suspended() {
+            return Err(Error::InvalidToken);
+        }
+
+        let access_token = AccessToken {
+            member_id: member.id,
+            session_id: session.id,
+            roles: member.roles,
+            expires_at: (now + Duration::minutes(ACCESS_TOKEN_LIFETIME_MINUTES))
+                .min(session.expires_at),
+        };
+
+        Ok(Response {
+            access_token: self
+                .member_session_signer
+                .sign_access_token(&access_token)?,
+            expires_at: access_token.expires_at,
+        })
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for RefreshAccessToken<'a> {
+    type Input = &'a str;
+    type Response = Response;
+
+    async fn guest_execute(&self, session_token: Self::Input) -> Result<Self::Response, Error> {
+        self.run(session_token).await
+    }
+}
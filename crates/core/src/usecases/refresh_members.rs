@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    member::{DiscordClient, MemberRepository},
+    Error,
+};
+
+use super::UseCase;
+
+/// Refresh stale member profiles from discord
+/// Members who left the guild are marked inactive and signed out
+pub struct RefreshMembers<'a> {
+    pub discord_client: &'a dyn DiscordClient,
+    pub member_repo: &'a dyn MemberRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub now: DateTime<Utc>,
+    /// Refresh members not updated since this time
+    pub updated_before: DateTime<Utc>,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Response {
+    pub refreshed: usize,
+    pub left: usize,
+    /// Members skipped because discord failed, retried once stale again
+    pub failed: usize,
+}
+
+#[async_trait]
+impl<'a> UseCase for RefreshMembers<'a> {
+    type Input = Input;
+    type Response = Response;
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let mut response = Response::default();
+
+        let members = self
+            .member_repo
+            .list_stale_members(input.updated_before, input.limit)
+            .await?;
+        for mut member in members {
+            match self.discord_client.get_member(member.id).await {
+                Ok(mut fresh) => {
+                    fresh.updated_at = input.now;
+                    fresh.suspended_at = member.suspended_at;
+                    self.member_repo.update_member(&fresh).await?;
+                    response.refreshed += 1;
+                }
+                Err(Error::ItemNotFound) => {
+                    // Keep the profile, the member may come back
+                    if member.is_active() {
+                        member.left_at = Some(input.now);
+                    }
+                    member.updated_at = input.now;
+                    self.member_repo.update_member(&member).await?;
+                    self.member_repo.revoke_member_sessions(member.id).await?;
+                    response.left += 1;
+                }
+                // Moved to the back of the queue, so failing members do not block the others
+                Err(_) => {
+                    member.updated_at = input.now;
+                    self.member_repo.update_member(&member).await?;
+                    response.failed += 1;
+                }
+            }
+        }
+
+        Ok(response)
+    }
+}
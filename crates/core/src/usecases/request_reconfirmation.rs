@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    announcement::{EventThread, ThreadMessage},
+    api_key::ApiKeyScope,
+    member::MemberId,
+    notification::{
+        DirectMessage, DirectMessenger, NotificationChannel, NotificationKind,
+        NotificationPreferenceRepository,
+    },
+    reconfirmation::{
+        needs_reconfirmation, reconfirmation_deadline, Reconfirmation, ReconfirmationRepository,
+        ReconfirmationStatus,
+    },
+    Error,
+};
+
+use super::UseCase;
+
+/// Ask the accepted members to confirm after a published event is rescheduled
+/// Run with the `ScheduleChanged` change, small moves are ignored
+/// The request is always delivered, a member whose message fails is skipped
+pub struct RequestReconfirmation<'a> {
+    pub reconfirmation_repo: &'a dyn ReconfirmationRepository,
+    pub preference_repo: &'a dyn NotificationPreferenceRepository,
+    pub direct_messenger: &'a dyn DirectMessenger,
+    pub event_thread: &'a dyn EventThread,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub event_id: String,
+    pub title: String,
+    pub old_start_at: DateTime<Utc>,
+    pub new_start_at: DateTime<Utc>,
+    /// Discussion thread, members preferring a mention get a direct message without it
+    pub thread_id: Option<String>,
+    /// Accepted members
+    pub roster: Vec<MemberId>,
+
+    pub now: DateTime<Utc>,
+    /// Moves up to this duration keep the roster as is
+    pub threshold: Duration,
+    /// Time given to answer
+    pub window: Duration,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Response {
+    /// None if the change did not need a reconfirmation
+    pub deadline: Option<DateTime<Utc>>,
+    pub sent: usize,
+    pub failed: usize,
+}
+
+impl<'a> RequestReconfirmation<'a> {
+    /// Send the direct message, count it as failed if Discord refused it
+    async fn send(
+        &self,
+        member_id: MemberId,
+        message: &DirectMessage,
+        response: &mut Response,
+    ) -> Result<(), Error> {
+        match self.direct_messenger.send(member_id, message).await {
+            Ok(()) => response.sent += 1,
+            // e.g. the member closed their direct messages
+            Err(Error::Upstream(_)) | Err(Error::DiscordUnavailable) => response.failed += 1,
+            Err(e) => return Err(e),
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for RequestReconfirmation<'a> {
+    type Input = Input;
+    type Response = Response;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageEvents)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        if input.roster.is_empty()
+            || !needs_reconfirmation(input.old_start_at, input.new_start_at, input.threshold)
+        {
+            return Ok(Response::default());
+        }
+
+        let deadline = reconfirmation_deadline(input.now, input.new_start_at, input.window);
+        let reconfirmations = input
+            .roster
+            .iter()
+            .map(|member_id| Reconfirmation {
+                event_id: input.event_id.clone(),
+                member_id: *member_id,
+                new_start_at: input.new_start_at,
+                deadline,
+                status: ReconfirmationStatus::Pending,
+            })
+            .collect::<Vec<_>>();
+        self.reconfirmation_repo.insert(&reconfirmations).await?;
+
+        let message = DirectMessage::ReconfirmationRequested {
+            event_id: input.event_id.clone(),
+            title: input.title.clone(),
+            old_start_at: input.old_start_at,
+            new_start_at: input.new_start_at,
+            deadline,
+        };
+        let mut response = Response {
+            deadline: Some(deadline),
+            ..Default::default()
+        };
+        let mut mentions = vec![];
+        for member_id in input.roster {
+            let preferences = self.preference_repo.get_preferences(member_id).await?;
+            match preferences.route_required(NotificationKind::Reschedule, input.now) {
+                NotificationChannel::ChannelMention if input.thread_id.is_some() => {
+                    mentions.push(member_id)
+                }
+                _ => self.send(member_id, &message, &mut response).await?,
+            }
+        }
+
+        if let (Some(thread_id), false) = (&input.thread_id, mentions.is_empty()) {
+            let posted = self
+                .event_thread
+                .post(
+                    thread_id,
+                    &ThreadMessage::ReconfirmationRequested {
+                        deadline,
+                        members: mentions.clone(),
+                    },
+                )
+                .await;
+            match posted {
+                Ok(()) => response.sent += mentions.len(),
+                // The thread was removed on Discord, the members still need to answer
+                Err(Error::Upstream(_)) | Err(Error::DiscordUnavailable) => {
+                    for member_id in mentions {
+                        self.send(member_id, &message, &mut response).await?;
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    event::EventRepository,
+    member::MemberId,
+    reconfirmation::{ReconfirmationRepository, ReconfirmationStatus},
+    Error,
+};
+
+use super::{leave_event::LeaveEvent, UseCase};
+
+/// Confirm or drop after a reschedule, dropping leaves the roster
+pub struct RespondReconfirmation<'a> {
+    pub reconfirmation_repo: &'a dyn ReconfirmationRepository,
+    pub event_repo: &'a dyn EventRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub event_id: String,
+    pub confirm: bool,
+}
+
+#[async_trait]
+impl<'a> UseCase for RespondReconfirmation<'a> {
+    type Input = Input;
+    /// Status after the answer, unchanged if it was already answered or expired
+    type Response = ReconfirmationStatus;
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let mut reconfirmation = self
+            .reconfirmation_repo
+            .get(&input.event_id, member_id)
+            .await?;
+        if reconfirmation.status != ReconfirmationStatus::Pending {
+            return Ok(reconfirmation.status);
+        }
+
+        reconfirmation.respond(input.confirm, Utc::now());
+        // Left first, so a failure keeps the request pending and the member can answer again
+        if reconfirmation.status == ReconfirmationStatus::Dropped {
+            leave_roster(self.event_repo, &input.event_id, member_id).await?;
+        }
+        match self.reconfirmation_repo.update(&reconfirmation).await {
+            Ok(()) => Ok(reconfirmation.status),
+            // Expired or answered from another device, keep that answer
+            Err(Error::Conflict) => self
+                .reconfirmation_repo
+                .get(&input.event_id, member_id)
+                .await
+                .map(|reconfirmation| reconfirmation.status),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Give the slot of the member to the waitlist
+/// Members who already left and events already over are left as is
+pub(crate) async fn leave_roster(
+    event_repo: &dyn EventRepository,
+    event_id: &str,
+    member_id: MemberId,
+) -> Result<(), Error> {
+    let leave = LeaveEvent { event_repo };
+    match leave
+        .member_execute(
+            member_id,
+            super::leave_event::Input {
+                event_id: event_id.to_string(),
+            },
+        )
+        .await
+    {
+        Ok(()) | Err(Error::ItemNotFound) | Err(Error::InvalidEventStatus) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+
+use crate::{api_key::ApiKeyRepository, member::MemberId, Error};
+
+use super::UseCase;
+
+pub struct RevokeApiKey<'a> {
+    pub api_key_repo: &'a dyn ApiKeyRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub id: String,
+}
+
+#[async_trait]
+impl<'a> UseCase for RevokeApiKey<'a> {
+    type Input = Input;
+    type Response = ();
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        self.api_key_repo.delete(&input.id).await
+    }
+
+    async fn admin_execute(
+        &self,
+        _member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        self.system_execute(input).await
+    }
+}
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+
+use crate::{
+    member::{MemberId, MemberRepository},
+    Error,
+};
+
+use super::UseCase;
+
+/// Sign out a session of the member, or every session
+pub struct RevokeMemberSession<'a> {
+    pub member_repo: &'a dyn MemberRepository,
+}
+
+#[derive(Debug, Clone)]
+pub enum Input {
+    Session(String),
+    /// Sign out everywhere
+    All,
+}
+
+#[async_trait]
+impl<'a> UseCase for RevokeMemberSession<'a> {
+    type Input = Input;
+    type Response = ();
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        match input {
+            Input::Session(session_id) => {
+                let session = self.member_repo.get_member_session(&session_id).await?;
+                // Other members' sessions are reported as missing
+                if session.member_id != member_id {
+                    return Err(Error::ItemNotFound);
+                }
+
+                self.member_repo.delete_member_session(&session.id).await
+            }
+            Input::All => self.member_repo.revoke_member_sessions(member_id).await,
+        }
+    }
+}
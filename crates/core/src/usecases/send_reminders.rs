@@ -0,0 +1,206 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    announcement::{EventThread, ThreadMessage},
+    api_key::ApiKeyScope,
+    member::MemberId,
+    notification::{
+        DirectMessage, DirectMessenger, NotificationChannel, NotificationKind,
+        NotificationPreferenceRepository,
+    },
+    reminder::{due_offset, ReminderRepository},
+    Error,
+};
+
+use super::UseCase;
+
+/// Send reminders before events start
+/// Run by the time based sweep, each reminder is sent once even if sweeps overlap
+/// A reminder that fails is released for the next sweep, the others still go out
+pub struct SendReminders<'a> {
+    pub reminder_repo: &'a dyn ReminderRepository,
+    pub preference_repo: &'a dyn NotificationPreferenceRepository,
+    pub direct_messenger: &'a dyn DirectMessenger,
+    pub event_thread: &'a dyn EventThread,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReminderEvent {
+    pub event_id: String,
+    pub title: String,
+    pub start_at: DateTime<Utc>,
+    /// Discussion thread, members preferring a mention get a direct message without it
+    pub thread_id: Option<String>,
+
+    pub host: Option<MemberId>,
+    pub roster: Vec<MemberId>,
+    pub total_slots: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub now: DateTime<Utc>,
+    /// Reminder offsets before start, e.g. 24 hours and 30 minutes
+    pub offsets: Vec<Duration>,
+    pub events: Vec<ReminderEvent>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Response {
+    pub sent: usize,
+    /// Reminders released after Discord refused them, retried on the next sweep
+    pub failed: usize,
+}
+
+impl<'a> SendReminders<'a> {
+    /// Channel the member wants the reminder on, none if it should be skipped
+    async fn channel(
+        &self,
+        event: &ReminderEvent,
+        member_id: MemberId,
+        now: DateTime<Utc>,
+    ) -> Result<NotificationChannel, Error> {
+        if self
+            .reminder_repo
+            .is_opted_out(member_id, &event.event_id)
+            .await?
+        {
+            return Ok(NotificationChannel::None);
+        }
+
+        let preferences = self.preference_repo.get_preferences(member_id).await?;
+        Ok(match preferences.route(NotificationKind::Reminder, now) {
+            NotificationChannel::ChannelMention if event.thread_id.is_none() => {
+                NotificationChannel::DirectMessage
+            }
+            channel => channel,
+        })
+    }
+
+    /// Release the claimed reminders, count them as failed if Discord refused them
+    async fn release(
+        &self,
+        event: &ReminderEvent,
+        members: &[MemberId],
+        offset: Duration,
+        error: Error,
+        response: &mut Response,
+    ) -> Result<(), Error> {
+        for member_id in members {
+            self.reminder_repo
+                .release(&event.event_id, *member_id, offset)
+                .await?;
+        }
+
+        match error {
+            // e.g. the member closed their direct messages
+            Error::Upstream(_) | Error::DiscordUnavailable => {
+                response.failed += members.len();
+                Ok(())
+            }
+            e => Err(e),
+        }
+    }
+
+    /// Send reminders for one event, each member once
+    async fn send_event(
+        &self,
+        event: &ReminderEvent,
+        offset: Duration,
+        now: DateTime<Utc>,
+        response: &mut Response,
+    ) -> Result<(), Error> {
+        let reminder = DirectMessage::Reminder {
+            event_id: event.event_id.clone(),
+            title: event.title.clone(),
+            start_at: event.start_at,
+        };
+
+        // Host get the host reminder instead
+        let mut recipients = event
+            .roster
+            .iter()
+            .filter(|member_id| Some(**member_id) != event.host)
+            .map(|member_id| (*member_id, reminder.clone()))
+            .collect::<Vec<_>>();
+        if let Some(host) = event.host {
+            let open_slots = event.total_slots.saturating_sub(event.roster.len());
+            let message = if open_slots > 0 {
+                DirectMessage::HostReminder {
+                    event_id: event.event_id.clone(),
+                    title: event.title.clone(),
+                    start_at: event.start_at,
+                    open_slots,
+                }
+            } else {
+                reminder.clone()
+            };
+            recipients.push((host, message));
+        }
+
+        let mut mentions = vec![];
+        for (member_id, message) in recipients {
+            let channel = self.channel(event, member_id, now).await?;
+            if channel == NotificationChannel::None
+                || !self
+                    .reminder_repo
+                    .claim(&event.event_id, member_id, offset)
+                    .await?
+            {
+                continue;
+            }
+
+            if channel == NotificationChannel::ChannelMention {
+                mentions.push(member_id);
+                continue;
+            }
+
+            match self.direct_messenger.send(member_id, &message).await {
+                Ok(()) => response.sent += 1,
+                Err(e) => {
+                    self.release(event, &[member_id], offset, e, response)
+                        .await?
+                }
+            }
+        }
+
+        if let (Some(thread_id), false) = (&event.thread_id, mentions.is_empty()) {
+            let message = ThreadMessage::Reminder {
+                start_at: event.start_at,
+                members: mentions.clone(),
+            };
+            match self.event_thread.post(thread_id, &message).await {
+                Ok(()) => response.sent += mentions.len(),
+                Err(e) => self.release(event, &mentions, offset, e, response).await?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for SendReminders<'a> {
+    type Input = Input;
+    type Response = Response;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageEvents)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let mut response = Response::default();
+
+        for event in input.events {
+            let Some(offset) = due_offset(event.start_at, &input.offsets, input.now) else {
+                continue;
+            };
+
+            self.send_event(&event, offset, input.now, &mut response)
+                .await?;
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+
+use crate::{
+    character::{Character, CharacterRepository},
+    member::MemberId,
+    validation::Validate,
+    world::WorldRepository,
+    Error,
+};
+
+use super::UseCase;
+
+/// Set the character of the member, its home world must be in the catalog
+pub struct SetCharacter<'a> {
+    pub character_repo: &'a dyn CharacterRepository,
+    pub world_repo: &'a dyn WorldRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub name: String,
+    pub world_id: String,
+}
+
+#[async_trait]
+impl<'a> UseCase for SetCharacter<'a> {
+    type Input = Input;
+    type Response = Character;
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let character = Character {
+            member_id,
+            name: input.name.trim().to_string(),
+            world_id: input.world_id,
+        };
+        character.validated()?;
+
+        match self.world_repo.get_world(&character.world_id).await {
+            Ok(_) => {}
+            Err(Error::ItemNotFound) => {
+                return Err(Error::invalid_field(
+                    "worldId",
+                    "not_found",
+                    "The world does not exist.",
+                ))
+            }
+            Err(e) => return Err(e),
+        }
+
+        self.character_repo.set_character(&character).await?;
+        Ok(character)
+    }
+}
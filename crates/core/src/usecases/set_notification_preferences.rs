@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+
+use crate::{
+    member::MemberId,
+    notification::{NotificationPreferenceRepository, NotificationPreferences},
+    validation::Validate,
+    Error,
+};
+
+use super::UseCase;
+
+/// Replace the notification preferences of the member
+pub struct SetNotificationPreferences<'a> {
+    pub preference_repo: &'a dyn NotificationPreferenceRepository,
+}
+
+#[async_trait]
+impl<'a> UseCase for SetNotificationPreferences<'a> {
+    type Input = NotificationPreferences;
+    type Response = ();
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        input.validated()?;
+
+        self.preference_repo
+            .set_preferences(member_id, &input)
+            .await
+    }
+}
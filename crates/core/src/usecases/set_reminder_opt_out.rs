@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+
+use crate::{event::EventRepository, member::MemberId, reminder::ReminderRepository, Error};
+
+use super::UseCase;
+
+/// Opt in or out of event reminders
+pub struct SetReminderOptOut<'a> {
+    pub reminder_repo: &'a dyn ReminderRepository,
+    pub event_repo: &'a dyn EventRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    /// Apply to all events if none
+    pub event_id: Option<String>,
+    pub opt_out: bool,
+}
+
+#[async_trait]
+impl<'a> UseCase for SetReminderOptOut<'a> {
+    type Input = Input;
+    type Response = ();
+
+    async fn member_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        let end_at = match &input.event_id {
+            Some(event_id) => Some(self.event_repo.get_event(event_id).await?.schedule.end_at()),
+            None => None,
+        };
+
+        self.reminder_repo
+            .set_opt_out(
+                member_id,
+                input.event_id.as_deref().zip(end_at),
+                input.opt_out,
+            )
+            .await
+    }
+}
@@ -1,34 +1,64 @@
 use async_trait::async_trait;
-use chrono::Duration;
+use chrono::{Duration, Utc};
 
 use crate::{
-    member::{DiscordClient, MemberRepository, MemberSession, MemberSessionSigner},
+    member::{
+        IdentityProvider, MemberRepository, MemberSession, MemberSessionSigner, OAuth2StateSigner,
+        Pkce, SESSION_LIFETIME_DAYS,
+    },
+    outbox::{DomainEvent, OutboxMessage},
+    permission::PermissionService,
     Error,
 };
 
 use super::UseCase;
 
 pub struct SignIn<'a> {
-    pub discord_client: &'a dyn DiscordClient,
+    pub identity_provider: &'a dyn IdentityProvider,
     pub member_repo: &'a dyn MemberRepository,
     pub member_session_signer: &'a dyn MemberSessionSigner,
+    pub permission_service: &'a dyn PermissionService,
+    pub oauth2_state_signer: &'a dyn OAuth2StateSigner,
 }
 
 pub struct SignInInput {
     pub discord_code: String,
     pub redirect_uri: String,
+    /// State returned by discord with the code
+    pub state: String,
+    /// PKCE verifier issued with the auth url
+    pub code_verifier: String,
 }
 
 impl<'a> SignIn<'a> {
     async fn run(&self, input: SignInInput) -> Result<String, Error> {
+        // The state is bound to the challenge, so a code from another attempt is rejected
+        let pkce = Pkce::from_verifier(input.code_verifier);
+        self.oauth2_state_signer
+            .verify(&input.state, &pkce.code_challenge, Utc::now())?;
+
         let member = self
-            .discord_client
-            .sign_in(&input.discord_code, &input.redirect_uri)
+            .identity_provider
+            .sign_in(
+                &input.discord_code,
+                &input.redirect_uri,
+                &pkce.code_verifier,
+            )
             .await?;
-        let session = MemberSession::new(member.id, Duration::days(30));
+        self.permission_service.check_sign_in(&member.roles)?;
+        match self.member_repo.get_member(member.id).await {
+            Ok(existing) if existing.is_suspended() => return Err(Error::MemberSuspended),
+            Ok(_) | Err(Error::ItemNotFound) => {}
+            Err(e) => return Err(e),
+        }
+
+        let session = MemberSession::new(member.id, Duration::days(SESSION_LIFETIME_DAYS));
+        let signed_in = OutboxMessage::new(DomainEvent::MemberSignedIn {
+            member_id: member.id,
+        });
 
         self.member_repo
-            .insert_member_and_session(&member, &session)
+            .insert_member_and_session(&member, &session, &[signed_in])
             .await?;
 
         let token = self.member_session_signer.sign(&session.id)?;
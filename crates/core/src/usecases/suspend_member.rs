@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    member::{MemberId, MemberRepository},
+    Error,
+};
+
+use super::UseCase;
+
+/// Suspend a member, signing them out everywhere, or lift the suspension
+pub struct SuspendMember<'a> {
+    pub member_repo: &'a dyn MemberRepository,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub member_id: MemberId,
+    pub suspended: bool,
+}
+
+#[async_trait]
+impl<'a> UseCase for SuspendMember<'a> {
+    type Input = Input;
+    type Response = ();
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let mut member = self.member_repo.get_member(input.member_id).await?;
+        if member.is_suspended() == input.suspended {
+            return Ok(());
+        }
+
+        member.suspended_at = input.suspended.then(Utc::now);
+        self.member_repo.update_member(&member).await?;
+        if input.suspended {
+            self.member_repo.revoke_member_sessions(member.id).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn admin_execute(
+        &self,
+        member_id: MemberId,
+        input: Self::Input,
+    ) -> Result<Self::Response, Error> {
+        // Admins cannot lock themselves out
+        if member_id == input.member_id {
+            return Err(Error::Forbidden);
+        }
+
+        self.system_execute(input).await
+    }
+}
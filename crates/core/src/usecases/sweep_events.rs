@@ -0,0 +1,175 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    announcement::EventThread,
+    api_key::ApiKeyScope,
+    event::{EventRepository, EventStatus},
+    notification::{DirectMessenger, NotificationPreferenceRepository},
+    reconfirmation::ReconfirmationRepository,
+    reminder::ReminderRepository,
+    Error,
+};
+
+use super::{
+    expire_reconfirmations::ExpireReconfirmations,
+    finish_event::FinishEvent,
+    send_reminders::{ReminderEvent, SendReminders},
+    start_event::StartEvent,
+    UseCase,
+};
+
+/// Time based sweep, run every few minutes
+/// Starts and finishes events on schedule, expires reconfirmations and sends reminders
+/// Safe to overlap, each step is skipped if another sweep already did it
+pub struct SweepEvents<'a> {
+    pub event_repo: &'a dyn EventRepository,
+    pub reconfirmation_repo: &'a dyn ReconfirmationRepository,
+    pub reminder_repo: &'a dyn ReminderRepository,
+    pub preference_repo: &'a dyn NotificationPreferenceRepository,
+    pub direct_messenger: &'a dyn DirectMessenger,
+    pub event_thread: &'a dyn EventThread,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub now: DateTime<Utc>,
+    /// Reminder offsets before start, e.g. 24 hours and 30 minutes
+    pub reminder_offsets: Vec<Duration>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Response {
+    pub started: usize,
+    pub finished: usize,
+    pub expired: usize,
+    pub reminders_sent: usize,
+    pub reminders_failed: usize,
+}
+
+impl<'a> SweepEvents<'a> {
+    /// Start the published events whose start passed
+    async fn start_events(&self, now: DateTime<Utc>) -> Result<usize, Error> {
+        let start = StartEvent {
+            event_repo: self.event_repo,
+        };
+        let mut started = 0;
+
+        for status in [EventStatus::Private, EventStatus::Public] {
+            for event in self.event_repo.list_events(status, Some(now)).await? {
+                let input = super::start_event::Input { event_id: event.id };
+                match start.system_execute(input).await {
+                    Ok(_) => started += 1,
+                    // Started or changed by someone else since it was listed
+                    Err(Error::InvalidEventStatus | Error::Conflict) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(started)
+    }
+
+    /// Finish the running events whose end passed
+    async fn finish_events(&self, now: DateTime<Utc>) -> Result<usize, Error> {
+        let finish = FinishEvent {
+            event_repo: self.event_repo,
+        };
+        let mut finished = 0;
+
+        let events = self
+            .event_repo
+            .list_events(EventStatus::InProcess, Some(now))
+            .await?;
+        for event in events {
+            if event.schedule.end_at() > now {
+                continue;
+            }
+
+            let input = super::finish_event::Input { event_id: event.id };
+            match finish.system_execute(input).await {
+                Ok(_) => finished += 1,
+                Err(Error::InvalidEventStatus | Error::Conflict) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(finished)
+    }
+
+    /// Published events starting before the largest reminder offset
+    async fn reminder_events(
+        &self,
+        now: DateTime<Utc>,
+        offsets: &[Duration],
+    ) -> Result<Vec<ReminderEvent>, Error> {
+        let Some(horizon) = offsets.iter().max().map(|offset| now + *offset) else {
+            return Ok(vec![]);
+        };
+
+        let mut events = vec![];
+        for status in [EventStatus::Private, EventStatus::Public] {
+            for event in self.event_repo.list_events(status, Some(horizon)).await? {
+                events.push(ReminderEvent {
+                    roster: event.roster(),
+                    total_slots: event.slots.len(),
+                    event_id: event.id,
+                    title: event.info.title,
+                    start_at: event.schedule.start_at,
+                    thread_id: event.thread_id,
+                    host: event.host,
+                });
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for SweepEvents<'a> {
+    type Input = Input;
+    type Response = Response;
+
+    fn api_key_scope(&self) -> Option<ApiKeyScope> {
+        Some(ApiKeyScope::ManageEvents)
+    }
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let mut response = Response {
+            started: self.start_events(input.now).await?,
+            finished: self.finish_events(input.now).await?,
+            ..Default::default()
+        };
+
+        let expire = ExpireReconfirmations {
+            reconfirmation_repo: self.reconfirmation_repo,
+            event_repo: self.event_repo,
+        };
+        response.expired = expire
+            .system_execute(super::expire_reconfirmations::Input { now: input.now })
+            .await?
+            .expired
+            .len();
+
+        let send_reminders = SendReminders {
+            reminder_repo: self.reminder_repo,
+            preference_repo: self.preference_repo,
+            direct_messenger: self.direct_messenger,
+            event_thread: self.event_thread,
+        };
+        let reminders = send_reminders
+            .system_execute(super::send_reminders::Input {
+                events: self
+                    .reminder_events(input.now, &input.reminder_offsets)
+                    .await?,
+                now: input.now,
+                offsets: input.reminder_offsets,
+            })
+            .await?;
+        response.reminders_sent = reminders.sent;
+        response.reminders_failed = reminders.failed;
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,193 @@
+use async_trait::async_trait;
+
+use crate::{
+    announcement::{Announcement, EventAnnouncer, EventThread, ThreadMessage},
+    duty::DutyRepository,
+    event::{Event, EventRepository, EventStatus},
+    outbox::DomainEvent,
+    scheduled_event::{MirrorChange, ScheduledEvent, ScheduledEventMirror},
+    Error,
+};
+
+use super::UseCase;
+
+/// Attempts to store the posted ids when the event keeps changing
+const SAVE_ATTEMPTS: usize = 3;
+
+/// Bring the Discord side of an event up to date, run by the outbox on each change
+/// Public events are announced and mirrored as guild scheduled events once published,
+/// then both are kept up to date. Safe to run again, it recovers changes made on discord
+/// Events opting in get a thread under the announcement, told about each change
+pub struct SyncEventDiscord<'a> {
+    pub event_repo: &'a dyn EventRepository,
+    pub duty_repo: &'a dyn DutyRepository,
+    pub announcer: &'a dyn EventAnnouncer,
+    pub mirror: &'a dyn ScheduledEventMirror,
+    pub thread: &'a dyn EventThread,
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub event_id: String,
+    /// Change that triggered the sync, none when recovering drift
+    pub change: Option<DomainEvent>,
+}
+
+impl<'a> SyncEventDiscord<'a> {
+    /// Image of the duty, none if the duty was removed from the catalog
+    async fn image(&self, event: &Event) -> Result<Option<String>, Error> {
+        let Some(duty_id) = &event.duty_id else {
+            return Ok(None);
+        };
+
+        match self.duty_repo.get_duty(duty_id).await {
+            Ok(detail) => Ok(Some(detail.duty.image)),
+            Err(Error::ItemNotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Post the announcement of a public event, or edit the posted one
+    /// Return the id of a newly posted announcement
+    async fn sync_announcement(&self, event: &Event) -> Result<Option<String>, Error> {
+        let Some(announcement) = Announcement::of(event, self.image(event).await?) else {
+            return Ok(None);
+        };
+
+        match &event.announcement_id {
+            Some(message_id) => {
+                self.announcer.edit(message_id, &announcement).await?;
+                Ok(None)
+            }
+            None if event.status == EventStatus::Public => self.announcer.post(&announcement).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Create, update or end the scheduled event
+    /// Return the id of a newly created scheduled event
+    async fn sync_scheduled_event(&self, event: &Event) -> Result<Option<String>, Error> {
+        if event.status.is_draft() || event.status == EventStatus::Private {
+            return Ok(None);
+        }
+
+        let mirrored = match &event.scheduled_event_id {
+            Some(id) => self.mirror.status(id).await?,
+            None => None,
+        };
+        match (
+            MirrorChange::plan(event.status, mirrored.as_ref()),
+            &event.scheduled_event_id,
+        ) {
+            (MirrorChange::Create, _) => self
+                .mirror
+                .create(&ScheduledEvent::of(event))
+                .await
+                .map(Some),
+            (MirrorChange::Update, Some(id)) => {
+                self.mirror.update(id, &ScheduledEvent::of(event)).await?;
+                Ok(None)
+            }
+            (MirrorChange::End(end), Some(id)) => {
+                self.mirror.end(id, end).await?;
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Open the thread under the announcement, return its id
+    async fn open_thread(&self, event: &Event) -> Result<Option<String>, Error> {
+        let Some(message_id) = &event.announcement_id else {
+            return Ok(None);
+        };
+        if !event.thread_enabled || event.thread_id.is_some() || !event.status.is_open() {
+            return Ok(None);
+        }
+
+        self.thread.open(message_id, &event.info.title).await
+    }
+
+    /// Tell the thread about the change, archive it once the event is over
+    async fn sync_thread(&self, event: &Event, change: &DomainEvent) -> Result<(), Error> {
+        let Some(thread_id) = &event.thread_id else {
+            return Ok(());
+        };
+
+        if let DomainEvent::SignUpAccepted { member_id, .. } = change {
+            self.thread.add_member(thread_id, *member_id).await?;
+        }
+        if let Some(message) = ThreadMessage::for_change(change, &event.roster()) {
+            self.thread.post(thread_id, &message).await?;
+        }
+        if matches!(
+            change,
+            DomainEvent::EventFinished { .. } | DomainEvent::EventCancelled { .. }
+        ) {
+            self.thread.archive(thread_id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Store the new ids, reloading the event if it changed since it was read
+    async fn save(
+        &self,
+        mut event: Event,
+        apply: impl Fn(&mut Event) + Send,
+    ) -> Result<Event, Error> {
+        for _ in 0..SAVE_ATTEMPTS {
+            apply(&mut event);
+            match self.event_repo.update_event(&mut event, &[]).await {
+                Ok(()) => return Ok(event),
+                Err(Error::Conflict) => event = self.event_repo.get_event(&event.id).await?,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(Error::Conflict)
+    }
+}
+
+#[async_trait]
+impl<'a> UseCase for SyncEventDiscord<'a> {
+    type Input = Input;
+    type Response = ();
+
+    async fn system_execute(&self, input: Self::Input) -> Result<Self::Response, Error> {
+        let mut event = self.event_repo.get_event(&input.event_id).await?;
+
+        // Each id is saved right away, so a failed step does not post twice on retry
+        if let Some(announcement_id) = self.sync_announcement(&event).await? {
+            event = self
+                .save(event, |event| {
+                    event.announcement_id = Some(announcement_id.clone());
+                })
+                .await?;
+        }
+        if let Some(scheduled_event_id) = self.sync_scheduled_event(&event).await? {
+            event = self
+                .save(event, |event| {
+                    event.scheduled_event_id = Some(scheduled_event_id.clone());
+                })
+                .await?;
+        }
+
+        if let Some(thread_id) = self.open_thread(&event).await? {
+            event = self
+                .save(event, |event| {
+                    event.thread_id = Some(thread_id.clone());
+                })
+                .await?;
+            // Members who signed up before the thread was opened
+            for member_id in event.roster() {
+                self.thread.add_member(&thread_id, member_id).await?;
+            }
+            return Ok(());
+        }
+        match &input.change {
+            Some(change) => self.sync_thread(&event, change).await,
+            None => Ok(()),
+        }
+    }
+}
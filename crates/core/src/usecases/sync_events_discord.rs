@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+
+use crate::{
+    announcement::{EventAnnouncer, EventThread},
+    duty::DutyRepository,
+    event::{EventRepository, EventStatus},
+    scheduled_event::ScheduledEventMirror,
+    Error,
+};
+
+use super::{sync_event_discord::SyncEventDiscord, UseCase};
+
+/// Sync every public or running event with discord
+/// Recovers what the outbox cannot see, like a scheduled event deleted on discord
+pub struct SyncEventsDiscord<'a> {
+    pub event_repo: &'a dyn EventRepository,
+    pub duty_repo: &'a dyn DutyRepository,
+    pub announcer: &'a dyn EventAnnouncer,
+    pub mirror: &'a dyn ScheduledEventMirror,
+    pub thread: &'a dyn EventThread,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Response {
+    pub synced: usize,
+    /// Events skipped because discord failed, retried on the next run
+    pub failed: usize,
+}
+
+#[async_trait]
+impl<'a> UseCase for SyncEventsDiscord<'a> {
+    type Input = ();
+    type Response = Response;
+
+    async fn system_execute(&self, _: Self::Input) -> Result<Self::Response, Error> {
+        let mut response = Response::default();
+
+        let sync = SyncEventDiscord {
+            event_repo: self.event_repo,
+            duty_repo: self.duty_repo,
+            announcer: self.announcer,
+            mirror: self.mirror,
+            thread: self.thread,
+        };
+        for status in [EventStatus::Public, EventStatus::InProcess] {
+            for event in self.event_repo.list_events(status, None).await? {
+                let input = super::sync_event_discord::Input {
+                    event_id: event.id,
+                    change: None,
+                };
+                match sync.system_execute(input).await {
+                    Ok(()) => response.synced += 1,
+                    Err(Error::Upstream(_) | Error::DiscordUnavailable | Error::Conflict) => {
+                        response.failed += 1
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,235 @@
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    character::{Character, CharacterRepository},
+    duty::{CategoriesAndDuties, Duty, DutyCategory, DutyDetail, DutyPhrase, DutyRepository},
+    event::{Event, EventDraft, EventInfo, EventRepository, EventSchedule, EventSlot, EventStatus},
+    member::{Member, MemberId, MemberRepository, MemberSession, RoleId},
+    outbox::OutboxMessage,
+    permission::{Permission, PermissionService},
+    world::{DataCenter, Location, Region, World, WorldCatalog, WorldDetail, WorldRepository},
+    AccessType, Error,
+};
+
+/// Run a future made of stubs, they never wait
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("Stubs must not wait"),
+    }
+}
+
+/// Draft starting tomorrow
+pub fn draft() -> EventDraft {
+    EventDraft {
+        info: EventInfo {
+            title: "UWU prog".to_string(),
+            description: None,
+        },
+        duty_id: None,
+        schedule: EventSchedule {
+            start_at: Utc::now() + Duration::days(1),
+            duration: Duration::hours(2),
+        },
+        slots: vec![EventSlot { jobs: vec![] }; 8],
+        location: Location::DataCenter("light".to_string()),
+        language: "en".to_string(),
+        travel_allowed: false,
+        thread_enabled: false,
+    }
+}
+
+/// Draft event hosted by the member
+pub fn event(host: MemberId) -> Event {
+    Event::new(Some(host), draft()).unwrap()
+}
+
+/// Active member without roles
+pub fn member(member_id: MemberId) -> Member {
+    Member::new(
+        member_id,
+        format!("Member {}", member_id),
+        String::new(),
+        vec![],
+        Utc::now(),
+    )
+}
+
+/// Every member has the same permissions
+pub struct StubPermissions(pub Vec<Permission>);
+
+#[async_trait]
+impl PermissionService for StubPermissions {
+    async fn permissions(&self, _: &AccessType) -> Result<Vec<Permission>, Error> {
+        Ok(self.0.clone())
+    }
+
+    async fn access_type(&self, member_id: MemberId) -> Result<AccessType, Error> {
+        Ok(AccessType::Member(member_id))
+    }
+
+    fn access_type_with_roles(&self, member_id: MemberId, _: &[RoleId]) -> AccessType {
+        AccessType::Member(member_id)
+    }
+
+    fn check_sign_in(&self, _: &[RoleId]) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Holds a single event, saves always succeed
+pub struct StubEventRepo(pub Event);
+
+#[async_trait]
+impl EventRepository for StubEventRepo {
+    async fn insert_event(&self, _: &Event, _: &[OutboxMessage]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn get_event(&self, event_id: &str) -> Result<Event, Error> {
+        if event_id == self.0.id {
+            Ok(self.0.clone())
+        } else {
+            Err(Error::ItemNotFound)
+        }
+    }
+
+    async fn update_event(&self, _: &mut Event, _: &[OutboxMessage]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn list_events(
+        &self,
+        _: EventStatus,
+        _: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Event>, Error> {
+        Ok(vec![self.0.clone()])
+    }
+}
+
+/// Holds a single member without character or session
+pub struct StubMemberRepo(pub Member);
+
+#[async_trait]
+impl MemberRepository for StubMemberRepo {
+    async fn insert_member_and_session(
+        &self,
+        _: &Member,
+        _: &MemberSession,
+        _: &[OutboxMessage],
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn get_member(&self, member_id: MemberId) -> Result<Member, Error> {
+        if member_id == self.0.id {
+            Ok(self.0.clone())
+        } else {
+            Err(Error::ItemNotFound)
+        }
+    }
+
+    async fn update_member(&self, _: &Member) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn list_stale_members(&self, _: DateTime<Utc>, _: usize) -> Result<Vec<Member>, Error> {
+        Ok(vec![])
+    }
+
+    async fn get_member_session(&self, _: &str) -> Result<MemberSession, Error> {
+        Err(Error::ItemNotFound)
+    }
+
+    async fn update_member_session(&self, _: &MemberSession) -> Result<(), Error> {
+        Err(Error::ItemNotFound)
+    }
+
+    async fn list_member_sessions(&self, _: MemberId) -> Result<Vec<MemberSession>, Error> {
+        Ok(vec![])
+    }
+
+    async fn delete_member_session(&self, _: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn revoke_member_sessions(&self, _: MemberId) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CharacterRepository for StubMemberRepo {
+    async fn get_character(&self, _: MemberId) -> Result<Character, Error> {
+        Err(Error::ItemNotFound)
+    }
+
+    async fn set_character(&self, _: &Character) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Empty duty and world catalog
+pub struct EmptyCatalog;
+
+#[async_trait]
+impl DutyRepository for EmptyCatalog {
+    async fn insert_categories(&self, _: &[DutyCategory]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn insert_duties(&self, _: &[(Duty, &[DutyPhrase])]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn list_categories(&self, _: Option<&str>) -> Result<Vec<DutyCategory>, Error> {
+        Ok(vec![])
+    }
+
+    async fn list_duties(&self, _: &str) -> Result<Vec<Duty>, Error> {
+        Ok(vec![])
+    }
+
+    async fn list_categories_and_duties(&self, _: &str) -> Result<CategoriesAndDuties, Error> {
+        Err(Error::ItemNotFound)
+    }
+
+    async fn delete_duty(&self, _: &str) -> Result<(), Error> {
+        Err(Error::ItemNotFound)
+    }
+
+    async fn get_duty(&self, _: &str) -> Result<DutyDetail, Error> {
+        Err(Error::ItemNotFound)
+    }
+}
+
+#[async_trait]
+impl WorldRepository for EmptyCatalog {
+    async fn insert_catalog(
+        &self,
+        _: &[Region],
+        _: &[DataCenter],
+        _: &[World],
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn get_catalog(&self) -> Result<WorldCatalog, Error> {
+        Ok(WorldCatalog {
+            regions: vec![],
+            data_centers: vec![],
+            worlds: vec![],
+        })
+    }
+
+    async fn get_world(&self, _: &str) -> Result<WorldDetail, Error> {
+        Err(Error::ItemNotFound)
+    }
+}
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use minibell::{member, Error};
+
+/// Sign in as a fixed test member without discord, for offline development and tests
+/// Only compiled with the dev-identity feature, which replaces discord sign in entirely
+#[derive(Debug, Clone)]
+pub struct DevIdentityProvider {
+    member_id: member::MemberId,
+    roles: Vec<member::RoleId>,
+}
+
+impl DevIdentityProvider {
+    pub fn from_env() -> Self {
+        let member_id = std::env::var("DEV_MEMBER_ID")
+            .expect("DEV_MEMBER_ID must be set with the dev-identity feature")
+            .parse::<u64>()
+            .expect("DEV_MEMBER_ID must be a number");
+        let roles = std::env::var("DEV_MEMBER_ROLE_IDS")
+            .map(|ids| {
+                ids.split(',')
+                    .map(|id| {
+                        id.trim()
+                            .parse::<u64>()
+                            .expect("DEV_MEMBER_ROLE_IDS must be comma separated numbers")
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { member_id, roles }
+    }
+}
+
+#[async_trait]
+impl member::IdentityProvider for DevIdentityProvider {
+    /// Redirect straight back with a dummy code
+    fn get_oauth2_url(&self, redirect_uri: &str, state: &str, _code_challenge: &str) -> String {
+        let separator = if redirect_uri.contains('?') { '&' } else { '?' };
+        format!(
+            "{}{}code=dev&state={}",
+            redirect_uri,
+            separator,
+            url_escape::encode_www_form_urlencoded(state)
+        )
+    }
+
+    async fn sign_in(
+        &self,
+        _code: &str,
+        _redirect_uri: &str,
+        _code_verifier: &str,
+    ) -> Result<member::Member, Error> {
+        Ok(member::Member::new(
+            self.member_id,
+            format!("Dev member {}", self.member_id),
+            "https://cdn.discordapp.com/embed/avatars/0.png".to_string(),
+            self.roles.clone(),
+            Utc::now(),
+        ))
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use minibell::{
+    announcement::{Announcement, AnnouncementStatus, EventAnnouncer},
+    Error,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shaku::Component;
+
+use super::{http::DiscordHttp, reqwest_error_to_error};
+
+const COLOR_OPEN: u32 = 0x5865f2;
+const COLOR_STARTED: u32 = 0x57f287;
+const COLOR_CLOSED: u32 = 0x99aab5;
+
+/// Post event announcements as embeds to a discord channel
+#[derive(Clone, Component)]
+#[shaku(interface = EventAnnouncer)]
+pub struct DiscordAnnouncerImpl {
+    http: Arc<DiscordHttp>,
+    api_url: String,
+
+    token: String,
+    /// Announcement channel, disabled when not set
+    channel_id: Option<u64>,
+}
+
+/// Build the message payload
+fn message(announcement: &Announcement) -> Value {
+    let start_at = announcement.start_at.timestamp();
+    let (title, color) = match announcement.status {
+        AnnouncementStatus::Open => (announcement.title.clone(), COLOR_OPEN),
+        AnnouncementStatus::Started => (format!("[Started] {}", announcement.title), COLOR_STARTED),
+        AnnouncementStatus::Finished => {
+            (format!("[Finished] {}", announcement.title), COLOR_CLOSED)
+        }
+        AnnouncementStatus::Cancelled => {
+            (format!("[Cancelled] {}", announcement.title), COLOR_CLOSED)
+        }
+    };
+
+    let mut embed = json!({
+        "title": title,
+        "color": color,
+        "fields": [
+            {
+                "name": "Start",
+                "value": format!("<t:{}:F> (<t:{}:R>)", start_at, start_at),
+                "inline": true,
+            },
+            {
+                "name": "Open slots",
+                "value": format!("{}/{}", announcement.open_slots, announcement.total_slots),
+                "inline": true,
+            },
+        ],
+    });
+    if let Some(description) = &announcement.description {
+        embed["description"] = json!(description);
+    }
+    if let Some(image) = &announcement.image {
+        embed["thumbnail"] = json!({ "url": image });
+    }
+
+    // Only open events can be interacted with
+    let components = match announcement.status {
+        AnnouncementStatus::Open => json!([{
+            "type": 1,
+            "components": [{
+                "type": 2,
+                "style": 1,
+                "label": "Details",
+                "custom_id": format!("event:show:{}", announcement.event_id),
+            }],
+        }]),
+        _ => json!([]),
+    };
+
+    json!({
+        "embeds": [embed],
+        "components": components,
+    })
+}
+
+#[async_trait]
+impl EventAnnouncer for DiscordAnnouncerImpl {
+    async fn post(&self, announcement: &Announcement) -> Result<Option<String>, Error> {
+        #[derive(Debug, Deserialize)]
+        struct MessagePayload {
+            id: String,
+        }
+
+        let Some(channel_id) = self.channel_id else {
+            return Ok(None);
+        };
+
+        self.http
+            .post(format!("{}/channels/{}/messages", self.api_url, channel_id))
+            .header("Authorization", format!("Bot {}", self.token))
+            .json(&message(announcement))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?
+            .json::<MessagePayload>()
+            .await
+            .map(|payload| Some(payload.id))
+            .map_err(reqwest_error_to_error)
+    }
+
+    async fn edit(&self, message_id: &str, announcement: &Announcement) -> Result<(), Error> {
+        let Some(channel_id) = self.channel_id else {
+            return Ok(());
+        };
+
+        self.http
+            .patch(format!(
+                "{}/channels/{}/messages/{}",
+                self.api_url, channel_id, message_id
+            ))
+            .header("Authorization", format!("Bot {}", self.token))
+            .json(&message(announcement))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::Utc;
+    use minibell::announcement::{Announcement, AnnouncementStatus, EventAnnouncer};
+
+    use super::DiscordAnnouncerImpl;
+    use crate::discord::{http::DiscordHttp, tests::mock_server};
+
+    fn announcement(status: AnnouncementStatus) -> Announcement {
+        Announcement {
+            event_id: "event".to_string(),
+            title: "UWU prog".to_string(),
+            description: None,
+            image: None,
+            start_at: Utc::now(),
+            open_slots: 3,
+            total_slots: 8,
+            status,
+        }
+    }
+
+    #[tokio::test]
+    async fn post_and_edit() {
+        let api_url = mock_server(vec![(200, r#"{"id":"5678"}"#), (200, r#"{"id":"5678"}"#)]).await;
+        let announcer = DiscordAnnouncerImpl {
+            http: Arc::new(DiscordHttp::new(reqwest::Client::new())),
+            api_url,
+            token: "token".to_string(),
+            channel_id: Some(1),
+        };
+
+        let message_id = announcer
+            .post(&announcement(AnnouncementStatus::Open))
+            .await
+            .unwrap();
+        assert_eq!(message_id.as_deref(), Some("5678"));
+
+        announcer
+            .edit("5678", &announcement(AnnouncementStatus::Cancelled))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn disabled_without_channel() {
+        // Any request would fail, nothing listens there
+        let announcer = DiscordAnnouncerImpl {
+            http: Arc::new(DiscordHttp::new(reqwest::Client::new())),
+            api_url: "http://127.0.0.1:9".to_string(),
+            token: "token".to_string(),
+            channel_id: None,
+        };
+
+        let message_id = announcer
+            .post(&announcement(AnnouncementStatus::Open))
+            .await
+            .unwrap();
+        assert_eq!(message_id, None);
+    }
+}
@@ -0,0 +1,394 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use minibell::Error;
+use rand::Rng;
+use reqwest::{header::HeaderMap, IntoUrl, Method, Request, Response, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tokio::time::{sleep_until, Instant};
+
+/// Retries after a rate limit, server error or network error
+/// Requests that are not idempotent are only retried when they surely did not run
+const MAX_RETRIES: u32 = 3;
+const BACKOFF_BASE: Duration = Duration::from_millis(500);
+/// Report unavailable instead of waiting longer than this for a rate limit
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Failed discord request, after the retries
+#[derive(Debug)]
+pub enum DiscordError {
+    /// Rate limited for too long, server or network failure
+    Unavailable,
+    /// 404, the resource does not exist or was deleted on discord
+    NotFound,
+    /// 401 or 403, the bot token or its permissions were refused
+    Unauthorized(StatusCode),
+    /// Any other 4xx, the request itself was refused
+    Rejected { status: StatusCode, message: String },
+    /// The request could not be built or the response not read
+    Request(String),
+}
+
+impl DiscordError {
+    /// Error of a 4xx response other than 429
+    async fn from_response(response: Response) -> Self {
+        match response.status() {
+            StatusCode::NOT_FOUND => Self::NotFound,
+            status @ (StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => {
+                Self::Unauthorized(status)
+            }
+            status => Self::Rejected {
+                status,
+                message: response.text().await.unwrap_or_default(),
+            },
+        }
+    }
+}
+
+impl From<reqwest::Error> for DiscordError {
+    fn from(value: reqwest::Error) -> Self {
+        if value.is_connect() || value.is_timeout() {
+            Self::Unavailable
+        } else {
+            Self::Request(value.to_string())
+        }
+    }
+}
+
+impl From<DiscordError> for Error {
+    fn from(value: DiscordError) -> Self {
+        match value {
+            DiscordError::Unavailable => Error::DiscordUnavailable,
+            DiscordError::NotFound => Error::upstream("Discord resource not found."),
+            DiscordError::Unauthorized(status) => Error::upstream(format!(
+                "Discord refused the bot token or its permissions ({}).",
+                status
+            )),
+            DiscordError::Rejected { status, message } => Error::upstream(format!(
+                "Discord rejected the request ({}): {}",
+                status, message
+            )),
+            DiscordError::Request(message) => Error::upstream(message),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct RateLimits {
+    /// Set when the bot hit the global rate limit
+    global_until: Option<Instant>,
+    /// Routes with an exhausted bucket, until it resets
+    routes: HashMap<String, Instant>,
+}
+
+/// Shared request layer for the discord API
+/// Wait on per-route and global rate limits, and retry rate limited, server and network errors
+/// Responses with an error status are returned as a `DiscordError`
+#[derive(Debug)]
+pub struct DiscordHttp {
+    client: reqwest::Client,
+    rate_limits: Mutex<RateLimits>,
+}
+
+/// Request builder sent through the rate limits
+pub struct DiscordRequest<'a> {
+    http: &'a DiscordHttp,
+    builder: reqwest::RequestBuilder,
+}
+
+impl<'a> DiscordRequest<'a> {
+    pub fn header(self, key: &str, value: impl AsRef<str>) -> Self {
+        Self {
+            builder: self.builder.header(key, value.as_ref()),
+            ..self
+        }
+    }
+
+    pub fn json<T: Serialize + ?Sized>(self, json: &T) -> Self {
+        Self {
+            builder: self.builder.json(json),
+            ..self
+        }
+    }
+
+    pub fn form<T: Serialize + ?Sized>(self, form: &T) -> Self {
+        Self {
+            builder: self.builder.form(form),
+            ..self
+        }
+    }
+
+    pub async fn send(self) -> Result<Response, DiscordError> {
+        let request = self.builder.build()?;
+        self.http.execute(request).await
+    }
+}
+
+/// Rate limit key, ids are replaced except the major parameters
+/// e.g. `PATCH /channels/1/messages/:id`
+fn route(method: &Method, url: &Url) -> String {
+    let mut major = false;
+    let path = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .map(|segment| {
+            let keep = major || !segment.chars().all(|c| c.is_ascii_digit());
+            major = matches!(segment, "channels" | "guilds" | "webhooks");
+            if keep {
+                segment
+            } else {
+                ":id"
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    format!("{} /{}", method, path)
+}
+
+fn header_seconds(headers: &HeaderMap, name: &str) -> Option<Duration> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<f64>().ok())
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+}
+
+/// Exponential backoff with up to 50% jitter
+fn backoff(attempt: u32) -> Duration {
+    let base = BACKOFF_BASE * 2u32.pow(attempt);
+    let jitter = rand::thread_rng().gen_range(0..=base.as_millis() as u64 / 2);
+
+    base + Duration::from_millis(jitter)
+}
+
+impl DiscordHttp {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            rate_limits: Mutex::new(RateLimits::default()),
+        }
+    }
+
+    fn request(&self, method: Method, url: impl IntoUrl) -> DiscordRequest<'_> {
+        DiscordRequest {
+            http: self,
+            builder: self.client.request(method, url),
+        }
+    }
+
+    pub fn get(&self, url: impl IntoUrl) -> DiscordRequest<'_> {
+        self.request(Method::GET, url)
+    }
+
+    pub fn post(&self, url: impl IntoUrl) -> DiscordRequest<'_> {
+        self.request(Method::POST, url)
+    }
+
+    pub fn put(&self, url: impl IntoUrl) -> DiscordRequest<'_> {
+        self.request(Method::PUT, url)
+    }
+
+    pub fn patch(&self, url: impl IntoUrl) -> DiscordRequest<'_> {
+        self.request(Method::PATCH, url)
+    }
+
+    /// Wait until neither the global nor the route limit is exhausted
+    async fn wait(&self, route: &str) {
+        let until = {
+            let rate_limits = self.rate_limits.lock().unwrap();
+            rate_limits
+                .global_until
+                .into_iter()
+                .chain(rate_limits.routes.get(route).copied())
+                .max()
+        };
+
+        if let Some(until) = until {
+            sleep_until(until).await;
+        }
+    }
+
+    /// Remember the route bucket once it is exhausted
+    fn update(&self, route: &str, headers: &HeaderMap) {
+        let remaining = headers
+            .get("X-RateLimit-Remaining")
+            .and_then(|value| value.to_str().ok());
+        let reset_after = header_seconds(headers, "X-RateLimit-Reset-After");
+
+        let mut rate_limits = self.rate_limits.lock().unwrap();
+        match (remaining, reset_after) {
+            (Some("0"), Some(reset_after)) => {
+                rate_limits
+                    .routes
+                    .insert(route.to_string(), Instant::now() + reset_after);
+            }
+            _ => {
+                rate_limits.routes.remove(route);
+            }
+        }
+    }
+
+    /// Record a 429, return how long to wait before retrying
+    async fn rate_limited(&self, route: &str, response: Response) -> Duration {
+        #[derive(Debug, Deserialize)]
+        struct RateLimitPayload {
+            retry_after: f64,
+            #[serde(default)]
+            global: bool,
+        }
+
+        let headers = response.headers().clone();
+        let payload = response.json::<RateLimitPayload>().await.ok();
+        let retry_after = payload
+            .as_ref()
+            .and_then(|payload| Duration::try_from_secs_f64(payload.retry_after).ok())
+            .or_else(|| header_seconds(&headers, "Retry-After"))
+            .unwrap_or(BACKOFF_BASE);
+        let global = payload.is_some_and(|payload| payload.global)
+            || headers.contains_key("X-RateLimit-Global");
+
+        let until = Instant::now() + retry_after;
+        let mut rate_limits = self.rate_limits.lock().unwrap();
+        if global {
+            rate_limits.global_until = Some(until);
+        } else {
+            rate_limits.routes.insert(route.to_string(), until);
+        }
+
+        retry_after
+    }
+
+    async fn execute(&self, request: Request) -> Result<Response, DiscordError> {
+        let route = route(request.method(), request.url());
+        // A server error or a timeout may come after the request ran, e.g. a message was posted
+        let idempotent = matches!(
+            *request.method(),
+            Method::GET | Method::PUT | Method::DELETE
+        );
+
+        let mut attempt = 0;
+        loop {
+            self.wait(&route).await;
+
+            let attempt_request = request.try_clone().ok_or(DiscordError::Request(
+                "Discord request body is not retryable.".to_string(),
+            ))?;
+            match self.client.execute(attempt_request).await {
+                // Rate limited requests did not run
+                Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                    let retry_after = self.rate_limited(&route, response).await;
+                    if attempt >= MAX_RETRIES || retry_after > MAX_RETRY_AFTER {
+                        return Err(DiscordError::Unavailable);
+                    }
+                }
+                Ok(response) if response.status().is_server_error() => {
+                    if !idempotent || attempt >= MAX_RETRIES {
+                        return Err(DiscordError::Unavailable);
+                    }
+                    tokio::time::sleep(backoff(attempt)).await;
+                }
+                Ok(response) if response.status().is_client_error() => {
+                    self.update(&route, response.headers());
+                    return Err(DiscordError::from_response(response).await);
+                }
+                Ok(response) => {
+                    self.update(&route, response.headers());
+                    return Ok(response);
+                }
+                // Connect errors happen before anything is sent
+                Err(e)
+                    if attempt < MAX_RETRIES
+                        && (e.is_connect() || (idempotent && e.is_timeout())) =>
+                {
+                    tokio::time::sleep(backoff(attempt)).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::{Method, Url};
+
+    use super::{route, DiscordError, DiscordHttp};
+    use crate::discord::tests::mock_server;
+
+    #[test]
+    fn route_keeps_major_parameters() {
+        let url = Url::parse("https://discord.com/api/v10/channels/1/messages/2").unwrap();
+        assert_eq!(
+            route(&Method::PATCH, &url),
+            "PATCH /api/v10/channels/1/messages/:id"
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_after_rate_limit() {
+        let api_url = mock_server(vec![
+            (
+                429,
+                r#"{"message":"You are being rate limited.","retry_after":0.05,"global":false}"#,
+            ),
+            (500, r#"{"message":"Internal Server Error"}"#),
+            (200, r#"{"id":"1"}"#),
+        ])
+        .await;
+        let http = DiscordHttp::new(reqwest::Client::new());
+
+        let response = http
+            .get(format!("{}/channels/1", api_url))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn post_is_not_retried_after_server_error() {
+        let api_url = mock_server(vec![
+            (500, r#"{"message":"Internal Server Error"}"#),
+            (200, r#"{"id":"1"}"#),
+        ])
+        .await;
+        let http = DiscordHttp::new(reqwest::Client::new());
+
+        let result = http
+            .post(format!("{}/channels/1/messages", api_url))
+            .send()
+            .await;
+
+        assert!(matches!(result, Err(DiscordError::Unavailable)));
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_typed() {
+        let api_url = mock_server(vec![
+            (404, r#"{"message":"Unknown Message","code":10008}"#),
+            (403, r#"{"message":"Missing Permissions","code":50013}"#),
+            (400, r#"{"message":"Invalid Form Body","code":50035}"#),
+        ])
+        .await;
+        let http = DiscordHttp::new(reqwest::Client::new());
+        let url = format!("{}/channels/1/messages/2", api_url);
+
+        assert!(matches!(
+            http.get(&url).send().await,
+            Err(DiscordError::NotFound)
+        ));
+        assert!(matches!(
+            http.get(&url).send().await,
+            Err(DiscordError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            http.get(&url).send().await,
+            Err(DiscordError::Rejected { status, message })
+                if status == 400 && message.contains("50035")
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use minibell::{member, Error};
+use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr};
+use shaku::Component;
+
+use super::{
+    http::{DiscordError, DiscordHttp},
+    reqwest_error_to_error,
+};
+
+/// Sign in with discord Oauth2, the member is then fetched from the guild
+#[derive(Clone, Component)]
+#[shaku(interface = member::IdentityProvider)]
+pub struct DiscordIdentityProviderImpl {
+    #[shaku(inject)]
+    discord_client: Arc<dyn member::DiscordClient>,
+
+    http: Arc<DiscordHttp>,
+    /// Discord API base URL, e.g. `https://discord.com/api/v10`
+    api_url: String,
+
+    client_id: String,
+    client_secret: String,
+}
+
+impl DiscordIdentityProviderImpl {
+    /// Fetch access token from Oauth2 code
+    async fn fetch_user_tokens(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<String, Error> {
+        #[derive(Debug, Deserialize)]
+        struct AuthorizationResult {
+            access_token: String,
+        }
+
+        let response = self
+            .http
+            .post(format!("{}/oauth2/token", self.api_url))
+            .form(&[
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+                ("code_verifier", code_verifier),
+            ])
+            .send()
+            .await;
+        let response = match response {
+            // Invalid, expired or already used code, or a verifier not matching the challenge
+            Err(
+                DiscordError::Unauthorized(reqwest::StatusCode::UNAUTHORIZED)
+                | DiscordError::Rejected {
+                    status: reqwest::StatusCode::BAD_REQUEST,
+                    ..
+                },
+            ) => return Err(Error::InvalidOAuth2Code),
+            response => response?,
+        };
+
+        response
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?
+            .json::<AuthorizationResult>()
+            .await
+            .map(|payload| payload.access_token)
+            .map_err(reqwest_error_to_error)
+    }
+
+    /// Fetch user id from access token
+    async fn fetch_user_id(&self, access_token: &str) -> Result<u64, Error> {
+        #[serde_as]
+        #[derive(Deserialize)]
+        struct Payload {
+            #[serde_as(as = "DisplayFromStr")]
+            id: u64,
+        }
+
+        self.http
+            .get(format!("{}/users/@me", self.api_url))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?
+            .json::<Payload>()
+            .await
+            .map(|payload| payload.id)
+            .map_err(reqwest_error_to_error)
+    }
+}
+
+#[async_trait]
+impl member::IdentityProvider for DiscordIdentityProviderImpl {
+    fn get_oauth2_url(&self, redirect_uri: &str, state: &str, code_challenge: &str) -> String {
+        format!(
+            "https://discord.com/api/oauth2/authorize?client_id={}&redirect_uri={}&response_type=code&scope=identify&state={}&code_challenge={}&code_challenge_method=S256",
+            self.client_id,
+            url_escape::encode_www_form_urlencoded(redirect_uri),
+            url_escape::encode_www_form_urlencoded(state),
+            code_challenge,
+        )
+    }
+
+    async fn sign_in(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> Result<member::Member, Error> {
+        let access_token = self
+            .fetch_user_tokens(code, redirect_uri, code_verifier)
+            .await?;
+        let user_id = self.fetch_user_id(&access_token).await?;
+
+        match self.discord_client.get_member(user_id).await {
+            Err(Error::ItemNotFound) => Err(Error::NotGuildMember),
+            result => result,
+        }
+    }
+}
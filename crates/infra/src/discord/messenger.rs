@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use minibell::{
+    member::MemberId,
+    notification::{DirectMessage, DirectMessenger},
+    Error,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shaku::Component;
+
+use super::{http::DiscordHttp, reqwest_error_to_error};
+
+/// Send direct messages through the bot
+#[derive(Clone, Component)]
+#[shaku(interface = DirectMessenger)]
+pub struct DiscordMessengerImpl {
+    http: Arc<DiscordHttp>,
+    api_url: String,
+
+    token: String,
+}
+
+fn content(message: &DirectMessage) -> String {
+    match message {
+        DirectMessage::Reminder {
+            title, start_at, ..
+        } => format!(
+            "Reminder: **{}** starts <t:{}:R>.",
+            title,
+            start_at.timestamp()
+        ),
+        DirectMessage::HostReminder {
+            title,
+            start_at,
+            open_slots,
+            ..
+        } => format!(
+            "Reminder: **{}** starts <t:{}:R>, and the roster still has {} open slot(s).",
+            title,
+            start_at.timestamp(),
+            open_slots
+        ),
+        DirectMessage::ReconfirmationRequested {
+            title,
+            old_start_at,
+            new_start_at,
+            deadline,
+            ..
+        } => format!(
+            "**{}** moved from <t:{}:F> to <t:{}:F>. Please confirm you can still make it before <t:{}:f>, or your slot opens to the waitlist.",
+            title,
+            old_start_at.timestamp(),
+            new_start_at.timestamp(),
+            deadline.timestamp()
+        ),
+        DirectMessage::RosterChanged {
+            title,
+            member_id,
+            joined: true,
+            ..
+        } => format!("<@{}> joined the roster of **{}**.", member_id, title),
+        DirectMessage::RosterChanged {
+            title,
+            member_id,
+            joined: false,
+            ..
+        } => format!("<@{}> left the roster of **{}**.", member_id, title),
+        DirectMessage::WaitlistPromoted {
+            title, start_at, ..
+        } => format!(
+            "A slot opened up, you are now in the roster of **{}** starting <t:{}:F>.",
+            title,
+            start_at.timestamp()
+        ),
+        DirectMessage::EventCancelled {
+            title, start_at, ..
+        } => format!(
+            "**{}** planned <t:{}:F> has been cancelled.",
+            title,
+            start_at.timestamp()
+        ),
+    }
+}
+
+/// Buttons under the message, handled by the interactions endpoint
+fn components(message: &DirectMessage) -> Value {
+    match message {
+        DirectMessage::ReconfirmationRequested { event_id, .. } => json!([{
+            "type": 1,
+            "components": [
+                {
+                    "type": 2,
+                    "style": 3,
+                    "label": "Confirm",
+                    "custom_id": format!("event:confirm:{}", event_id),
+                },
+                {
+                    "type": 2,
+                    "style": 4,
+                    "label": "Drop",
+                    "custom_id": format!("event:drop:{}", event_id),
+                },
+            ],
+        }]),
+        _ => json!([]),
+    }
+}
+
+impl DiscordMessengerImpl {
+    /// Open or get the DM channel with the member
+    async fn dm_channel(&self, member_id: MemberId) -> Result<String, Error> {
+        #[derive(Debug, Deserialize)]
+        struct ChannelPayload {
+            id: String,
+        }
+
+        self.http
+            .post(format!("{}/users/@me/channels", self.api_url))
+            .header("Authorization", format!("Bot {}", self.token))
+            .json(&json!({ "recipient_id": member_id.to_string() }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?
+            .json::<ChannelPayload>()
+            .await
+            .map(|payload| payload.id)
+            .map_err(reqwest_error_to_error)
+    }
+}
+
+#[async_trait]
+impl DirectMessenger for DiscordMessengerImpl {
+    async fn send(&self, member_id: MemberId, message: &DirectMessage) -> Result<(), Error> {
+        let channel_id = self.dm_channel(member_id).await?;
+
+        self.http
+            .post(format!("{}/channels/{}/messages", self.api_url, channel_id))
+            .header("Authorization", format!("Bot {}", self.token))
+            .json(&json!({
+                "content": content(message),
+                "components": components(message),
+            }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?;
+
+        Ok(())
+    }
+}
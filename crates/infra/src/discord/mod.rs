@@ -0,0 +1,249 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr};
+use shaku::Component;
+
+use minibell::{member, Error};
+
+pub mod announcer;
+pub mod http;
+pub mod identity;
+pub mod messenger;
+pub mod scheduled_event;
+pub mod sink;
+pub mod thread;
+
+use http::{DiscordError, DiscordHttp};
+
+/// Discord request client service
+#[derive(Clone, Component)]
+#[shaku(interface = member::DiscordClient)]
+pub struct DiscordClientImpl {
+    http: Arc<DiscordHttp>,
+    /// Discord API base URL, e.g. `https://discord.com/api/v10`
+    api_url: String,
+
+    guild_id: u64,
+    token: String,
+    /// Application public key, hex encoded, interactions are rejected without it
+    public_key: Option<String>,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+struct UserPayload {
+    #[serde_as(as = "DisplayFromStr")]
+    id: u64,
+    global_name: String,
+    avatar: Option<String>,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+pub struct MemberPayload {
+    user: UserPayload,
+    nick: Option<String>,
+    avatar: Option<String>,
+    joined_at: DateTime<Utc>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde(default)]
+    roles: Vec<u64>,
+}
+
+/// Discord being down, slow or rate limiting is reported as unavailable
+fn reqwest_error_to_error(err: reqwest::Error) -> Error {
+    let unavailable = err.is_connect()
+        || err.is_timeout()
+        || err.status().is_some_and(|status| {
+            status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        });
+
+    if unavailable {
+        Error::DiscordUnavailable
+    } else {
+        Error::upstream(err.to_string())
+    }
+}
+
+impl DiscordClientImpl {
+    /// Fetch member info by user id
+    /// Return item not found if the user is not in the guild
+    async fn fetch_member_info(&self, user_id: u64) -> Result<MemberPayload, Error> {
+        let response = self
+            .http
+            .get(format!(
+                "{}/guilds/{}/members/{}",
+                self.api_url, self.guild_id, user_id
+            ))
+            .header("Authorization", format!("Bot {}", self.token))
+            .send()
+            .await;
+        let response = match response {
+            Err(DiscordError::NotFound) => return Err(Error::ItemNotFound),
+            response => response?,
+        };
+
+        response
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?
+            .json::<MemberPayload>()
+            .await
+            .map_err(reqwest_error_to_error)
+    }
+
+    /// Fetch the guild member and convert it to a member
+    async fn member(&self, user_id: u64) -> Result<member::Member, Error> {
+        let payload = self.fetch_member_info(user_id).await?;
+        let avatar_url = self.avatar_url(&payload);
+
+        Ok(member::Member::new(
+            payload.user.id,
+            payload
+                .nick
+                .clone()
+                .unwrap_or(payload.user.global_name.clone()),
+            avatar_url,
+            payload.roles,
+            payload.joined_at,
+        ))
+    }
+
+    fn avatar_url(&self, payload: &MemberPayload) -> String {
+        if let Some(avatar) = &payload.avatar {
+            format!(
+                "https://cdn.discordapp.com/guilds/{}/users/{}/avatars/{}.png",
+                self.guild_id, payload.user.id, avatar
+            )
+        } else if let Some(avatar) = &payload.user.avatar {
+            format!(
+                "https://cdn.discordapp.com/avatars/{}/{}.png",
+                payload.user.id, avatar
+            )
+        } else {
+            let index = (payload.user.id >> 22) % 6;
+            format!("https://cdn.discordapp.com/embed/avatars/{}.png", index)
+        }
+    }
+
+    /// Verify interaction signature, signed over timestamp and raw body
+    fn verify_interaction(
+        &self,
+        signature: &str,
+        timestamp: &str,
+        body: &[u8],
+    ) -> Result<(), Error> {
+        let public_key = self
+            .public_key
+            .as_deref()
+            .ok_or(Error::internal("Discord public key is not set."))?;
+        let public_key: [u8; 32] = hex::decode(public_key)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(Error::internal("Discord public key is invalid."))?;
+        let public_key = VerifyingKey::from_bytes(&public_key)
+            .map_err(|_| Error::internal("Discord public key is invalid."))?;
+
+        let signature: [u8; 64] = hex::decode(signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(Error::InvalidToken)?;
+        let signature = Signature::from_bytes(&signature);
+
+        let message = [timestamp.as_bytes(), body].concat();
+        public_key
+            .verify_strict(&message, &signature)
+            .map_err(|_| Error::InvalidToken)
+    }
+}
+
+#[async_trait]
+impl member::DiscordClient for DiscordClientImpl {
+    async fn get_member(&self, member_id: member::MemberId) -> Result<member::Member, Error> {
+        self.member(member_id).await
+    }
+
+    fn verify_interaction(
+        &self,
+        signature: &str,
+        timestamp: &str,
+        body: &[u8],
+    ) -> Result<(), Error> {
+        self.verify_interaction(signature, timestamp, body)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::Arc;
+
+    use ed25519_dalek::{Signer, SigningKey};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::{DiscordClientImpl, DiscordHttp};
+
+    /// Serve the given responses in order, one request per connection
+    pub(crate) async fn mock_server(responses: Vec<(u16, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await.unwrap();
+
+                let response = format!(
+                    "HTTP/1.1 {} OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn verify_interaction() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let client = DiscordClientImpl {
+            http: Arc::new(DiscordHttp::new(reqwest::Client::new())),
+            api_url: "https://discord.com/api/v10".to_string(),
+            guild_id: 1,
+            token: "token".to_string(),
+            public_key: Some(hex::encode(signing_key.verifying_key().as_bytes())),
+        };
+
+        let body = br#"{"type":1}"#;
+        let signature = hex::encode(
+            signing_key
+                .sign(&[b"1700000000".as_slice(), body].concat())
+                .to_bytes(),
+        );
+
+        assert!(client
+            .verify_interaction(&signature, "1700000000", body)
+            .is_ok());
+        assert!(client
+            .verify_interaction(&signature, "1700000001", body)
+            .is_err());
+        assert!(client.verify_interaction("zz", "1700000000", body).is_err());
+
+        let unset = DiscordClientImpl {
+            public_key: None,
+            ..client
+        };
+        assert!(unset
+            .verify_interaction(&signature, "1700000000", body)
+            .is_err());
+    }
+}
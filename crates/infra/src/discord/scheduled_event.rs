@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use minibell::{
+    scheduled_event::{
+        ScheduledEvent, ScheduledEventEnd, ScheduledEventMirror, ScheduledEventStatus,
+    },
+    Error,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shaku::Component;
+
+use super::{
+    http::{DiscordError, DiscordHttp},
+    reqwest_error_to_error,
+};
+
+const PRIVACY_GUILD_ONLY: u8 = 2;
+const ENTITY_EXTERNAL: u8 = 3;
+
+const STATUS_SCHEDULED: u8 = 1;
+const STATUS_ACTIVE: u8 = 2;
+const STATUS_COMPLETED: u8 = 3;
+const STATUS_CANCELED: u8 = 4;
+
+/// Mirror events as discord guild scheduled events
+#[derive(Clone, Component)]
+#[shaku(interface = ScheduledEventMirror)]
+pub struct DiscordScheduledEventImpl {
+    http: Arc<DiscordHttp>,
+    api_url: String,
+
+    guild_id: u64,
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScheduledEventPayload {
+    id: String,
+    status: u8,
+}
+
+fn body(event: &ScheduledEvent) -> Value {
+    json!({
+        "name": event.name,
+        "description": event.description.clone().unwrap_or_default(),
+        "privacy_level": PRIVACY_GUILD_ONLY,
+        "entity_type": ENTITY_EXTERNAL,
+        "entity_metadata": { "location": event.location },
+        "scheduled_start_time": event.start_at.to_rfc3339(),
+        "scheduled_end_time": event.end_at.to_rfc3339(),
+    })
+}
+
+impl DiscordScheduledEventImpl {
+    fn url(&self, id: Option<&str>) -> String {
+        match id {
+            Some(id) => format!(
+                "{}/guilds/{}/scheduled-events/{}",
+                self.api_url, self.guild_id, id
+            ),
+            None => format!("{}/guilds/{}/scheduled-events", self.api_url, self.guild_id),
+        }
+    }
+
+    async fn patch(&self, id: &str, body: &Value) -> Result<(), Error> {
+        self.http
+            .patch(self.url(Some(id)))
+            .header("Authorization", format!("Bot {}", self.token))
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?;
+
+        Ok(())
+    }
+
+    async fn set_status(&self, id: &str, status: u8) -> Result<(), Error> {
+        self.patch(id, &json!({ "status": status })).await
+    }
+}
+
+#[async_trait]
+impl ScheduledEventMirror for DiscordScheduledEventImpl {
+    async fn create(&self, event: &ScheduledEvent) -> Result<String, Error> {
+        self.http
+            .post(self.url(None))
+            .header("Authorization", format!("Bot {}", self.token))
+            .json(&body(event))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?
+            .json::<ScheduledEventPayload>()
+            .await
+            .map(|payload| payload.id)
+            .map_err(reqwest_error_to_error)
+    }
+
+    async fn update(&self, id: &str, event: &ScheduledEvent) -> Result<(), Error> {
+        self.patch(id, &body(event)).await
+    }
+
+    /// Discord only allows scheduled -> active -> completed, or scheduled -> canceled
+    async fn end(&self, id: &str, end: ScheduledEventEnd) -> Result<(), Error> {
+        let Some(status) = self.status(id).await? else {
+            return Ok(());
+        };
+
+        match (status, end) {
+            (ScheduledEventStatus::Scheduled, ScheduledEventEnd::Cancelled) => {
+                self.set_status(id, STATUS_CANCELED).await
+            }
+            (ScheduledEventStatus::Scheduled, ScheduledEventEnd::Finished) => {
+                self.set_status(id, STATUS_ACTIVE).await?;
+                self.set_status(id, STATUS_COMPLETED).await
+            }
+            (ScheduledEventStatus::Active, _) => self.set_status(id, STATUS_COMPLETED).await,
+            (ScheduledEventStatus::Completed | ScheduledEventStatus::Cancelled, _) => Ok(()),
+        }
+    }
+
+    async fn status(&self, id: &str) -> Result<Option<ScheduledEventStatus>, Error> {
+        let response = self
+            .http
+            .get(self.url(Some(id)))
+            .header("Authorization", format!("Bot {}", self.token))
+            .send()
+            .await;
+        let response = match response {
+            Err(DiscordError::NotFound) => return Ok(None),
+            response => response?,
+        };
+
+        let payload = response
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?
+            .json::<ScheduledEventPayload>()
+            .await
+            .map_err(reqwest_error_to_error)?;
+
+        match payload.status {
+            STATUS_SCHEDULED => Ok(Some(ScheduledEventStatus::Scheduled)),
+            STATUS_ACTIVE => Ok(Some(ScheduledEventStatus::Active)),
+            STATUS_COMPLETED => Ok(Some(ScheduledEventStatus::Completed)),
+            STATUS_CANCELED => Ok(Some(ScheduledEventStatus::Cancelled)),
+            status => Err(Error::upstream(format!(
+                "Unknown scheduled event status {}",
+                status
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::{Duration, Utc};
+    use minibell::scheduled_event::{ScheduledEvent, ScheduledEventMirror, ScheduledEventStatus};
+
+    use super::DiscordScheduledEventImpl;
+    use crate::discord::{http::DiscordHttp, tests::mock_server};
+
+    #[tokio::test]
+    async fn create_and_status() {
+        let api_url = mock_server(vec![
+            (200, r#"{"id":"1234","status":1}"#),
+            (200, r#"{"id":"1234","status":2}"#),
+            (
+                404,
+                r#"{"message":"Unknown Guild Scheduled Event","code":10070}"#,
+            ),
+        ])
+        .await;
+        let mirror = DiscordScheduledEventImpl {
+            http: Arc::new(DiscordHttp::new(reqwest::Client::new())),
+            api_url,
+            guild_id: 1,
+            token: "token".to_string(),
+        };
+
+        let start_at = Utc::now() + Duration::hours(1);
+        let id = mirror
+            .create(&ScheduledEvent {
+                event_id: "event".to_string(),
+                name: "UWU prog".to_string(),
+                description: None,
+                location: "Light".to_string(),
+                start_at,
+                end_at: start_at + Duration::hours(2),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(id, "1234");
+        assert_eq!(
+            mirror.status(&id).await.unwrap(),
+            Some(ScheduledEventStatus::Active)
+        );
+        assert_eq!(mirror.status(&id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn unknown_status() {
+        let api_url = mock_server(vec![(200, r#"{"id":"1234","status":9}"#)]).await;
+        let mirror = DiscordScheduledEventImpl {
+            http: Arc::new(DiscordHttp::new(reqwest::Client::new())),
+            api_url,
+            guild_id: 1,
+            token: "token".to_string(),
+        };
+
+        assert!(mirror.status("1234").await.is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use minibell::{
+    announcement::{EventAnnouncer, EventThread},
+    duty::DutyRepository,
+    event::EventRepository,
+    notification::{DirectMessenger, NotificationPreferenceRepository},
+    outbox::{DiscordSink, NotificationSink, OutboxMessage, OutboxSink},
+    reconfirmation::ReconfirmationRepository,
+    scheduled_event::ScheduledEventMirror,
+    usecases::{notify_event_change, sync_event_discord, UseCase},
+    AccessType, Error,
+};
+use shaku::Component;
+
+/// Sync the announcement, scheduled event and thread of events from the outbox
+#[derive(Component)]
+#[shaku(interface = DiscordSink)]
+pub struct DiscordSinkImpl {
+    #[shaku(inject)]
+    event_repo: Arc<dyn EventRepository>,
+    #[shaku(inject)]
+    duty_repo: Arc<dyn DutyRepository>,
+    #[shaku(inject)]
+    announcer: Arc<dyn EventAnnouncer>,
+    #[shaku(inject)]
+    mirror: Arc<dyn ScheduledEventMirror>,
+    #[shaku(inject)]
+    thread: Arc<dyn EventThread>,
+}
+
+impl DiscordSink for DiscordSinkImpl {}
+
+#[async_trait]
+impl OutboxSink for DiscordSinkImpl {
+    fn name(&self) -> &str {
+        "discord"
+    }
+
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), Error> {
+        let Some(event_id) = message.event.event_id() else {
+            return Ok(());
+        };
+
+        let sync = sync_event_discord::SyncEventDiscord {
+            event_repo: self.event_repo.as_ref(),
+            duty_repo: self.duty_repo.as_ref(),
+            announcer: self.announcer.as_ref(),
+            mirror: self.mirror.as_ref(),
+            thread: self.thread.as_ref(),
+        };
+        match sync
+            .execute(
+                &AccessType::System,
+                sync_event_discord::Input {
+                    event_id: event_id.to_string(),
+                    change: Some(message.event.clone()),
+                },
+            )
+            .await
+        {
+            // Nothing left to sync
+            Err(Error::ItemNotFound) => Ok(()),
+            result => result,
+        }
+    }
+}
+
+/// Send the direct messages owed for event changes from the outbox
+#[derive(Component)]
+#[shaku(interface = NotificationSink)]
+pub struct NotificationSinkImpl {
+    #[shaku(inject)]
+    event_repo: Arc<dyn EventRepository>,
+    #[shaku(inject)]
+    preference_repo: Arc<dyn NotificationPreferenceRepository>,
+    #[shaku(inject)]
+    direct_messenger: Arc<dyn DirectMessenger>,
+    #[shaku(inject)]
+    reconfirmation_repo: Arc<dyn ReconfirmationRepository>,
+    #[shaku(inject)]
+    event_thread: Arc<dyn EventThread>,
+}
+
+impl NotificationSink for NotificationSinkImpl {}
+
+#[async_trait]
+impl OutboxSink for NotificationSinkImpl {
+    fn name(&self) -> &str {
+        "notification"
+    }
+
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), Error> {
+        let notify = notify_event_change::NotifyEventChange {
+            event_repo: self.event_repo.as_ref(),
+            preference_repo: self.preference_repo.as_ref(),
+            direct_messenger: self.direct_messenger.as_ref(),
+            reconfirmation_repo: self.reconfirmation_repo.as_ref(),
+            event_thread: self.event_thread.as_ref(),
+        };
+        match notify
+            .execute(
+                &AccessType::System,
+                notify_event_change::Input {
+                    change: message.event.clone(),
+                    now: Utc::now(),
+                },
+            )
+            .await
+        {
+            Ok(_) | Err(Error::ItemNotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+}
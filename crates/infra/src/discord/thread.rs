@@ -0,0 +1,229 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use minibell::{
+    announcement::{EventThread, ThreadMessage},
+    member::MemberId,
+    Error,
+};
+use serde::Deserialize;
+use serde_json::json;
+use shaku::Component;
+
+use super::{http::DiscordHttp, reqwest_error_to_error};
+
+/// Discord limits thread names to 100 characters
+const THREAD_NAME_MAX: usize = 100;
+/// Archive after a day without activity, in minutes
+const AUTO_ARCHIVE_DURATION: u32 = 1440;
+
+/// Event discussion threads, opened under the announcement messages
+#[derive(Clone, Component)]
+#[shaku(interface = EventThread)]
+pub struct DiscordThreadImpl {
+    http: Arc<DiscordHttp>,
+    api_url: String,
+
+    token: String,
+    /// Announcement channel, disabled when not set
+    channel_id: Option<u64>,
+}
+
+fn mentions(members: &[MemberId]) -> String {
+    members
+        .iter()
+        .map(|id| format!("<@{}>", id))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Message content and the members to ping
+fn content(message: &ThreadMessage) -> (String, Vec<MemberId>) {
+    match message {
+        ThreadMessage::RosterChanged { joined, left } => {
+            let mut lines = vec![];
+            if !joined.is_empty() {
+                lines.push(format!("{} joined the roster.", mentions(joined)));
+            }
+            if !left.is_empty() {
+                lines.push(format!("{} left the roster.", mentions(left)));
+            }
+
+            // Mention without pinging
+            (lines.join("\n"), vec![])
+        }
+        ThreadMessage::Rescheduled {
+            old_start_at,
+            new_start_at,
+        } => (
+            format!(
+                "The event has been rescheduled from <t:{}:F> to <t:{}:F>.",
+                old_start_at.timestamp(),
+                new_start_at.timestamp()
+            ),
+            vec![],
+        ),
+        ThreadMessage::Starting { roster } => (
+            format!("The event is starting! {}", mentions(roster)),
+            roster.clone(),
+        ),
+        ThreadMessage::ReconfirmationRequested { deadline, members } => (
+            format!(
+                "The event moved, please confirm you can still make it before <t:{}:f>. {}",
+                deadline.timestamp(),
+                mentions(members)
+            ),
+            members.clone(),
+        ),
+        ThreadMessage::Reminder { start_at, members } => (
+            format!(
+                "Reminder: the event starts <t:{}:R>. {}",
+                start_at.timestamp(),
+                mentions(members)
+            ),
+            members.clone(),
+        ),
+    }
+}
+
+#[async_trait]
+impl EventThread for DiscordThreadImpl {
+    async fn open(&self, message_id: &str, name: &str) -> Result<Option<String>, Error> {
+        #[derive(Debug, Deserialize)]
+        struct ChannelPayload {
+            id: String,
+        }
+
+        let Some(channel_id) = self.channel_id else {
+            return Ok(None);
+        };
+
+        self.http
+            .post(format!(
+                "{}/channels/{}/messages/{}/threads",
+                self.api_url, channel_id, message_id
+            ))
+            .header("Authorization", format!("Bot {}", self.token))
+            .json(&json!({
+                "name": name.chars().take(THREAD_NAME_MAX).collect::<String>(),
+                "auto_archive_duration": AUTO_ARCHIVE_DURATION,
+            }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?
+            .json::<ChannelPayload>()
+            .await
+            .map(|payload| Some(payload.id))
+            .map_err(reqwest_error_to_error)
+    }
+
+    async fn add_member(&self, thread_id: &str, member_id: MemberId) -> Result<(), Error> {
+        self.http
+            .put(format!(
+                "{}/channels/{}/thread-members/{}",
+                self.api_url, thread_id, member_id
+            ))
+            .header("Authorization", format!("Bot {}", self.token))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?;
+
+        Ok(())
+    }
+
+    async fn post(&self, thread_id: &str, message: &ThreadMessage) -> Result<(), Error> {
+        let (content, pings) = content(message);
+        if content.is_empty() {
+            return Ok(());
+        }
+
+        self.http
+            .post(format!("{}/channels/{}/messages", self.api_url, thread_id))
+            .header("Authorization", format!("Bot {}", self.token))
+            .json(&json!({
+                "content": content,
+                "allowed_mentions": {
+                    "users": pings.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+                },
+            }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?;
+
+        Ok(())
+    }
+
+    async fn archive(&self, thread_id: &str) -> Result<(), Error> {
+        self.http
+            .patch(format!("{}/channels/{}", self.api_url, thread_id))
+            .header("Authorization", format!("Bot {}", self.token))
+            .json(&json!({
+                "archived": true,
+                "locked": true,
+            }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(reqwest_error_to_error)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use minibell::announcement::{EventThread, ThreadMessage};
+
+    use super::DiscordThreadImpl;
+    use crate::discord::{http::DiscordHttp, tests::mock_server};
+
+    #[tokio::test]
+    async fn open_post_and_archive() {
+        let api_url = mock_server(vec![
+            (201, r#"{"id":"4321"}"#),
+            (204, ""),
+            (200, r#"{"id":"9"}"#),
+            (200, r#"{"id":"4321"}"#),
+        ])
+        .await;
+        let thread = DiscordThreadImpl {
+            http: Arc::new(DiscordHttp::new(reqwest::Client::new())),
+            api_url,
+            token: "token".to_string(),
+            channel_id: Some(1),
+        };
+
+        let thread_id = thread.open("5678", "UWU prog").await.unwrap().unwrap();
+        assert_eq!(thread_id, "4321");
+
+        thread.add_member(&thread_id, 2).await.unwrap();
+        thread
+            .post(
+                &thread_id,
+                &ThreadMessage::RosterChanged {
+                    joined: vec![2],
+                    left: vec![],
+                },
+            )
+            .await
+            .unwrap();
+        thread.archive(&thread_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn disabled_without_channel() {
+        let thread = DiscordThreadImpl {
+            http: Arc::new(DiscordHttp::new(reqwest::Client::new())),
+            api_url: "http://127.0.0.1:9".to_string(),
+            token: "token".to_string(),
+            channel_id: None,
+        };
+
+        assert_eq!(thread.open("5678", "UWU prog").await.unwrap(), None);
+    }
+}
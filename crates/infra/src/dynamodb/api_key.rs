@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use minibell::{
+    api_key::{ApiKey, ApiKeyRepository, ApiKeyScope},
+    Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, TimestampMilliSeconds};
+use shaku::Component;
+
+use super::{DynamoClient, PrimaryModel};
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+struct ApiKeyModel {
+    id: String,
+    name: String,
+    secret_hash: String,
+    scopes: Vec<ApiKeyScope>,
+
+    #[serde_as(as = "TimestampMilliSeconds")]
+    created_at: DateTime<Utc>,
+    #[serde_as(as = "Option<TimestampMilliSeconds>")]
+    #[serde(default)]
+    expires_at: Option<DateTime<Utc>>,
+    #[serde_as(as = "Option<TimestampMilliSeconds>")]
+    #[serde(default)]
+    last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<&ApiKey> for ApiKeyModel {
+    fn from(value: &ApiKey) -> Self {
+        Self {
+            id: value.id.clone(),
+            name: value.name.clone(),
+            secret_hash: value.secret_hash.clone(),
+            scopes: value.scopes.clone(),
+
+            created_at: value.created_at,
+            expires_at: value.expires_at,
+            last_used_at: value.last_used_at,
+        }
+    }
+}
+
+impl From<ApiKeyModel> for ApiKey {
+    fn from(value: ApiKeyModel) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            secret_hash: value.secret_hash,
+            scopes: value.scopes,
+
+            created_at: value.created_at,
+            expires_at: value.expires_at,
+            last_used_at: value.last_used_at,
+        }
+    }
+}
+
+impl PrimaryModel for ApiKeyModel {
+    fn data_type(&self) -> String {
+        "ApiKey".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "API_KEY".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        format!("API_KEY#{}", self.id)
+    }
+}
+
+#[derive(Debug, Component)]
+#[shaku(interface = ApiKeyRepository)]
+pub struct ApiKeyRepoImpl {
+    db: Arc<DynamoClient>,
+}
+
+#[async_trait]
+impl ApiKeyRepository for ApiKeyRepoImpl {
+    async fn insert(&self, api_key: &ApiKey) -> Result<(), Error> {
+        self.db.insert_item(ApiKeyModel::from(api_key)).await
+    }
+
+    async fn get(&self, id: &str) -> Result<ApiKey, Error> {
+        self.db
+            .get_item::<ApiKeyModel>("API_KEY", &format!("API_KEY#{}", id))
+            .await
+            .map(Into::into)
+    }
+
+    async fn list(&self) -> Result<Vec<ApiKey>, Error> {
+        self.db
+            .query_items::<ApiKeyModel>(None, "API_KEY", "API_KEY#")
+            .await
+            .map(|api_keys| api_keys.into_iter().map(Into::into).collect())
+    }
+
+    /// Never recreate a key revoked in the meantime
+    async fn update_last_used(&self, api_key: &ApiKey) -> Result<(), Error> {
+        self.db
+            .insert_item_when(ApiKeyModel::from(api_key), "attribute_exists(PK)", &[])
+            .await
+            .map(|_| ())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), Error> {
+        let sort_key = format!("API_KEY#{}", id);
+        self.db
+            .get_item::<ApiKeyModel>("API_KEY", &sort_key)
+            .await?;
+
+        self.db.delete_item("API_KEY", &sort_key).await
+    }
+}
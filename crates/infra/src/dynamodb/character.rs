@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use minibell::{
+    character::{Character, CharacterRepository},
+    member::MemberId,
+    Error,
+};
+use serde::{Deserialize, Serialize};
+use shaku::Component;
+
+use super::{DynamoClient, PrimaryModel};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CharacterModel {
+    member_id: u64,
+    name: String,
+    world_id: String,
+}
+
+impl PrimaryModel for CharacterModel {
+    fn data_type(&self) -> String {
+        "Character".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "CHARACTER".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        format!("CHARACTER#{}", self.member_id)
+    }
+}
+
+impl From<&Character> for CharacterModel {
+    fn from(value: &Character) -> Self {
+        Self {
+            member_id: value.member_id,
+            name: value.name.clone(),
+            world_id: value.world_id.clone(),
+        }
+    }
+}
+
+impl From<CharacterModel> for Character {
+    fn from(value: CharacterModel) -> Self {
+        Character {
+            member_id: value.member_id,
+            name: value.name,
+            world_id: value.world_id,
+        }
+    }
+}
+
+#[derive(Debug, Component)]
+#[shaku(interface = CharacterRepository)]
+pub struct CharacterRepoImpl {
+    db: Arc<DynamoClient>,
+}
+
+#[async_trait]
+impl CharacterRepository for CharacterRepoImpl {
+    async fn get_character(&self, member_id: MemberId) -> Result<Character, Error> {
+        self.db
+            .get_item::<CharacterModel>("CHARACTER", &format!("CHARACTER#{}", member_id))
+            .await
+            .map(Into::into)
+    }
+
+    async fn set_character(&self, character: &Character) -> Result<(), Error> {
+        self.db.insert_item(CharacterModel::from(character)).await
+    }
+}
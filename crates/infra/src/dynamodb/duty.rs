@@ -29,13 +29,13 @@ impl From<&DutyCategory> for DutyCategoryModel {
     }
 }
 
-impl Into<DutyCategory> for DutyCategoryModel {
-    fn into(self) -> DutyCategory {
+impl From<DutyCategoryModel> for DutyCategory {
+    fn from(value: DutyCategoryModel) -> Self {
         DutyCategory {
-            id: self.id,
-            name: self.name,
-            parent: self.parent,
-            sort: self.sort,
+            id: value.id,
+            name: value.name,
+            parent: value.parent,
+            sort: value.sort,
         }
     }
 }
@@ -93,17 +93,17 @@ impl From<&Duty> for DutyModel {
     }
 }
 
-impl Into<Duty> for DutyModel {
-    fn into(self) -> Duty {
+impl From<DutyModel> for Duty {
+    fn from(value: DutyModel) -> Self {
         Duty {
-            id: self.id,
-            category: self.category,
-            name: self.name,
-            description: self.description,
-            short_name: self.short_name,
-            patch: self.patch,
-            image: self.image,
-            sort: self.sort,
+            id: value.id,
+            category: value.category,
+            name: value.name,
+            description: value.description,
+            short_name: value.short_name,
+            patch: value.patch,
+            image: value.image,
+            sort: value.sort,
         }
     }
 }
@@ -147,11 +147,11 @@ impl From<(&Duty, &DutyPhrase)> for DutyPhraseModel {
     }
 }
 
-impl Into<DutyPhrase> for DutyPhraseModel {
-    fn into(self) -> DutyPhrase {
+impl From<DutyPhraseModel> for DutyPhrase {
+    fn from(value: DutyPhraseModel) -> Self {
         DutyPhrase {
-            name: self.name,
-            progression: self.progression,
+            name: value.name,
+            progression: value.progression,
         }
     }
 }
@@ -170,6 +170,21 @@ impl PrimaryModel for DutyPhraseModel {
     }
 }
 
+/// Sort keys of the existing phrases missing from the new ones
+/// A transaction cannot put and delete the same item, so kept phrases are only put
+fn stale_phrases(existing: &[DutyPhraseModel], pharses: &[DutyPhraseModel]) -> Vec<String> {
+    let kept = pharses
+        .iter()
+        .map(PrimaryModel::sort_key)
+        .collect::<Vec<_>>();
+
+    existing
+        .iter()
+        .map(PrimaryModel::sort_key)
+        .filter(|sort_key| !kept.contains(sort_key))
+        .collect()
+}
+
 #[derive(Debug, Component)]
 #[shaku(interface = DutyRepository)]
 pub struct DutyRepoImpl {
@@ -210,19 +225,33 @@ impl DutyRepository for DutyRepoImpl {
     }
 
     /// Insert a duty
-    /// Create or update if exists
+    /// Create or update if exists, replacing its phrases
     async fn insert_duties(&self, duties: &[(Duty, &[DutyPhrase])]) -> Result<(), Error> {
-        let mut command = self.db.batch_insert_items();
+        // One transaction per duty, so a duty never shows old and new phrases together
         for (duty, pharses) in duties {
-            command = command.add_item(DutyModel::from(duty))?.add_items(
-                &pharses
-                    .iter()
-                    .map(|pharse| DutyPhraseModel::from((duty, pharse)))
-                    .collect::<Vec<_>>(),
-            )?;
+            let pharses = pharses
+                .iter()
+                .map(|pharse| DutyPhraseModel::from((duty, pharse)))
+                .collect::<Vec<_>>();
+            let existing = self
+                .db
+                .query_items::<DutyPhraseModel>(None, "DUTY_PHRASE", &format!("DUTY#{}#", duty.id))
+                .await?;
+
+            let mut transaction = self
+                .db
+                .transact_write_items()
+                .put_item(DutyModel::from(duty))?;
+            for sort_key in stale_phrases(&existing, &pharses) {
+                transaction = transaction.delete_item("DUTY_PHRASE", &sort_key)?;
+            }
+            for pharse in pharses {
+                transaction = transaction.put_item(pharse)?;
+            }
+            transaction.send().await?;
         }
 
-        command.send().await
+        Ok(())
     }
 
     /// List all categories or list categories by parent
@@ -251,7 +280,7 @@ impl DutyRepository for DutyRepoImpl {
     /// List all categories and duties
     /// Return the parent category, all sub categories and all duties
     async fn list_categories_and_duties(&self, parent: &str) -> Result<CategoriesAndDuties, Error> {
-        let query_parent = self.get_category(&parent);
+        let query_parent = self.get_category(parent);
         let query_categories = self.list_categories(Some(parent));
         let query_duties = self.list_duties(parent);
 
@@ -267,6 +296,26 @@ impl DutyRepository for DutyRepoImpl {
         })
     }
 
+    async fn delete_duty(&self, duty_id: &str) -> Result<(), Error> {
+        let sort_key = format!("DUTY#{}", duty_id);
+        let phrases_sk = format!("{}#", sort_key);
+        let get_duty = self.db.get_item::<DutyModel>("DUTY", &sort_key);
+        let query_phrases =
+            self.db
+                .query_items::<DutyPhraseModel>(None, "DUTY_PHRASE", &phrases_sk);
+        let (_, phrases) = futures::try_join!(get_duty, query_phrases)?;
+
+        let mut transaction = self
+            .db
+            .transact_write_items()
+            .delete_item("DUTY", &sort_key)?;
+        for phrase in phrases {
+            transaction = transaction.delete_item("DUTY_PHRASE", &phrase.sort_key())?;
+        }
+
+        transaction.send().await
+    }
+
     /// Get a duty will pharse
     async fn get_duty(&self, duty_id: &str) -> Result<DutyDetail, Error> {
         let get_duty_sk = format!("DUTY#{}", duty_id);
@@ -294,7 +343,7 @@ mod tests {
     use minibell::duty::{Duty, DutyPhrase};
 
     use crate::dynamodb::{
-        duty::{DutyModel, DutyPhraseModel},
+        duty::{stale_phrases, DutyModel, DutyPhraseModel},
         PrimaryModel,
     };
 
@@ -321,4 +370,21 @@ mod tests {
             DutyPhraseModel::from((&duty, &pharse)).to_item().unwrap()
         );
     }
+
+    #[test]
+    fn removed_phrases_are_stale() {
+        let phrase = |progression| DutyPhraseModel {
+            duty_id: "uwu".to_string(),
+            name: "Phase".to_string(),
+            progression,
+        };
+
+        let existing = vec![phrase(1.0), phrase(2.0), phrase(3.0)];
+        let pharses = vec![phrase(1.0), phrase(2.5)];
+        assert_eq!(
+            stale_phrases(&existing, &pharses),
+            vec![phrase(2.0).sort_key(), phrase(3.0).sort_key()]
+        );
+        assert!(stale_phrases(&[], &pharses).is_empty());
+    }
 }
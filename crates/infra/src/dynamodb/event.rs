@@ -0,0 +1,330 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{DateTime, Duration, Utc};
+use minibell::{
+    event::{
+        Event, EventInfo, EventRepository, EventSchedule, EventSlot, EventStatus, SignUp,
+        SignUpStatus,
+    },
+    outbox::OutboxMessage,
+    world::Location,
+    Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, TimestampMilliSeconds};
+use shaku::Component;
+
+use super::{outbox::OutboxModel, DynamoClient, PrimaryModel};
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum EventStatusModel {
+    Draft,
+    Private,
+    Public,
+    InProcess,
+    Finished,
+    Cancelled,
+}
+
+impl EventStatusModel {
+    fn key(&self) -> &'static str {
+        match self {
+            EventStatusModel::Draft => "DRAFT",
+            EventStatusModel::Private => "PRIVATE",
+            EventStatusModel::Public => "PUBLIC",
+            EventStatusModel::InProcess => "IN_PROCESS",
+            EventStatusModel::Finished => "FINISHED",
+            EventStatusModel::Cancelled => "CANCELLED",
+        }
+    }
+}
+
+impl From<EventStatus> for EventStatusModel {
+    fn from(value: EventStatus) -> Self {
+        match value {
+            EventStatus::Draft => EventStatusModel::Draft,
+            EventStatus::Private => EventStatusModel::Private,
+            EventStatus::Public => EventStatusModel::Public,
+            EventStatus::InProcess => EventStatusModel::InProcess,
+            EventStatus::Finished => EventStatusModel::Finished,
+            EventStatus::Cancelled => EventStatusModel::Cancelled,
+        }
+    }
+}
+
+impl From<EventStatusModel> for EventStatus {
+    fn from(value: EventStatusModel) -> Self {
+        match value {
+            EventStatusModel::Draft => EventStatus::Draft,
+            EventStatusModel::Private => EventStatus::Private,
+            EventStatusModel::Public => EventStatus::Public,
+            EventStatusModel::InProcess => EventStatus::InProcess,
+            EventStatusModel::Finished => EventStatus::Finished,
+            EventStatusModel::Cancelled => EventStatus::Cancelled,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+enum LocationModel {
+    DataCenter(String),
+    Region(String),
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+struct SignUpModel {
+    member_id: u64,
+    job: String,
+    world_id: String,
+    /// Slot of an accepted member, none when waitlisted
+    slot: Option<usize>,
+    #[serde_as(as = "TimestampMilliSeconds")]
+    signed_up_at: DateTime<Utc>,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+struct EventModel {
+    id: String,
+    host: Option<u64>,
+
+    status: EventStatusModel,
+    title: String,
+    description: Option<String>,
+    duty_id: Option<String>,
+    #[serde_as(as = "TimestampMilliSeconds")]
+    start_at: DateTime<Utc>,
+    duration_minutes: i64,
+    /// Accepted job ids of each slot
+    slots: Vec<Vec<String>>,
+
+    location: LocationModel,
+    language: String,
+    travel_allowed: bool,
+    #[serde(default)]
+    thread_enabled: bool,
+
+    sign_ups: Vec<SignUpModel>,
+
+    #[serde_as(as = "TimestampMilliSeconds")]
+    created_at: DateTime<Utc>,
+    #[serde_as(as = "TimestampMilliSeconds")]
+    updated_at: DateTime<Utc>,
+    #[serde_as(as = "Option<TimestampMilliSeconds>")]
+    published_at: Option<DateTime<Utc>>,
+    announcement_id: Option<String>,
+    scheduled_event_id: Option<String>,
+    thread_id: Option<String>,
+    version: u64,
+}
+
+impl PrimaryModel for EventModel {
+    fn data_type(&self) -> String {
+        "Event".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "EVENT".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        format!("EVENT#{}", self.id)
+    }
+
+    /// Query list of events by status and start
+    fn gsi1(&self) -> Option<(String, String)> {
+        Some((
+            format!("EVENT#{}", self.status.key()),
+            start_sort_key(self.start_at, &self.id),
+        ))
+    }
+}
+
+fn start_sort_key(start_at: DateTime<Utc>, id: &str) -> String {
+    format!("EVENT#{:0>13}#{}", start_at.timestamp_millis().max(0), id)
+}
+
+impl From<&Event> for EventModel {
+    fn from(value: &Event) -> Self {
+        Self {
+            id: value.id.clone(),
+            host: value.host,
+
+            status: value.status.into(),
+            title: value.info.title.clone(),
+            description: value.info.description.clone(),
+            duty_id: value.duty_id.clone(),
+            start_at: value.schedule.start_at,
+            duration_minutes: value.schedule.duration.num_minutes(),
+            slots: value.slots.iter().map(|slot| slot.jobs.clone()).collect(),
+
+            location: match &value.location {
+                Location::DataCenter(id) => LocationModel::DataCenter(id.clone()),
+                Location::Region(id) => LocationModel::Region(id.clone()),
+            },
+            language: value.language.clone(),
+            travel_allowed: value.travel_allowed,
+            thread_enabled: value.thread_enabled,
+
+            sign_ups: value
+                .sign_ups
+                .iter()
+                .map(|sign_up| SignUpModel {
+                    member_id: sign_up.member_id,
+                    job: sign_up.job.clone(),
+                    world_id: sign_up.world_id.clone(),
+                    slot: match sign_up.status {
+                        SignUpStatus::Accepted { slot } => Some(slot),
+                        SignUpStatus::Waitlisted => None,
+                    },
+                    signed_up_at: sign_up.signed_up_at,
+                })
+                .collect(),
+
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            published_at: value.published_at,
+            announcement_id: value.announcement_id.clone(),
+            scheduled_event_id: value.scheduled_event_id.clone(),
+            thread_id: value.thread_id.clone(),
+            version: value.version,
+        }
+    }
+}
+
+impl From<EventModel> for Event {
+    fn from(value: EventModel) -> Self {
+        Event {
+            id: value.id,
+            host: value.host,
+
+            status: value.status.into(),
+            info: EventInfo {
+                title: value.title,
+                description: value.description,
+            },
+            duty_id: value.duty_id,
+            schedule: EventSchedule {
+                start_at: value.start_at,
+                duration: Duration::minutes(value.duration_minutes),
+            },
+            slots: value
+                .slots
+                .into_iter()
+                .map(|jobs| EventSlot { jobs })
+                .collect(),
+
+            location: match value.location {
+                LocationModel::DataCenter(id) => Location::DataCenter(id),
+                LocationModel::Region(id) => Location::Region(id),
+            },
+            language: value.language,
+            travel_allowed: value.travel_allowed,
+            thread_enabled: value.thread_enabled,
+
+            sign_ups: value
+                .sign_ups
+                .into_iter()
+                .map(|sign_up| SignUp {
+                    member_id: sign_up.member_id,
+                    job: sign_up.job,
+                    world_id: sign_up.world_id,
+                    status: match sign_up.slot {
+                        Some(slot) => SignUpStatus::Accepted { slot },
+                        None => SignUpStatus::Waitlisted,
+                    },
+                    signed_up_at: sign_up.signed_up_at,
+                })
+                .collect(),
+
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            published_at: value.published_at,
+            announcement_id: value.announcement_id,
+            scheduled_event_id: value.scheduled_event_id,
+            thread_id: value.thread_id,
+            version: value.version,
+        }
+    }
+}
+
+#[derive(Debug, Component)]
+#[shaku(interface = EventRepository)]
+pub struct EventRepoImpl {
+    db: Arc<DynamoClient>,
+}
+
+#[async_trait]
+impl EventRepository for EventRepoImpl {
+    async fn insert_event(&self, event: &Event, outbox: &[OutboxMessage]) -> Result<(), Error> {
+        outbox
+            .iter()
+            .try_fold(
+                self.db.transact_write_items().put_item_when(
+                    EventModel::from(event),
+                    "attribute_not_exists(PK)",
+                    &[],
+                )?,
+                |write, message| write.put_item(OutboxModel::from(message)),
+            )?
+            .send()
+            .await
+    }
+
+    async fn get_event(&self, event_id: &str) -> Result<Event, Error> {
+        self.db
+            .get_item::<EventModel>("EVENT", &format!("EVENT#{}", event_id))
+            .await
+            .map(Into::into)
+    }
+
+    async fn update_event(&self, event: &mut Event, outbox: &[OutboxMessage]) -> Result<(), Error> {
+        let mut model = EventModel::from(&*event);
+        model.version += 1;
+
+        outbox
+            .iter()
+            .try_fold(
+                self.db.transact_write_items().put_item_when(
+                    model,
+                    "version = :version",
+                    &[(":version", AttributeValue::N(event.version.to_string()))],
+                )?,
+                |write, message| write.put_item(OutboxModel::from(message)),
+            )?
+            .send()
+            .await?;
+
+        event.version += 1;
+        Ok(())
+    }
+
+    async fn list_events(
+        &self,
+        status: EventStatus,
+        starting_before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Event>, Error> {
+        let pk = format!("EVENT#{}", EventStatusModel::from(status).key());
+        let items = match starting_before {
+            Some(starting_before) => {
+                // Ids sort after the separator, so every event starting at the time is included
+                let to = format!("EVENT#{:0>13}$", starting_before.timestamp_millis().max(0));
+                self.db
+                    .query_items_between::<EventModel>(Some("GSI1"), &pk, ("EVENT#", &to), None)
+                    .await?
+            }
+            None => {
+                self.db
+                    .query_items::<EventModel>(Some("GSI1"), &pk, "EVENT#")
+                    .await?
+            }
+        };
+
+        Ok(items.into_iter().map(Into::into).collect())
+    }
+}
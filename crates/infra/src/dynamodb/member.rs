@@ -4,13 +4,14 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use minibell::{
     member::{self, MemberId},
+    outbox::OutboxMessage,
     Error,
 };
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, TimestampMilliSeconds};
+use serde_with::{serde_as, TimestampMilliSeconds, TimestampSeconds};
 use shaku::Component;
 
-use super::{DynamoClient, PrimaryModel};
+use super::{outbox::OutboxModel, DynamoClient, PrimaryModel};
 
 #[derive(Debug, Component)]
 #[shaku(interface = member::MemberRepository)]
@@ -24,11 +25,19 @@ struct MemberModel {
     id: u64,
     name: String,
     avatar: String,
+    #[serde(default)]
+    roles: Vec<u64>,
 
     #[serde_as(as = "TimestampMilliSeconds")]
     updated_at: DateTime<Utc>,
     #[serde_as(as = "TimestampMilliSeconds")]
     joined_at: DateTime<Utc>,
+    #[serde_as(as = "Option<TimestampMilliSeconds>")]
+    #[serde(default)]
+    left_at: Option<DateTime<Utc>>,
+    #[serde_as(as = "Option<TimestampMilliSeconds>")]
+    #[serde(default)]
+    suspended_at: Option<DateTime<Utc>>,
 }
 
 impl From<&member::Member> for MemberModel {
@@ -37,22 +46,28 @@ impl From<&member::Member> for MemberModel {
             id: member.id,
             name: member.display_name.clone(),
             avatar: member.avatar.clone(),
+            roles: member.roles.clone(),
 
             updated_at: member.updated_at,
             joined_at: member.joined_at,
+            left_at: member.left_at,
+            suspended_at: member.suspended_at,
         }
     }
 }
 
-impl Into<member::Member> for MemberModel {
-    fn into(self) -> member::Member {
+impl From<MemberModel> for member::Member {
+    fn from(value: MemberModel) -> Self {
         member::Member {
-            id: self.id,
-            display_name: self.name,
-            avatar: self.avatar,
+            id: value.id,
+            display_name: value.name,
+            avatar: value.avatar,
+            roles: value.roles,
 
-            updated_at: self.updated_at,
-            joined_at: self.joined_at,
+            updated_at: value.updated_at,
+            joined_at: value.joined_at,
+            left_at: value.left_at,
+            suspended_at: value.suspended_at,
         }
     }
 }
@@ -88,6 +103,9 @@ struct MemberSessionModel {
     issued_at: DateTime<Utc>,
     #[serde_as(as = "TimestampMilliSeconds")]
     expires_at: DateTime<Utc>,
+    /// Expiry in epoch seconds, for the DynamoDB TTL
+    #[serde_as(as = "TimestampSeconds")]
+    ttl: DateTime<Utc>,
 }
 
 impl PrimaryModel for MemberSessionModel {
@@ -126,18 +144,19 @@ impl From<&member::MemberSession> for MemberSessionModel {
 
             issued_at: session.issued_at,
             expires_at: session.expires_at,
+            ttl: session.expires_at,
         }
     }
 }
 
-impl Into<member::MemberSession> for MemberSessionModel {
-    fn into(self) -> member::MemberSession {
+impl From<MemberSessionModel> for member::MemberSession {
+    fn from(value: MemberSessionModel) -> Self {
         member::MemberSession {
-            id: self.id,
-            member_id: self.member_id,
+            id: value.id,
+            member_id: value.member_id,
 
-            issued_at: self.issued_at,
-            expires_at: self.expires_at,
+            issued_at: value.issued_at,
+            expires_at: value.expires_at,
         }
     }
 }
@@ -148,11 +167,17 @@ impl member::MemberRepository for MemberRepoImpl {
         &self,
         member: &member::Member,
         session: &member::MemberSession,
+        outbox: &[OutboxMessage],
     ) -> Result<(), Error> {
-        self.db
-            .batch_insert_items()
-            .add_item(MemberModel::from(member))?
-            .add_item(MemberSessionModel::from(session))?
+        outbox
+            .iter()
+            .try_fold(
+                self.db
+                    .transact_write_items()
+                    .put_item(MemberModel::from(member))?
+                    .put_item(MemberSessionModel::from(session))?,
+                |write, message| write.put_item(OutboxModel::from(message)),
+            )?
             .send()
             .await
     }
@@ -164,6 +189,28 @@ impl member::MemberRepository for MemberRepoImpl {
             .map(Into::into)
     }
 
+    async fn update_member(&self, member: &member::Member) -> Result<(), Error> {
+        self.db.insert_item(MemberModel::from(member)).await
+    }
+
+    async fn list_stale_members(
+        &self,
+        updated_before: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<member::Member>, Error> {
+        let to = format!("MEMBER#{}", updated_before.timestamp_millis());
+
+        self.db
+            .query_items_between::<MemberModel>(
+                Some("GSI1"),
+                "MEMBER",
+                ("MEMBER#", &to),
+                Some(limit.min(i32::MAX as usize) as i32),
+            )
+            .await
+            .map(|items| items.into_iter().map(Into::into).collect())
+    }
+
     async fn get_member_session(&self, session_id: &str) -> Result<member::MemberSession, Error> {
         self.db
             .get_item::<MemberSessionModel>(
@@ -173,4 +220,50 @@ impl member::MemberRepository for MemberRepoImpl {
             .await
             .map(Into::into)
     }
+
+    /// Never recreate a session signed out in the meantime
+    async fn update_member_session(&self, session: &member::MemberSession) -> Result<(), Error> {
+        let updated = self
+            .db
+            .insert_item_when(
+                MemberSessionModel::from(session),
+                "attribute_exists(PK)",
+                &[],
+            )
+            .await?;
+
+        if updated {
+            Ok(())
+        } else {
+            Err(Error::ItemNotFound)
+        }
+    }
+
+    async fn list_member_sessions(
+        &self,
+        member_id: MemberId,
+    ) -> Result<Vec<member::MemberSession>, Error> {
+        self.db
+            .query_items::<MemberSessionModel>(
+                Some("GSI1"),
+                &format!("MEMBER#{}", member_id),
+                "MEMBER_SESSION#",
+            )
+            .await
+            .map(|items| items.into_iter().map(Into::into).collect())
+    }
+
+    async fn delete_member_session(&self, session_id: &str) -> Result<(), Error> {
+        self.db
+            .delete_item("MEMBER_SESSION", &format!("MEMBER_SESSION#{}", session_id))
+            .await
+    }
+
+    async fn revoke_member_sessions(&self, member_id: MemberId) -> Result<(), Error> {
+        for session in self.list_member_sessions(member_id).await? {
+            self.delete_member_session(&session.id).await?;
+        }
+
+        Ok(())
+    }
 }
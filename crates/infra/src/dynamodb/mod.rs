@@ -1,14 +1,29 @@
 use std::collections::HashMap;
 
 use aws_config::SdkConfig;
-use aws_sdk_dynamodb::types::{AttributeValue, PutRequest, WriteRequest};
+use aws_sdk_dynamodb::{
+    operation::{put_item::PutItemError, transact_write_items::TransactWriteItemsError},
+    types::{
+        AttributeValue, Delete, Put, PutRequest, ReturnValue, TransactWriteItem, WriteRequest,
+    },
+};
+use chrono::{DateTime, Utc};
 use minibell::Error;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::Parameters;
 
+pub mod api_key;
+pub mod character;
 pub mod duty;
+pub mod event;
 pub mod member;
+pub mod notification;
+pub mod outbox;
+pub mod rate_limit;
+pub mod reconfirmation;
+pub mod reminder;
+pub mod world;
 
 #[derive(Debug)]
 pub struct DynamoClient {
@@ -20,7 +35,7 @@ pub struct DynamoClient {
 impl DynamoClient {
     pub(crate) fn new(sdkconfig: &SdkConfig, parameters: &Parameters) -> Self {
         Self {
-            client: aws_sdk_dynamodb::Client::new(&sdkconfig),
+            client: aws_sdk_dynamodb::Client::new(sdkconfig),
 
             primary_table: parameters.primary_table.to_string(),
         }
@@ -35,27 +50,112 @@ impl DynamoClient {
             .key("SK", AttributeValue::S(sk.to_string()))
             .send()
             .await
-            .map_err(|e| Error::internal(e.to_string()))?
+            .map_err(|e| Error::upstream(e.to_string()))?
             .item
             .ok_or(Error::ItemNotFound)?;
         let member: M =
             serde_dynamo::from_item(item).map_err(|e| Error::internal(e.to_string()))?;
 
-        Ok(member.into())
+        Ok(member)
     }
 
-    // async fn insert_item<M: PrimaryModel>(&self, item: M) -> Result<(), Error> {
-    //     let item = item.to_item()?;
-    //     self.client
-    //         .put_item()
-    //         .table_name(&self.primary_table)
-    //         .set_item(Some(item))
-    //         .send()
-    //         .await
-    //         .map_err(|e| Error::internal(e.to_string()))?;
+    async fn insert_item<M: PrimaryModel>(&self, item: M) -> Result<(), Error> {
+        let item = item.to_item()?;
+        self.client
+            .put_item()
+            .table_name(&self.primary_table)
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| Error::upstream(e.to_string()))?;
 
-    //     Ok(())
-    // }
+        Ok(())
+    }
+
+    /// Insert the item only if it does not exist
+    /// Return false if the item already exists
+    async fn insert_item_if_absent<M: PrimaryModel>(&self, item: M) -> Result<bool, Error> {
+        self.insert_item_when(item, "attribute_not_exists(PK)", &[])
+            .await
+    }
+
+    /// Insert the item only if the condition holds on the existing item
+    /// Return false if the condition failed
+    async fn insert_item_when<M: PrimaryModel>(
+        &self,
+        item: M,
+        condition: &str,
+        values: &[(&str, AttributeValue)],
+    ) -> Result<bool, Error> {
+        let item = item.to_item()?;
+        let mut put_item = self
+            .client
+            .put_item()
+            .table_name(&self.primary_table)
+            .set_item(Some(item))
+            .condition_expression(condition);
+        for (name, value) in values {
+            put_item = put_item.expression_attribute_values(*name, value.clone());
+        }
+
+        match put_item.send().await {
+            Ok(_) => Ok(true),
+            Err(e) => match e.into_service_error() {
+                PutItemError::ConditionalCheckFailedException(_) => Ok(false),
+                e => Err(Error::upstream(e.to_string())),
+            },
+        }
+    }
+
+    /// Atomically add one to the counter attribute, creating the item if missing
+    /// The item expires with the DynamoDB TTL, return the new count
+    async fn increment_item(
+        &self,
+        pk: &str,
+        sk: &str,
+        counter: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<u64, Error> {
+        let output = self
+            .client
+            .update_item()
+            .table_name(&self.primary_table)
+            .key("PK", AttributeValue::S(pk.to_string()))
+            .key("SK", AttributeValue::S(sk.to_string()))
+            .update_expression("ADD #counter :one SET #ttl = if_not_exists(#ttl, :ttl)")
+            .expression_attribute_names("#counter", counter)
+            .expression_attribute_names("#ttl", "ttl")
+            .expression_attribute_values(":one", AttributeValue::N("1".to_string()))
+            .expression_attribute_values(
+                ":ttl",
+                AttributeValue::N(expires_at.timestamp().to_string()),
+            )
+            .return_values(ReturnValue::UpdatedNew)
+            .send()
+            .await
+            .map_err(|e| Error::upstream(e.to_string()))?;
+
+        output
+            .attributes
+            .as_ref()
+            .and_then(|attributes| attributes.get(counter))
+            .and_then(|value| value.as_n().ok())
+            .and_then(|value| value.parse().ok())
+            .ok_or(Error::internal("Counter missing from the update response."))
+    }
+
+    async fn delete_item(&self, pk: &str, sk: &str) -> Result<(), Error> {
+        self.client
+            .delete_item()
+            .table_name(&self.primary_table)
+            .key("PK", AttributeValue::S(pk.to_string()))
+            .key("SK", AttributeValue::S(sk.to_string()))
+            .send()
+            .await
+            .map_err(|e| Error::upstream(e.to_string()))?;
+
+        Ok(())
+    }
 
     async fn query_items<M: PrimaryModel>(
         &self,
@@ -71,8 +171,8 @@ impl DynamoClient {
         let query = if let Some(index) = index {
             query
                 .index_name(index)
-                .expression_attribute_names("#pk", "GSI1PK")
-                .expression_attribute_names("#sk", "GSI1SK")
+                .expression_attribute_names("#pk", format!("{}PK", index))
+                .expression_attribute_names("#sk", format!("{}SK", index))
         } else {
             query
                 .expression_attribute_names("#pk", "PK")
@@ -85,9 +185,46 @@ impl DynamoClient {
             .send()
             .await
             .map(|i| i.items().to_vec())
-            .map_err(|e| Error::internal(e.to_string()))?;
+            .map_err(|e| Error::upstream(e.to_string()))?;
 
-        Ok(serde_dynamo::from_items(items).map_err(|e| Error::internal(e.to_string()))?)
+        serde_dynamo::from_items(items).map_err(|e| Error::internal(e.to_string()))
+    }
+
+    /// Query items with sort key between `from` and `to`, in ascending order
+    async fn query_items_between<M: PrimaryModel>(
+        &self,
+        index: Option<&str>,
+        pk: &str,
+        (from, to): (&str, &str),
+        limit: Option<i32>,
+    ) -> Result<Vec<M>, Error> {
+        let query = self
+            .client
+            .query()
+            .table_name(&self.primary_table)
+            .key_condition_expression("#pk = :pk AND #sk BETWEEN :from AND :to")
+            .set_limit(limit);
+        let query = if let Some(index) = index {
+            query
+                .index_name(index)
+                .expression_attribute_names("#pk", format!("{}PK", index))
+                .expression_attribute_names("#sk", format!("{}SK", index))
+        } else {
+            query
+                .expression_attribute_names("#pk", "PK")
+                .expression_attribute_names("#sk", "SK")
+        };
+
+        let items = query
+            .expression_attribute_values(":pk", AttributeValue::S(pk.to_string()))
+            .expression_attribute_values(":from", AttributeValue::S(from.to_string()))
+            .expression_attribute_values(":to", AttributeValue::S(to.to_string()))
+            .send()
+            .await
+            .map(|i| i.items().to_vec())
+            .map_err(|e| Error::upstream(e.to_string()))?;
+
+        serde_dynamo::from_items(items).map_err(|e| Error::internal(e.to_string()))
     }
 
     fn batch_insert_items(&self) -> BatchItemWrite {
@@ -97,6 +234,93 @@ impl DynamoClient {
             items: Vec::new(),
         }
     }
+
+    fn transact_write_items(&self) -> TransactItemWrite {
+        TransactItemWrite {
+            client: self.client.clone(),
+            table: self.primary_table.clone(),
+            items: Vec::new(),
+        }
+    }
+}
+
+/// Write items in a single transaction, all or nothing
+struct TransactItemWrite {
+    client: aws_sdk_dynamodb::Client,
+    table: String,
+    items: Vec<TransactWriteItem>,
+}
+
+impl TransactItemWrite {
+    fn put_item<M: PrimaryModel>(mut self, item: M) -> Result<Self, Error> {
+        let put = Put::builder()
+            .table_name(&self.table)
+            .set_item(Some(item.to_item()?))
+            .build()
+            .map_err(|e| Error::internal(e.to_string()))?;
+        self.items
+            .push(TransactWriteItem::builder().put(put).build());
+
+        Ok(self)
+    }
+
+    /// Put the item only if the condition holds on the existing item
+    /// The whole transaction fails with a conflict otherwise
+    fn put_item_when<M: PrimaryModel>(
+        mut self,
+        item: M,
+        condition: &str,
+        values: &[(&str, AttributeValue)],
+    ) -> Result<Self, Error> {
+        let mut put = Put::builder()
+            .table_name(&self.table)
+            .set_item(Some(item.to_item()?))
+            .condition_expression(condition);
+        for (name, value) in values {
+            put = put.expression_attribute_values(*name, value.clone());
+        }
+        let put = put.build().map_err(|e| Error::internal(e.to_string()))?;
+        self.items
+            .push(TransactWriteItem::builder().put(put).build());
+
+        Ok(self)
+    }
+
+    fn delete_item(mut self, pk: &str, sk: &str) -> Result<Self, Error> {
+        let delete = Delete::builder()
+            .table_name(&self.table)
+            .key("PK", AttributeValue::S(pk.to_string()))
+            .key("SK", AttributeValue::S(sk.to_string()))
+            .build()
+            .map_err(|e| Error::internal(e.to_string()))?;
+        self.items
+            .push(TransactWriteItem::builder().delete(delete).build());
+
+        Ok(self)
+    }
+
+    async fn send(self) -> Result<(), Error> {
+        let result = self
+            .client
+            .transact_write_items()
+            .set_transact_items(Some(self.items))
+            .send()
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => match e.into_service_error() {
+                TransactWriteItemsError::TransactionCanceledException(e)
+                    if e.cancellation_reasons()
+                        .iter()
+                        .any(|reason| reason.code() == Some("ConditionalCheckFailed")) =>
+                {
+                    Err(Error::Conflict)
+                }
+                e => Err(Error::upstream(e.to_string())),
+            },
+        }
+    }
 }
 
 struct BatchItemWrite {
@@ -157,7 +381,7 @@ impl BatchItemWrite {
                 .set_request_items(Some(input))
                 .send()
                 .await
-                .map_err(|e| Error::internal(e.to_string()))?;
+                .map_err(|e| Error::upstream(e.to_string()))?;
         }
         Ok(())
     }
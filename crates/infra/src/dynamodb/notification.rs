@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveTime;
+use chrono_tz::Tz;
+use minibell::{
+    member::MemberId,
+    notification::{
+        NotificationChannel, NotificationKind, NotificationPreferenceRepository,
+        NotificationPreferences, QuietHours,
+    },
+    Error,
+};
+use serde::{Deserialize, Serialize};
+use shaku::Component;
+
+use super::{DynamoClient, PrimaryModel};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct NotificationPreferenceModel {
+    member_id: u64,
+    channels: Vec<NotificationChannelModel>,
+    quiet_hours: Option<QuietHoursModel>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct NotificationChannelModel {
+    kind: NotificationKind,
+    channel: NotificationChannel,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct QuietHoursModel {
+    timezone: Tz,
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl PrimaryModel for NotificationPreferenceModel {
+    fn data_type(&self) -> String {
+        "NotificationPreference".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "NOTIFICATION_PREFERENCE".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        format!("NOTIFICATION_PREFERENCE#{}", self.member_id)
+    }
+}
+
+impl From<NotificationPreferenceModel> for NotificationPreferences {
+    fn from(value: NotificationPreferenceModel) -> Self {
+        Self {
+            channels: value
+                .channels
+                .into_iter()
+                .map(|channel| (channel.kind, channel.channel))
+                .collect(),
+            quiet_hours: value.quiet_hours.map(|quiet_hours| QuietHours {
+                timezone: quiet_hours.timezone,
+                start: quiet_hours.start,
+                end: quiet_hours.end,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Component)]
+#[shaku(interface = NotificationPreferenceRepository)]
+pub struct NotificationPreferenceRepoImpl {
+    db: Arc<DynamoClient>,
+}
+
+#[async_trait]
+impl NotificationPreferenceRepository for NotificationPreferenceRepoImpl {
+    async fn get_preferences(&self, member_id: MemberId) -> Result<NotificationPreferences, Error> {
+        match self
+            .db
+            .get_item::<NotificationPreferenceModel>(
+                "NOTIFICATION_PREFERENCE",
+                &format!("NOTIFICATION_PREFERENCE#{}", member_id),
+            )
+            .await
+        {
+            Ok(model) => Ok(model.into()),
+            Err(Error::ItemNotFound) => Ok(NotificationPreferences::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn set_preferences(
+        &self,
+        member_id: MemberId,
+        preferences: &NotificationPreferences,
+    ) -> Result<(), Error> {
+        self.db
+            .insert_item(NotificationPreferenceModel {
+                member_id,
+                channels: preferences
+                    .channels
+                    .iter()
+                    .map(|(kind, channel)| NotificationChannelModel {
+                        kind: *kind,
+                        channel: *channel,
+                    })
+                    .collect(),
+                quiet_hours: preferences
+                    .quiet_hours
+                    .as_ref()
+                    .map(|quiet_hours| QuietHoursModel {
+                        timezone: quiet_hours.timezone,
+                        start: quiet_hours.start,
+                        end: quiet_hours.end,
+                    }),
+            })
+            .await
+    }
+}
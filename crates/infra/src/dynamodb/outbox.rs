@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{DateTime, Utc};
+use minibell::{
+    outbox::{DomainEvent, OutboxMessage, OutboxRepository},
+    Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, TimestampMilliSeconds};
+use shaku::Component;
+
+use super::{DynamoClient, PrimaryModel};
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+pub(super) struct OutboxModel {
+    id: String,
+    event: DomainEvent,
+    #[serde_as(as = "TimestampMilliSeconds")]
+    created_at: DateTime<Utc>,
+
+    attempts: u32,
+    #[serde_as(as = "TimestampMilliSeconds")]
+    next_attempt_at: DateTime<Utc>,
+    delivered_sinks: Vec<String>,
+    last_error: Option<String>,
+}
+
+impl PrimaryModel for OutboxModel {
+    fn data_type(&self) -> String {
+        "Outbox".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "OUTBOX".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        format!("OUTBOX#{}", self.id)
+    }
+
+    /// Ordered by next attempt, to list the due messages
+    fn gsi1(&self) -> Option<(String, String)> {
+        Some((
+            "OUTBOX".to_string(),
+            due_sort_key(self.next_attempt_at, &self.id),
+        ))
+    }
+}
+
+fn due_sort_key(next_attempt_at: DateTime<Utc>, id: &str) -> String {
+    format!(
+        "OUTBOX#{:0>13}#{}",
+        next_attempt_at.timestamp_millis().max(0),
+        id
+    )
+}
+
+impl From<&OutboxMessage> for OutboxModel {
+    fn from(value: &OutboxMessage) -> Self {
+        Self {
+            id: value.id.clone(),
+            event: value.event.clone(),
+            created_at: value.created_at,
+
+            attempts: value.attempts,
+            next_attempt_at: value.next_attempt_at,
+            delivered_sinks: value.delivered_sinks.clone(),
+            last_error: value.last_error.clone(),
+        }
+    }
+}
+
+impl From<OutboxModel> for OutboxMessage {
+    fn from(value: OutboxModel) -> Self {
+        Self {
+            id: value.id,
+            event: value.event,
+            created_at: value.created_at,
+
+            attempts: value.attempts,
+            next_attempt_at: value.next_attempt_at,
+            delivered_sinks: value.delivered_sinks,
+            last_error: value.last_error,
+        }
+    }
+}
+
+/// Message that ran out of attempts, kept for inspection
+#[derive(Debug, Deserialize, Serialize)]
+struct OutboxDeadLetterModel {
+    #[serde(flatten)]
+    message: OutboxModel,
+}
+
+impl PrimaryModel for OutboxDeadLetterModel {
+    fn data_type(&self) -> String {
+        "OutboxDeadLetter".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "OUTBOX_DEAD_LETTER".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        format!("OUTBOX_DEAD_LETTER#{}", self.message.id)
+    }
+}
+
+#[derive(Debug, Component)]
+#[shaku(interface = OutboxRepository)]
+pub struct OutboxRepoImpl {
+    db: Arc<DynamoClient>,
+}
+
+#[async_trait]
+impl OutboxRepository for OutboxRepoImpl {
+    async fn list_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<OutboxMessage>, Error> {
+        let from = "OUTBOX#".to_string();
+        // Ids sort after the separator, so every message due at `now` is included
+        let to = format!("OUTBOX#{:0>13}$", now.timestamp_millis().max(0));
+
+        self.db
+            .query_items_between::<OutboxModel>(
+                Some("GSI1"),
+                "OUTBOX",
+                (&from, &to),
+                Some(limit.min(i32::MAX as usize) as i32),
+            )
+            .await
+            .map(|items| items.into_iter().map(Into::into).collect())
+    }
+
+    async fn lease(&self, message: &OutboxMessage, until: DateTime<Utc>) -> Result<bool, Error> {
+        let mut leased = OutboxModel::from(message);
+        leased.next_attempt_at = until;
+
+        self.db
+            .insert_item_when(
+                leased,
+                "next_attempt_at = :next_attempt_at",
+                &[(
+                    ":next_attempt_at",
+                    AttributeValue::N(message.next_attempt_at.timestamp_millis().to_string()),
+                )],
+            )
+            .await
+    }
+
+    async fn update(&self, message: &OutboxMessage) -> Result<(), Error> {
+        self.db.insert_item(OutboxModel::from(message)).await
+    }
+
+    async fn delete(&self, message_id: &str) -> Result<(), Error> {
+        self.db
+            .delete_item("OUTBOX", &format!("OUTBOX#{}", message_id))
+            .await
+    }
+
+    async fn dead_letter(&self, message: &OutboxMessage) -> Result<(), Error> {
+        self.db
+            .transact_write_items()
+            .put_item(OutboxDeadLetterModel {
+                message: OutboxModel::from(message),
+            })?
+            .delete_item("OUTBOX", &format!("OUTBOX#{}", message.id))?
+            .send()
+            .await
+    }
+}
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use minibell::{rate_limit::RateLimitStore, Error};
+use shaku::Component;
+
+use super::DynamoClient;
+
+/// Atomic counters, one item per key and window, removed by the TTL
+/// Each key is its own partition, so busy callers do not share a hot partition
+#[derive(Debug, Component)]
+#[shaku(interface = RateLimitStore)]
+pub struct RateLimitStoreImpl {
+    db: Arc<DynamoClient>,
+}
+
+#[async_trait]
+impl RateLimitStore for RateLimitStoreImpl {
+    async fn hit(&self, key: &str, window_end: DateTime<Utc>) -> Result<u64, Error> {
+        self.db
+            .increment_item(
+                &format!("RATE_LIMIT#{}", key),
+                &format!("WINDOW#{}", window_end.timestamp()),
+                "hits",
+                window_end,
+            )
+            .await
+    }
+}
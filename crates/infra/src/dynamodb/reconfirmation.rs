@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{DateTime, Utc};
+use minibell::{
+    member::MemberId,
+    reconfirmation::{Reconfirmation, ReconfirmationRepository, ReconfirmationStatus},
+    Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, TimestampMilliSeconds};
+use shaku::Component;
+
+use super::{DynamoClient, PrimaryModel};
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ReconfirmationStatusModel {
+    Pending,
+    Confirmed,
+    Dropped,
+    Expired,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+struct ReconfirmationModel {
+    event_id: String,
+    member_id: u64,
+    #[serde_as(as = "TimestampMilliSeconds")]
+    new_start_at: DateTime<Utc>,
+    #[serde_as(as = "TimestampMilliSeconds")]
+    deadline: DateTime<Utc>,
+    status: ReconfirmationStatusModel,
+}
+
+impl PrimaryModel for ReconfirmationModel {
+    fn data_type(&self) -> String {
+        "Reconfirmation".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "RECONFIRMATION".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        reconfirmation_sort_key(&self.event_id, self.member_id)
+    }
+
+    /// Only pending requests are indexed, ordered by deadline
+    fn gsi1(&self) -> Option<(String, String)> {
+        match self.status {
+            ReconfirmationStatusModel::Pending => Some((
+                "RECONFIRMATION_PENDING".to_string(),
+                format!(
+                    "RECONFIRMATION_PENDING#{:0>13}#{}#{}",
+                    self.deadline.timestamp_millis().max(0),
+                    self.event_id,
+                    self.member_id
+                ),
+            )),
+            _ => None,
+        }
+    }
+}
+
+fn reconfirmation_sort_key(event_id: &str, member_id: MemberId) -> String {
+    format!("RECONFIRMATION#{}#{}", event_id, member_id)
+}
+
+impl From<&Reconfirmation> for ReconfirmationModel {
+    fn from(value: &Reconfirmation) -> Self {
+        Self {
+            event_id: value.event_id.clone(),
+            member_id: value.member_id,
+            new_start_at: value.new_start_at,
+            deadline: value.deadline,
+            status: match value.status {
+                ReconfirmationStatus::Pending => ReconfirmationStatusModel::Pending,
+                ReconfirmationStatus::Confirmed => ReconfirmationStatusModel::Confirmed,
+                ReconfirmationStatus::Dropped => ReconfirmationStatusModel::Dropped,
+                ReconfirmationStatus::Expired => ReconfirmationStatusModel::Expired,
+            },
+        }
+    }
+}
+
+impl From<ReconfirmationModel> for Reconfirmation {
+    fn from(value: ReconfirmationModel) -> Self {
+        Self {
+            event_id: value.event_id,
+            member_id: value.member_id,
+            new_start_at: value.new_start_at,
+            deadline: value.deadline,
+            status: match value.status {
+                ReconfirmationStatusModel::Pending => ReconfirmationStatus::Pending,
+                ReconfirmationStatusModel::Confirmed => ReconfirmationStatus::Confirmed,
+                ReconfirmationStatusModel::Dropped => ReconfirmationStatus::Dropped,
+                ReconfirmationStatusModel::Expired => ReconfirmationStatus::Expired,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Component)]
+#[shaku(interface = ReconfirmationRepository)]
+pub struct ReconfirmationRepoImpl {
+    db: Arc<DynamoClient>,
+}
+
+#[async_trait]
+impl ReconfirmationRepository for ReconfirmationRepoImpl {
+    async fn insert(&self, reconfirmations: &[Reconfirmation]) -> Result<(), Error> {
+        reconfirmations
+            .iter()
+            .try_fold(self.db.batch_insert_items(), |batch, reconfirmation| {
+                batch.add_item(ReconfirmationModel::from(reconfirmation))
+            })?
+            .send()
+            .await
+    }
+
+    async fn get(&self, event_id: &str, member_id: MemberId) -> Result<Reconfirmation, Error> {
+        self.db
+            .get_item::<ReconfirmationModel>(
+                "RECONFIRMATION",
+                &reconfirmation_sort_key(event_id, member_id),
+            )
+            .await
+            .map(Into::into)
+    }
+
+    /// Only pending items have the GSI1 key
+    async fn update(&self, reconfirmation: &Reconfirmation) -> Result<(), Error> {
+        let updated = self
+            .db
+            .insert_item_when(
+                ReconfirmationModel::from(reconfirmation),
+                "GSI1PK = :pending",
+                &[(
+                    ":pending",
+                    AttributeValue::S("RECONFIRMATION_PENDING".to_string()),
+                )],
+            )
+            .await?;
+
+        if updated {
+            Ok(())
+        } else {
+            Err(Error::Conflict)
+        }
+    }
+
+    async fn list_expired(&self, now: DateTime<Utc>) -> Result<Vec<Reconfirmation>, Error> {
+        let to = format!(
+            "RECONFIRMATION_PENDING#{:0>13}$",
+            now.timestamp_millis().max(0)
+        );
+
+        self.db
+            .query_items_between::<ReconfirmationModel>(
+                Some("GSI1"),
+                "RECONFIRMATION_PENDING",
+                ("RECONFIRMATION_PENDING#", &to),
+                None,
+            )
+            .await
+            .map(|items| items.into_iter().map(Into::into).collect())
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use minibell::{member::MemberId, reminder::ReminderRepository, Error};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, TimestampMilliSeconds, TimestampSeconds};
+use shaku::Component;
+
+use super::{DynamoClient, PrimaryModel};
+
+/// Opt out applied to every event
+const ALL_EVENTS: &str = "*";
+/// Days claims and per event opt outs are kept after the event, in case it is rescheduled
+const RETENTION_DAYS: i64 = 30;
+
+/// Sent reminder, one per event, member and offset
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+struct ReminderModel {
+    event_id: String,
+    member_id: u64,
+    offset_minutes: i64,
+
+    #[serde_as(as = "TimestampMilliSeconds")]
+    sent_at: DateTime<Utc>,
+    /// Expiry in epoch seconds, for the DynamoDB TTL
+    #[serde_as(as = "TimestampSeconds")]
+    ttl: DateTime<Utc>,
+}
+
+impl PrimaryModel for ReminderModel {
+    fn data_type(&self) -> String {
+        "Reminder".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "REMINDER".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        reminder_sort_key(&self.event_id, self.member_id, self.offset_minutes)
+    }
+}
+
+fn reminder_sort_key(event_id: &str, member_id: MemberId, offset_minutes: i64) -> String {
+    format!("REMINDER#{}#{}#{}", event_id, member_id, offset_minutes)
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
+struct ReminderOptOutModel {
+    member_id: u64,
+    /// Event id, or `*` for all events
+    event_id: String,
+    /// Expiry in epoch seconds, for the DynamoDB TTL, none for all events
+    #[serde_as(as = "Option<TimestampSeconds>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ttl: Option<DateTime<Utc>>,
+}
+
+impl PrimaryModel for ReminderOptOutModel {
+    fn data_type(&self) -> String {
+        "ReminderOptOut".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "REMINDER_OPT_OUT".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        format!("REMINDER_OPT_OUT#{}#{}", self.member_id, self.event_id)
+    }
+}
+
+#[derive(Debug, Component)]
+#[shaku(interface = ReminderRepository)]
+pub struct ReminderRepoImpl {
+    db: Arc<DynamoClient>,
+}
+
+#[async_trait]
+impl ReminderRepository for ReminderRepoImpl {
+    async fn claim(
+        &self,
+        event_id: &str,
+        member_id: MemberId,
+        offset: Duration,
+    ) -> Result<bool, Error> {
+        let now = Utc::now();
+
+        // Claimed at most the offset before start
+        self.db
+            .insert_item_if_absent(ReminderModel {
+                event_id: event_id.to_string(),
+                member_id,
+                offset_minutes: offset.num_minutes(),
+                sent_at: now,
+                ttl: now + offset + Duration::days(RETENTION_DAYS),
+            })
+            .await
+    }
+
+    async fn release(
+        &self,
+        event_id: &str,
+        member_id: MemberId,
+        offset: Duration,
+    ) -> Result<(), Error> {
+        self.db
+            .delete_item(
+                "REMINDER",
+                &reminder_sort_key(event_id, member_id, offset.num_minutes()),
+            )
+            .await
+    }
+
+    async fn set_opt_out(
+        &self,
+        member_id: MemberId,
+        event: Option<(&str, DateTime<Utc>)>,
+        opt_out: bool,
+    ) -> Result<(), Error> {
+        let model = ReminderOptOutModel {
+            member_id,
+            event_id: event
+                .map(|(event_id, _)| event_id)
+                .unwrap_or(ALL_EVENTS)
+                .to_string(),
+            ttl: event.map(|(_, end_at)| end_at + Duration::days(RETENTION_DAYS)),
+        };
+
+        if opt_out {
+            self.db.insert_item(model).await
+        } else {
+            self.db
+                .delete_item(&model.primary_key(), &model.sort_key())
+                .await
+        }
+    }
+
+    async fn is_opted_out(&self, member_id: MemberId, event_id: &str) -> Result<bool, Error> {
+        let opt_outs = self
+            .db
+            .query_items::<ReminderOptOutModel>(
+                None,
+                "REMINDER_OPT_OUT",
+                &format!("REMINDER_OPT_OUT#{}#", member_id),
+            )
+            .await?;
+
+        Ok(opt_outs
+            .iter()
+            .any(|opt_out| opt_out.event_id == ALL_EVENTS || opt_out.event_id == event_id))
+    }
+}
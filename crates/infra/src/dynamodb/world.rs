@@ -0,0 +1,221 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use minibell::{
+    world::{DataCenter, Region, World, WorldCatalog, WorldDetail, WorldRepository},
+    Error,
+};
+use serde::{Deserialize, Serialize};
+use shaku::Component;
+
+use super::{DynamoClient, PrimaryModel};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct RegionModel {
+    id: String,
+    name: String,
+    sort: i32,
+}
+
+impl From<&Region> for RegionModel {
+    fn from(value: &Region) -> Self {
+        Self {
+            id: value.id.clone(),
+            name: value.name.clone(),
+            sort: value.sort,
+        }
+    }
+}
+
+impl From<RegionModel> for Region {
+    fn from(value: RegionModel) -> Self {
+        Region {
+            id: value.id,
+            name: value.name,
+            sort: value.sort,
+        }
+    }
+}
+
+impl PrimaryModel for RegionModel {
+    fn data_type(&self) -> String {
+        "Region".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "REGION".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        format!("REGION#{}", self.id)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct DataCenterModel {
+    id: String,
+    region: String,
+    name: String,
+    sort: i32,
+}
+
+impl From<&DataCenter> for DataCenterModel {
+    fn from(value: &DataCenter) -> Self {
+        Self {
+            id: value.id.clone(),
+            region: value.region.clone(),
+            name: value.name.clone(),
+            sort: value.sort,
+        }
+    }
+}
+
+impl From<DataCenterModel> for DataCenter {
+    fn from(value: DataCenterModel) -> Self {
+        DataCenter {
+            id: value.id,
+            region: value.region,
+            name: value.name,
+            sort: value.sort,
+        }
+    }
+}
+
+impl PrimaryModel for DataCenterModel {
+    fn data_type(&self) -> String {
+        "DataCenter".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "DATA_CENTER".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        format!("DATA_CENTER#{}", self.id)
+    }
+
+    /// Query list of data centers by region and sort
+    fn gsi1(&self) -> Option<(String, String)> {
+        Some((
+            format!("DATA_CENTER#{}", self.region),
+            format!("DATA_CENTER#{:0>8}", self.sort),
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct WorldModel {
+    id: String,
+    data_center: String,
+    name: String,
+    sort: i32,
+}
+
+impl From<&World> for WorldModel {
+    fn from(value: &World) -> Self {
+        Self {
+            id: value.id.clone(),
+            data_center: value.data_center.clone(),
+            name: value.name.clone(),
+            sort: value.sort,
+        }
+    }
+}
+
+impl From<WorldModel> for World {
+    fn from(value: WorldModel) -> Self {
+        World {
+            id: value.id,
+            data_center: value.data_center,
+            name: value.name,
+            sort: value.sort,
+        }
+    }
+}
+
+impl PrimaryModel for WorldModel {
+    fn data_type(&self) -> String {
+        "World".to_string()
+    }
+
+    fn primary_key(&self) -> String {
+        "WORLD".to_string()
+    }
+
+    fn sort_key(&self) -> String {
+        format!("WORLD#{}", self.id)
+    }
+
+    /// Query list of worlds by data center and sort
+    fn gsi1(&self) -> Option<(String, String)> {
+        Some((
+            format!("WORLD#{}", self.data_center),
+            format!("WORLD#{:0>8}", self.sort),
+        ))
+    }
+}
+
+#[derive(Debug, Component)]
+#[shaku(interface = WorldRepository)]
+pub struct WorldRepoImpl {
+    db: Arc<DynamoClient>,
+}
+
+#[async_trait]
+impl WorldRepository for WorldRepoImpl {
+    async fn insert_catalog(
+        &self,
+        regions: &[Region],
+        data_centers: &[DataCenter],
+        worlds: &[World],
+    ) -> Result<(), Error> {
+        self.db
+            .batch_insert_items()
+            .add_items::<RegionModel>(&regions.iter().map(From::from).collect::<Vec<_>>())?
+            .add_items::<DataCenterModel>(&data_centers.iter().map(From::from).collect::<Vec<_>>())?
+            .add_items::<WorldModel>(&worlds.iter().map(From::from).collect::<Vec<_>>())?
+            .send()
+            .await
+    }
+
+    async fn get_catalog(&self) -> Result<WorldCatalog, Error> {
+        let query_regions = self
+            .db
+            .query_items::<RegionModel>(None, "REGION", "REGION#");
+        let query_data_centers =
+            self.db
+                .query_items::<DataCenterModel>(None, "DATA_CENTER", "DATA_CENTER#");
+        let query_worlds = self.db.query_items::<WorldModel>(None, "WORLD", "WORLD#");
+
+        let (mut regions, mut data_centers, mut worlds) =
+            futures::try_join!(query_regions, query_data_centers, query_worlds)?;
+        regions.sort_by_key(|r| r.sort);
+        data_centers.sort_by_key(|d| d.sort);
+        worlds.sort_by_key(|w| w.sort);
+
+        Ok(WorldCatalog {
+            regions: regions.into_iter().map(Into::into).collect(),
+            data_centers: data_centers.into_iter().map(Into::into).collect(),
+            worlds: worlds.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    async fn get_world(&self, world_id: &str) -> Result<WorldDetail, Error> {
+        let world = self
+            .db
+            .get_item::<WorldModel>("WORLD", &format!("WORLD#{}", world_id))
+            .await?;
+        let data_center = self
+            .db
+            .get_item::<DataCenterModel>(
+                "DATA_CENTER",
+                &format!("DATA_CENTER#{}", world.data_center),
+            )
+            .await?;
+
+        Ok(WorldDetail {
+            world: world.into(),
+            data_center: data_center.into(),
+        })
+    }
+}
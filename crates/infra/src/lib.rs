@@ -1,24 +1,43 @@
 use std::sync::Arc;
 
 use aws_config::{BehaviorVersion, SdkConfig};
+use minibell::permission::RolePermissions;
 use serde::Deserialize;
 use serde_with::{serde_as, DisplayFromStr};
 use shaku::module;
 
+#[cfg(feature = "dev-identity")]
+mod dev_identity;
 mod discord;
 mod dynamodb;
+mod permission;
+mod rate_limit;
 mod session_hmac;
+mod webhook;
+
+const DISCORD_API_URL: &str = "https://discord.com/api/v10";
 
 #[derive(Debug, Clone)]
 pub(crate) struct Parameters {
+    discord_api_url: String,
     discord_client_id: String,
     discord_client_secret: String,
     discord_guild_id: u64,
     discord_token: String,
+    /// Interactions are rejected without it
+    discord_public_key: Option<String>,
+    discord_announcement_channel_id: Option<u64>,
+
+    session_keys: Vec<session_hmac::SessionKey>,
+
+    outbox_webhook_url: Option<String>,
 
-    session_secret: String,
+    role_permissions: RolePermissions,
+    required_role_ids: Vec<u64>,
+    admin_user_ids: Vec<u64>,
 
     primary_table: String,
+    rate_limit_store: rate_limit::RateLimitStoreKind,
 }
 
 #[derive(Debug, Clone)]
@@ -35,19 +54,47 @@ module! {
     pub InfraModule {
         components = [
             discord::DiscordClientImpl,
+            discord::identity::DiscordIdentityProviderImpl,
+            discord::announcer::DiscordAnnouncerImpl,
+            discord::messenger::DiscordMessengerImpl,
+            discord::scheduled_event::DiscordScheduledEventImpl,
+            discord::sink::DiscordSinkImpl,
+            discord::sink::NotificationSinkImpl,
+            discord::thread::DiscordThreadImpl,
+            permission::RolePermissionServiceImpl,
             session_hmac::SessionHmac,
+            session_hmac::OAuth2StateHmac,
+            webhook::WebhookSinkImpl,
 
+            dynamodb::api_key::ApiKeyRepoImpl,
+            dynamodb::character::CharacterRepoImpl,
+            dynamodb::event::EventRepoImpl,
             dynamodb::member::MemberRepoImpl,
             dynamodb::duty::DutyRepoImpl,
+            dynamodb::notification::NotificationPreferenceRepoImpl,
+            dynamodb::outbox::OutboxRepoImpl,
+            dynamodb::rate_limit::RateLimitStoreImpl,
+            dynamodb::reconfirmation::ReconfirmationRepoImpl,
+            dynamodb::reminder::ReminderRepoImpl,
+            dynamodb::world::WorldRepoImpl,
         ],
         providers = [],
     }
 }
 
+/// Key set from a single secret, it keeps verifying the tokens issued before key ids
+fn default_session_keys(secret: String) -> Vec<session_hmac::SessionKey> {
+    vec![session_hmac::SessionKey {
+        id: "default".to_string(),
+        secret,
+    }]
+}
+
 fn get_env_dotenv() -> Parameters {
     #[cfg(debug_assertions)]
     dotenv::dotenv().ok();
 
+    let discord_api_url = std::env::var("DISCORD_API_URL").unwrap_or(DISCORD_API_URL.to_string());
     let discord_client_id =
         std::env::var("DISCORD_CLIENT_ID").expect("DISCORD_CLIENT_ID must be set");
     let discord_client_secret =
@@ -57,34 +104,121 @@ fn get_env_dotenv() -> Parameters {
         .parse::<u64>()
         .expect("DISCORD_GUILD_ID must be a number");
     let discord_token = std::env::var("DISCORD_TOKEN").expect("DISCORD_TOKEN must be set");
+    let discord_public_key = std::env::var("DISCORD_PUBLIC_KEY").ok();
+    let discord_announcement_channel_id = std::env::var("DISCORD_ANNOUNCEMENT_CHANNEL_ID")
+        .ok()
+        .map(|id| {
+            id.parse::<u64>()
+                .expect("DISCORD_ANNOUNCEMENT_CHANNEL_ID must be a number")
+        });
 
-    let session_secret = std::env::var("SESSION_SECRET").expect("SESSION_SECRET must be set");
+    let session_keys = match std::env::var("SESSION_KEYS") {
+        Ok(keys) => keys
+            .split(',')
+            .map(|key| {
+                let (id, secret) = key
+                    .split_once(':')
+                    .expect("SESSION_KEYS must be comma separated <id>:<secret>");
+                session_hmac::SessionKey {
+                    id: id.trim().to_string(),
+                    secret: secret.trim().to_string(),
+                }
+            })
+            .collect(),
+        Err(_) => default_session_keys(
+            std::env::var("SESSION_SECRET").expect("SESSION_KEYS or SESSION_SECRET must be set"),
+        ),
+    };
+
+    let outbox_webhook_url = std::env::var("OUTBOX_WEBHOOK_URL").ok();
+
+    let role_permissions = std::env::var("ROLE_PERMISSIONS")
+        .map(|permissions| {
+            serde_json::from_str::<RolePermissions>(&permissions)
+                .expect("ROLE_PERMISSIONS must be a JSON object of role id to permissions")
+        })
+        .unwrap_or_default();
+    let required_role_ids = env_ids("REQUIRED_ROLE_IDS");
+    let admin_user_ids = env_ids("ADMIN_USER_IDS");
 
     let primary_table = std::env::var("PRIMARY_TABLE").expect("PRIMARY_TABLE must be set");
+    // Local runs are a single process
+    let rate_limit_store =
+        rate_limit::RateLimitStoreKind::from_env(rate_limit::RateLimitStoreKind::Memory);
 
     Parameters {
+        discord_api_url,
         discord_client_id,
         discord_client_secret,
         discord_guild_id,
         discord_token,
+        discord_public_key,
+        discord_announcement_channel_id,
+
+        session_keys,
+
+        outbox_webhook_url,
 
-        session_secret,
+        role_permissions,
+        required_role_ids,
+        admin_user_ids,
 
         primary_table,
+        rate_limit_store,
     }
 }
 
+/// Parse comma separated ids, blank entries are skipped so an empty list is allowed
+fn parse_ids(ids: &str) -> Result<Vec<u64>, std::num::ParseIntError> {
+    ids.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::parse::<u64>)
+        .collect()
+}
+
+/// Ids of the environment variable, none if it is not set
+fn env_ids(name: &str) -> Vec<u64> {
+    std::env::var(name)
+        .map(|ids| {
+            parse_ids(&ids).unwrap_or_else(|_| panic!("{} must be comma separated numbers", name))
+        })
+        .unwrap_or_default()
+}
+
 async fn get_secret_manager(config: &SdkConfig, key: &str) -> Parameters {
     #[serde_as]
     #[derive(Debug, Deserialize)]
     struct Secret {
+        discord_api_url: Option<String>,
         discord_client_id: String,
         discord_client_secret: String,
         #[serde_as(as = "DisplayFromStr")]
         discord_guild_id: u64,
         discord_token: String,
+        #[serde(default)]
+        discord_public_key: Option<String>,
+        #[serde_as(as = "Option<DisplayFromStr>")]
+        #[serde(default)]
+        discord_announcement_channel_id: Option<u64>,
+
+        /// The first key signs, the others only verify
+        #[serde(default)]
+        session_keys: Vec<session_hmac::SessionKey>,
+        /// Single key, before key ids
+        session_secret: Option<String>,
 
-        session_secret: String,
+        #[serde(default)]
+        outbox_webhook_url: Option<String>,
+
+        #[serde(default)]
+        role_permissions: RolePermissions,
+        #[serde_as(as = "Vec<DisplayFromStr>")]
+        #[serde(default)]
+        required_role_ids: Vec<u64>,
+        #[serde_as(as = "Vec<DisplayFromStr>")]
+        #[serde(default)]
+        admin_user_ids: Vec<u64>,
     }
 
     let asm = aws_sdk_secretsmanager::Client::new(config);
@@ -98,16 +232,38 @@ async fn get_secret_manager(config: &SdkConfig, key: &str) -> Parameters {
     let secret = serde_json::from_str::<Secret>(&secret).expect("Failed to parse secret");
 
     let primary_table = std::env::var("PRIMARY_TABLE").expect("PRIMARY_TABLE must be set");
+    let rate_limit_store =
+        rate_limit::RateLimitStoreKind::from_env(rate_limit::RateLimitStoreKind::DynamoDb);
 
     Parameters {
+        discord_api_url: secret
+            .discord_api_url
+            .unwrap_or(DISCORD_API_URL.to_string()),
         discord_client_id: secret.discord_client_id,
         discord_client_secret: secret.discord_client_secret,
         discord_guild_id: secret.discord_guild_id,
         discord_token: secret.discord_token,
+        discord_public_key: secret.discord_public_key,
+        discord_announcement_channel_id: secret.discord_announcement_channel_id,
+
+        session_keys: if secret.session_keys.is_empty() {
+            default_session_keys(
+                secret
+                    .session_secret
+                    .expect("session_keys or session_secret must be set"),
+            )
+        } else {
+            secret.session_keys
+        },
 
-        session_secret: secret.session_secret,
+        outbox_webhook_url: secret.outbox_webhook_url,
+
+        role_permissions: secret.role_permissions,
+        required_role_ids: secret.required_role_ids,
+        admin_user_ids: secret.admin_user_ids,
 
         primary_table,
+        rate_limit_store,
     }
 }
 
@@ -118,24 +274,109 @@ pub async fn bootstrap(config: BootstrapConfig) -> Result<InfraModule, InfraErro
         Some(key) => get_secret_manager(&sdkconfig, &key).await,
         None => get_env_dotenv(),
     };
+    if parameters
+        .session_keys
+        .iter()
+        .any(|key| key.id.is_empty() || key.id.contains('.'))
+    {
+        return Err(InfraError::DependencyError(
+            "Session key ids must be non empty and without dots".to_string(),
+        ));
+    }
 
     let reqwest = Arc::new(reqwest::Client::new());
+    let discord_http = Arc::new(discord::http::DiscordHttp::new(reqwest.as_ref().clone()));
     let dynamodb = Arc::new(dynamodb::DynamoClient::new(&sdkconfig, &parameters));
 
-    let infra = InfraModule::builder()
+    let builder = InfraModule::builder()
         .with_component_parameters::<discord::DiscordClientImpl>(
             discord::DiscordClientImplParameters {
-                reqwest,
+                http: discord_http.clone(),
+                api_url: parameters.discord_api_url.clone(),
+
+                guild_id: parameters.discord_guild_id,
+                token: parameters.discord_token.clone(),
+                public_key: parameters.discord_public_key,
+            },
+        )
+        .with_component_parameters::<discord::identity::DiscordIdentityProviderImpl>(
+            discord::identity::DiscordIdentityProviderImplParameters {
+                http: discord_http.clone(),
+                api_url: parameters.discord_api_url.clone(),
 
                 client_id: parameters.discord_client_id,
                 client_secret: parameters.discord_client_secret,
+            },
+        )
+        .with_component_parameters::<discord::announcer::DiscordAnnouncerImpl>(
+            discord::announcer::DiscordAnnouncerImplParameters {
+                http: discord_http.clone(),
+                api_url: parameters.discord_api_url.clone(),
+
+                token: parameters.discord_token.clone(),
+                channel_id: parameters.discord_announcement_channel_id,
+            },
+        )
+        .with_component_parameters::<discord::messenger::DiscordMessengerImpl>(
+            discord::messenger::DiscordMessengerImplParameters {
+                http: discord_http.clone(),
+                api_url: parameters.discord_api_url.clone(),
+
+                token: parameters.discord_token.clone(),
+            },
+        )
+        .with_component_parameters::<discord::thread::DiscordThreadImpl>(
+            discord::thread::DiscordThreadImplParameters {
+                http: discord_http.clone(),
+                api_url: parameters.discord_api_url.clone(),
+
+                token: parameters.discord_token.clone(),
+                channel_id: parameters.discord_announcement_channel_id,
+            },
+        )
+        .with_component_parameters::<discord::scheduled_event::DiscordScheduledEventImpl>(
+            discord::scheduled_event::DiscordScheduledEventImplParameters {
+                http: discord_http.clone(),
+                api_url: parameters.discord_api_url,
+
                 guild_id: parameters.discord_guild_id,
                 token: parameters.discord_token,
             },
         )
+        .with_component_parameters::<permission::RolePermissionServiceImpl>(
+            permission::RolePermissionServiceImplParameters {
+                role_permissions: parameters.role_permissions,
+                required_roles: parameters.required_role_ids,
+                admin_user_ids: parameters.admin_user_ids,
+            },
+        )
+        .with_component_parameters::<session_hmac::OAuth2StateHmac>(
+            session_hmac::OAuth2StateHmacParameters {
+                keys: parameters.session_keys.clone(),
+            },
+        )
         .with_component_parameters::<session_hmac::SessionHmac>(
             session_hmac::SessionHmacParameters {
-                secret: parameters.session_secret,
+                keys: parameters.session_keys,
+            },
+        )
+        .with_component_parameters::<webhook::WebhookSinkImpl>(webhook::WebhookSinkImplParameters {
+            reqwest: reqwest.clone(),
+            url: parameters.outbox_webhook_url,
+        })
+        .with_component_parameters::<dynamodb::api_key::ApiKeyRepoImpl>(
+            dynamodb::api_key::ApiKeyRepoImplParameters {
+                db: dynamodb.clone(),
+            },
+        )
+        .with_component_parameters::<dynamodb::character::CharacterRepoImpl>(
+            dynamodb::character::CharacterRepoImplParameters {
+                db: dynamodb.clone(),
+            },
+        )
+        .with_component_parameters::<dynamodb::event::EventRepoImpl>(
+            dynamodb::event::EventRepoImplParameters {
+                db: dynamodb.clone(),
             },
         )
         .with_component_parameters::<dynamodb::member::MemberRepoImpl>(
@@ -148,7 +389,67 @@ pub async fn bootstrap(config: BootstrapConfig) -> Result<InfraModule, InfraErro
                 db: dynamodb.clone(),
             },
         )
-        .build();
+        .with_component_parameters::<dynamodb::notification::NotificationPreferenceRepoImpl>(
+            dynamodb::notification::NotificationPreferenceRepoImplParameters {
+                db: dynamodb.clone(),
+            },
+        )
+        .with_component_parameters::<dynamodb::outbox::OutboxRepoImpl>(
+            dynamodb::outbox::OutboxRepoImplParameters {
+                db: dynamodb.clone(),
+            },
+        )
+        .with_component_parameters::<dynamodb::rate_limit::RateLimitStoreImpl>(
+            dynamodb::rate_limit::RateLimitStoreImplParameters {
+                db: dynamodb.clone(),
+            },
+        )
+        .with_component_parameters::<dynamodb::reconfirmation::ReconfirmationRepoImpl>(
+            dynamodb::reconfirmation::ReconfirmationRepoImplParameters {
+                db: dynamodb.clone(),
+            },
+        )
+        .with_component_parameters::<dynamodb::reminder::ReminderRepoImpl>(
+            dynamodb::reminder::ReminderRepoImplParameters {
+                db: dynamodb.clone(),
+            },
+        )
+        .with_component_parameters::<dynamodb::world::WorldRepoImpl>(
+            dynamodb::world::WorldRepoImplParameters {
+                db: dynamodb.clone(),
+            },
+        );
+
+    let builder = match parameters.rate_limit_store {
+        rate_limit::RateLimitStoreKind::Memory => builder
+            .with_component_override::<dyn minibell::rate_limit::RateLimitStore>(Box::new(
+                rate_limit::MemoryRateLimitStore::default(),
+            )),
+        rate_limit::RateLimitStoreKind::DynamoDb => builder,
+    };
+
+    // Sign in as a test member instead of discord, only built with the dev-identity feature
+    #[cfg(feature = "dev-identity")]
+    let builder = {
+        tracing::warn!("Signing in with the dev identity provider");
+        builder.with_component_override::<dyn minibell::member::IdentityProvider>(Box::new(
+            dev_identity::DevIdentityProvider::from_env(),
+        ))
+    };
+
+    let infra = builder.build();
 
     Ok(infra)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_ids;
+
+    #[test]
+    fn parse_ids_skips_blank_entries() {
+        assert_eq!(parse_ids("").unwrap(), Vec::<u64>::new());
+        assert_eq!(parse_ids(" 1, 2 ,,").unwrap(), vec![1, 2]);
+        assert!(parse_ids("1,admin").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use minibell::{
+    member::{MemberId, MemberRepository, RoleId},
+    permission::{Permission, PermissionService, RolePermissions},
+    AccessType, Error,
+};
+use shaku::Component;
+
+/// Permissions granted by the guild roles stored on the member
+#[derive(Component)]
+#[shaku(interface = PermissionService)]
+pub struct RolePermissionServiceImpl {
+    #[shaku(inject)]
+    member_repo: Arc<dyn MemberRepository>,
+
+    role_permissions: RolePermissions,
+    /// Roles allowed to sign in, anyone in the guild if empty
+    required_roles: Vec<RoleId>,
+    /// Discord user ids always granted admin, besides the admin roles
+    admin_user_ids: Vec<MemberId>,
+}
+
+#[async_trait]
+impl PermissionService for RolePermissionServiceImpl {
+    async fn permissions(&self, access_type: &AccessType) -> Result<Vec<Permission>, Error> {
+        match access_type {
+            AccessType::System | AccessType::Admin(_) => Ok(Permission::ALL.to_vec()),
+            AccessType::Member(member_id) if self.admin_user_ids.contains(member_id) => {
+                Ok(Permission::ALL.to_vec())
+            }
+            AccessType::Member(member_id) => {
+                let member = self.member_repo.get_member(*member_id).await?;
+                Ok(self.role_permissions.permissions(&member.roles))
+            }
+            AccessType::ApiKey(_) | AccessType::Guest => Ok(vec![]),
+        }
+    }
+
+    async fn access_type(&self, member_id: MemberId) -> Result<AccessType, Error> {
+        if self.admin_user_ids.contains(&member_id) {
+            return Ok(AccessType::Admin(member_id));
+        }
+
+        let member = self.member_repo.get_member(member_id).await?;
+        Ok(self.access_type_with_roles(member_id, &member.roles))
+    }
+
+    fn access_type_with_roles(&self, member_id: MemberId, roles: &[RoleId]) -> AccessType {
+        if self.admin_user_ids.contains(&member_id)
+            || self
+                .role_permissions
+                .permissions(roles)
+                .contains(&Permission::Admin)
+        {
+            AccessType::Admin(member_id)
+        } else {
+            AccessType::Member(member_id)
+        }
+    }
+
+    fn check_sign_in(&self, roles: &[RoleId]) -> Result<(), Error> {
+        if self.required_roles.is_empty()
+            || self.required_roles.iter().any(|role| roles.contains(role))
+        {
+            Ok(())
+        } else {
+            Err(Error::MissingRequiredRole)
+        }
+    }
+}
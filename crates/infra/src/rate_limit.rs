@@ -0,0 +1,74 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use minibell::{rate_limit::RateLimitStore, Error};
+
+/// Where the rate limit counters live
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RateLimitStoreKind {
+    /// Per process, for local runs with a single instance
+    Memory,
+    /// Shared by every Lambda instance
+    DynamoDb,
+}
+
+impl RateLimitStoreKind {
+    /// Read RATE_LIMIT_STORE, `memory` or `dynamodb`
+    pub(crate) fn from_env(default: Self) -> Self {
+        match std::env::var("RATE_LIMIT_STORE").as_deref() {
+            Ok("memory") => Self::Memory,
+            Ok("dynamodb") => Self::DynamoDb,
+            Ok(_) => panic!("RATE_LIMIT_STORE must be memory or dynamodb"),
+            Err(_) => default,
+        }
+    }
+}
+
+/// Counters kept in memory, dropped when their window ends
+#[derive(Debug, Default)]
+pub struct MemoryRateLimitStore {
+    counters: Mutex<HashMap<String, (DateTime<Utc>, u64)>>,
+}
+
+#[async_trait]
+impl RateLimitStore for MemoryRateLimitStore {
+    async fn hit(&self, key: &str, window_end: DateTime<Utc>) -> Result<u64, Error> {
+        let now = Utc::now();
+        let mut counters = self
+            .counters
+            .lock()
+            .map_err(|_| Error::internal("Rate limit counters are poisoned."))?;
+        counters.retain(|_, (end, _)| *end > now);
+
+        let (end, hits) = counters.entry(key.to_string()).or_insert((window_end, 0));
+        if *end != window_end {
+            *end = window_end;
+            *hits = 0;
+        }
+        *hits += 1;
+
+        Ok(*hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use minibell::rate_limit::RateLimitStore;
+
+    use super::MemoryRateLimitStore;
+
+    #[tokio::test]
+    async fn memory_store() {
+        let store = MemoryRateLimitStore::default();
+        let window_end = Utc::now() + Duration::seconds(60);
+
+        assert_eq!(store.hit("a", window_end).await.unwrap(), 1);
+        assert_eq!(store.hit("a", window_end).await.unwrap(), 2);
+        assert_eq!(store.hit("b", window_end).await.unwrap(), 1);
+
+        let next_window_end = window_end + Duration::seconds(60);
+        assert_eq!(store.hit("a", next_window_end).await.unwrap(), 1);
+    }
+}
@@ -1,48 +1,310 @@
 use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, TimeZone, Utc};
 use hmac::Mac;
 use minibell::{member, Error};
+use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use shaku::Component;
 
+type HmacSha256 = hmac::Hmac<Sha256>;
+
+/// Session signing key, the id is carried in the token
+#[derive(Debug, Clone, Deserialize)]
+pub struct SessionKey {
+    pub id: String,
+    pub secret: String,
+}
+
+/// Prefix of access tokens, never a valid session id
+const ACCESS_TOKEN_PREFIX: &str = "at";
+
+/// Sign session tokens as `session_id.key_id.signature`
+/// and access tokens as `at.payload.key_id.signature`
 #[derive(Debug, Clone, Component)]
 #[shaku(interface = member::MemberSessionSigner)]
 pub struct SessionHmac {
-    secret: String,
+    /// The first key signs, the others are only accepted for verification until retired
+    keys: Vec<SessionKey>,
+}
+
+fn mac(key: &SessionKey, message: &str) -> Result<HmacSha256, Error> {
+    let mut mac = HmacSha256::new_from_slice(key.secret.as_bytes())
+        .map_err(|_| Error::internal("Sign signature failed, secret is invalid."))?;
+    mac.update(message.as_bytes());
+
+    Ok(mac)
+}
+
+fn signature(key: &SessionKey, message: &str) -> Result<String, Error> {
+    Ok(BASE64_URL_SAFE_NO_PAD.encode(mac(key, message)?.finalize().into_bytes()))
 }
 
-impl SessionHmac {
-    fn sign_signature(&self, session_id: &str) -> Result<String, Error> {
-        type HmacSha256 = hmac::Hmac<Sha256>;
+/// Verify in constant time
+fn verify_signature(key: &SessionKey, message: &str, signature: &str) -> Result<(), Error> {
+    let signature = BASE64_URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| Error::InvalidToken)?;
+
+    mac(key, message)?
+        .verify_slice(&signature)
+        .map_err(|_| Error::InvalidToken)
+}
+
+fn signing_key(keys: &[SessionKey]) -> Result<&SessionKey, Error> {
+    keys.first()
+        .ok_or(Error::internal("No session key configured."))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AccessTokenPayload {
+    sub: u64,
+    sid: String,
+    roles: Vec<u64>,
+    exp: i64,
+}
+
+impl From<&member::AccessToken> for AccessTokenPayload {
+    fn from(value: &member::AccessToken) -> Self {
+        Self {
+            sub: value.member_id,
+            sid: value.session_id.clone(),
+            roles: value.roles.clone(),
+            exp: value.expires_at.timestamp(),
+        }
+    }
+}
 
-        let mut mac = HmacSha256::new_from_slice(self.secret.as_bytes())
-            .map_err(|_| Error::internal("Sign signature failed, secret is invalid."))?;
-        mac.update(session_id.as_bytes());
+impl TryFrom<AccessTokenPayload> for member::AccessToken {
+    type Error = Error;
 
-        Ok(BASE64_URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()))
+    fn try_from(value: AccessTokenPayload) -> Result<Self, Self::Error> {
+        Ok(Self {
+            member_id: value.sub,
+            session_id: value.sid,
+            roles: value.roles,
+            expires_at: Utc
+                .timestamp_opt(value.exp, 0)
+                .single()
+                .ok_or(Error::InvalidToken)?,
+        })
     }
 }
 
 impl member::MemberSessionSigner for SessionHmac {
     fn sign(&self, session_id: &str) -> Result<String, Error> {
-        let signature = self.sign_signature(session_id)?;
-        Ok(format!("{}.{}", session_id, signature))
+        let key = signing_key(&self.keys)?;
+        let signature = signature(key, &format!("{}.{}", key.id, session_id))?;
+
+        Ok(format!("{}.{}.{}", session_id, key.id, signature))
     }
 
     fn verify(&self, token: &str) -> Result<String, Error> {
         let parts: Vec<&str> = token.split('.').collect();
-        if parts.len() != 2 {
+        match parts.as_slice() {
+            [session_id, key_id, signature] => {
+                let key = self
+                    .keys
+                    .iter()
+                    .find(|key| key.id == *key_id)
+                    .ok_or(Error::InvalidToken)?;
+                verify_signature(key, &format!("{}.{}", key_id, session_id), signature)?;
+
+                Ok(session_id.to_string())
+            }
+            // Tokens issued before key ids, signed over the session id only
+            [session_id, signature] => self
+                .keys
+                .iter()
+                .find(|key| verify_signature(key, session_id, signature).is_ok())
+                .map(|_| session_id.to_string())
+                .ok_or(Error::InvalidToken),
+            _ => Err(Error::InvalidToken),
+        }
+    }
+
+    fn sign_access_token(&self, access_token: &member::AccessToken) -> Result<String, Error> {
+        let key = signing_key(&self.keys)?;
+        let payload = serde_json::to_vec(&AccessTokenPayload::from(access_token))
+            .map_err(|e| Error::internal(e.to_string()))?;
+        let payload = BASE64_URL_SAFE_NO_PAD.encode(payload);
+        let signature = signature(
+            key,
+            &format!("{}.{}.{}", ACCESS_TOKEN_PREFIX, key.id, payload),
+        )?;
+
+        Ok(format!(
+            "{}.{}.{}.{}",
+            ACCESS_TOKEN_PREFIX, payload, key.id, signature
+        ))
+    }
+
+    fn verify_access_token(
+        &self,
+        token: &str,
+        now: DateTime<Utc>,
+    ) -> Result<member::AccessToken, Error> {
+        let parts: Vec<&str> = token.split('.').collect();
+        let [ACCESS_TOKEN_PREFIX, payload, key_id, signature] = parts.as_slice() else {
+            return Err(Error::InvalidToken);
+        };
+
+        let key = self
+            .keys
+            .iter()
+            .find(|key| key.id == *key_id)
+            .ok_or(Error::InvalidToken)?;
+        verify_signature(
+            key,
+            &format!("{}.{}.{}", ACCESS_TOKEN_PREFIX, key_id, payload),
+            signature,
+        )?;
+
+        let payload = BASE64_URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| Error::InvalidToken)?;
+        let access_token: member::AccessToken =
+            serde_json::from_slice::<AccessTokenPayload>(&payload)
+                .map_err(|_| Error::InvalidToken)?
+                .try_into()?;
+        if access_token.expires_at <= now {
             return Err(Error::InvalidToken);
         }
 
-        let session_id = parts[0];
-        let signature = parts[1];
+        Ok(access_token)
+    }
+}
 
-        self.sign_signature(session_id).and_then(|com_signature| {
-            if com_signature == signature {
-                Ok(session_id.to_string())
-            } else {
-                Err(Error::InvalidToken)
-            }
-        })
+/// Sign Oauth2 states as `expires_at.key_id.signature`, with the session keys
+/// The signature covers the PKCE challenge, only the client holding the verifier can use the state
+#[derive(Debug, Clone, Component)]
+#[shaku(interface = member::OAuth2StateSigner)]
+pub struct OAuth2StateHmac {
+    keys: Vec<SessionKey>,
+}
+
+impl OAuth2StateHmac {
+    fn message(key_id: &str, expires_at: i64, code_challenge: &str) -> String {
+        format!("oauth2.{}.{}.{}", key_id, expires_at, code_challenge)
+    }
+}
+
+impl member::OAuth2StateSigner for OAuth2StateHmac {
+    fn sign(&self, code_challenge: &str, expires_at: DateTime<Utc>) -> Result<String, Error> {
+        let key = signing_key(&self.keys)?;
+        let expires_at = expires_at.timestamp();
+        let signature = signature(key, &Self::message(&key.id, expires_at, code_challenge))?;
+
+        Ok(format!("{}.{}.{}", expires_at, key.id, signature))
+    }
+
+    fn verify(&self, state: &str, code_challenge: &str, now: DateTime<Utc>) -> Result<(), Error> {
+        let parts: Vec<&str> = state.split('.').collect();
+        let [expires_at, key_id, signature] = parts.as_slice() else {
+            return Err(Error::InvalidOAuth2State);
+        };
+
+        let expires_at = expires_at
+            .parse::<i64>()
+            .map_err(|_| Error::InvalidOAuth2State)?;
+        let key = self
+            .keys
+            .iter()
+            .find(|key| key.id == *key_id)
+            .ok_or(Error::InvalidOAuth2State)?;
+        verify_signature(
+            key,
+            &Self::message(key_id, expires_at, code_challenge),
+            signature,
+        )
+        .map_err(|_| Error::InvalidOAuth2State)?;
+
+        if expires_at <= now.timestamp() {
+            return Err(Error::InvalidOAuth2State);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use minibell::member::{AccessToken, MemberSessionSigner, OAuth2StateSigner};
+
+    use super::{OAuth2StateHmac, SessionHmac, SessionKey};
+
+    fn key(id: &str) -> SessionKey {
+        SessionKey {
+            id: id.to_string(),
+            secret: format!("{}-secret", id),
+        }
+    }
+
+    #[test]
+    fn rotate_keys() {
+        let old = SessionHmac {
+            keys: vec![key("old")],
+        };
+        let token = old.sign("session").unwrap();
+
+        // Old key still accepted after the rotation
+        let rotated = SessionHmac {
+            keys: vec![key("new"), key("old")],
+        };
+        assert_eq!(rotated.verify(&token).unwrap(), "session");
+        assert!(rotated.sign("session").unwrap().starts_with("session.new."));
+
+        // Rejected once retired
+        let retired = SessionHmac {
+            keys: vec![key("new")],
+        };
+        assert!(retired.verify(&token).is_err());
+        assert!(retired.verify("session.new.AAAA").is_err());
+    }
+
+    #[test]
+    fn oauth2_state() {
+        let signer = OAuth2StateHmac {
+            keys: vec![key("new")],
+        };
+        let now = Utc::now();
+        let state = signer
+            .sign("challenge", now + Duration::minutes(10))
+            .unwrap();
+
+        assert!(signer.verify(&state, "challenge", now).is_ok());
+        // Another attempt, or a tampered state
+        assert!(signer.verify(&state, "other", now).is_err());
+        assert!(signer
+            .verify(&state.replacen('.', "0.", 1), "challenge", now)
+            .is_err());
+        // Expired
+        assert!(signer
+            .verify(&state, "challenge", now + Duration::minutes(11))
+            .is_err());
+    }
+
+    #[test]
+    fn access_token() {
+        let signer = SessionHmac {
+            keys: vec![key("new")],
+        };
+        let now = Utc::now();
+        let access_token = AccessToken {
+            member_id: 1,
+            session_id: "session".to_string(),
+            roles: vec![2],
+            expires_at: now + Duration::minutes(5),
+        };
+        let token = signer.sign_access_token(&access_token).unwrap();
+
+        let verified = signer.verify_access_token(&token, now).unwrap();
+        assert_eq!(verified.session_id, access_token.session_id);
+        assert_eq!(verified.roles, access_token.roles);
+        // Never accepted as a session token
+        assert!(signer.verify(&token).is_err());
+        assert!(signer
+            .verify_access_token(&token, now + Duration::minutes(6))
+            .is_err());
     }
 }
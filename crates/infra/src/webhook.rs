@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use minibell::{
+    outbox::{OutboxMessage, OutboxSink},
+    Error,
+};
+use serde_json::json;
+use shaku::Component;
+
+/// Post outbox messages as JSON to a webhook
+#[derive(Clone, Component)]
+#[shaku(interface = OutboxSink)]
+pub struct WebhookSinkImpl {
+    reqwest: Arc<reqwest::Client>,
+
+    /// Disabled when not set
+    url: Option<String>,
+}
+
+#[async_trait]
+impl OutboxSink for WebhookSinkImpl {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), Error> {
+        let Some(url) = &self.url else {
+            return Ok(());
+        };
+
+        self.reqwest
+            .post(url)
+            .json(&json!({
+                "id": message.id,
+                "created_at": message.created_at.to_rfc3339(),
+                "event": message.event,
+            }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| Error::upstream(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -8,10 +8,23 @@ export interface Member {
 }
 
 export const load: ServerLoad = async (evt) => {
-  const result = await get<{ authUrl: string; member?: Member }>(evt, {
+  const result = await get<{
+    authUrl: string;
+    state: string;
+    codeVerifier: string;
+    member?: Member;
+  }>(evt, {
     path: "/auth",
     query: { redirect_uri: "http://localhost:5173/auth/callback" },
   });
+  // Kept until discord redirects back to the callback
+  if (!result.member) {
+    evt.cookies.set(
+      "oauth2",
+      JSON.stringify({ state: result.state, codeVerifier: result.codeVerifier }),
+      { path: "/auth/callback", httpOnly: true, sameSite: "lax", maxAge: 600 }
+    );
+  }
 
   return {
     auth: result,
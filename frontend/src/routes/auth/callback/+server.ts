@@ -4,13 +4,23 @@ import { post } from "$lib/fetch";
 
 export const GET: RequestHandler = async (evt) => {
   const code = evt.url.searchParams.get("code");
-  if (!code) return redirect(302, "/");
-  console.log(code);
+  const state = evt.url.searchParams.get("state");
+  const oauth2 = evt.cookies.get("oauth2");
+  evt.cookies.delete("oauth2", { path: "/auth/callback" });
+  if (!code || !state || !oauth2) return redirect(302, "/");
+
+  const { state: expectedState, codeVerifier } = JSON.parse(oauth2);
+  if (state !== expectedState) return redirect(302, "/");
 
   const token = await post<{ token: string }>(
     evt,
     { path: "/auth" },
-    { code, redirectUri: "http://localhost:5173/auth/callback" }
+    {
+      code,
+      redirectUri: "http://localhost:5173/auth/callback",
+      state,
+      codeVerifier,
+    }
   );
   evt.cookies.set("token", token.token, { path: "/" });
 
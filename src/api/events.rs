@@ -50,28 +50,28 @@ mod draft {
         is_private: bool,
     }
 
-    impl Into<draft_event::DraftEventUCInput> for DraftEventInput {
-        fn into(self) -> draft_event::DraftEventUCInput {
+    impl From<DraftEventInput> for draft_event::DraftEventUCInput {
+        fn from(value: DraftEventInput) -> Self {
             draft_event::DraftEventUCInput {
-                kind: match &self.submit_type {
-                    SubmitType::Publish => match self.is_private {
+                kind: match &value.submit_type {
+                    SubmitType::Publish => match value.is_private {
                         true => draft_event::DraftEventUCKind::Private,
                         false => draft_event::DraftEventUCKind::Public,
                     },
                     SubmitType::Save => draft_event::DraftEventUCKind::Draft,
                 },
-                title: self.title.clone(),
-                description: self.description.clone(),
-                slots: self
+                title: value.title.clone(),
+                description: value.description.clone(),
+                slots: value
                     .slots
                     .iter()
                     .map(|s| event::EventSlot {
                         jobs: s.jobs.clone(),
                     })
                     .collect::<Vec<_>>(),
-                start_at: self.start_at,
-                deadline_at: self.deadline_at,
-                duration: Duration::minutes(self.duration),
+                start_at: value.start_at,
+                deadline_at: value.deadline_at,
+                duration: Duration::minutes(value.duration),
             }
         }
     }
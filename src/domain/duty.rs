@@ -48,12 +48,12 @@ impl Eq for Phase {}
 
 impl PartialOrd for Phase {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.progression.partial_cmp(&other.progression)
+        Some(self.cmp(other))
     }
 }
 impl Ord for Phase {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.progression.partial_cmp(&other.progression).unwrap()
     }
 }
 
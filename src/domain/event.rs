@@ -260,10 +260,7 @@ impl Event {
                 }
                 _ => false,
             },
-            EventHost::System => match access_type {
-                AccessType::System => true,
-                _ => false,
-            },
+            EventHost::System => matches!(access_type, AccessType::System),
         }
     }
 
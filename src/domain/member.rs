@@ -44,6 +44,7 @@ pub trait DiscordService {
     async fn get_member(&self, member_id: MemberId) -> Result<Self::DiscordMember, Error>;
 
     /// Into member insert input
+    #[allow(clippy::wrong_self_convention)]
     fn into_member_insert_input(
         &self,
         discord_member: &Self::DiscordMember,
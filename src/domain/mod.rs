@@ -4,6 +4,7 @@ pub mod event;
 pub mod member;
 
 #[derive(Debug, thiserror::Error)]
+#[allow(clippy::enum_variant_names)]
 pub enum Error {
     #[error("internal error: {0}")]
     Internal(String),
@@ -14,21 +14,21 @@ struct DutyCategoryModel {
     duties: Vec<DutyReviewModel>,
 }
 
-impl Into<duty::DutyCategory> for DutyCategoryModel {
-    fn into(self) -> duty::DutyCategory {
+impl From<DutyCategoryModel> for duty::DutyCategory {
+    fn from(value: DutyCategoryModel) -> Self {
         duty::DutyCategory {
-            id: self.id,
-            name: self.name,
+            id: value.id,
+            name: value.name,
         }
     }
 }
 
-impl Into<duty::DutyCategoryWithDutiesReview> for DutyCategoryModel {
-    fn into(self) -> duty::DutyCategoryWithDutiesReview {
+impl From<DutyCategoryModel> for duty::DutyCategoryWithDutiesReview {
+    fn from(value: DutyCategoryModel) -> Self {
         duty::DutyCategoryWithDutiesReview {
-            id: self.id,
-            name: self.name,
-            duties: self.duties.into_iter().map(Into::into).collect(),
+            id: value.id,
+            name: value.name,
+            duties: value.duties.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -40,12 +40,12 @@ struct DutyReviewModel {
     pub short_name: String,
 }
 
-impl Into<duty::DutyReview> for DutyReviewModel {
-    fn into(self) -> duty::DutyReview {
+impl From<DutyReviewModel> for duty::DutyReview {
+    fn from(value: DutyReviewModel) -> Self {
         duty::DutyReview {
-            id: self.id,
-            name: self.name,
-            short_name: self.short_name,
+            id: value.id,
+            name: value.name,
+            short_name: value.short_name,
         }
     }
 }
@@ -56,11 +56,11 @@ struct PhaseModel {
     progression: f64,
 }
 
-impl Into<duty::Phase> for PhaseModel {
-    fn into(self) -> duty::Phase {
+impl From<PhaseModel> for duty::Phase {
+    fn from(value: PhaseModel) -> Self {
         duty::Phase {
-            name: self.name,
-            progression: self.progression,
+            name: value.name,
+            progression: value.progression,
         }
     }
 }
@@ -75,25 +75,25 @@ struct DutyModel {
     phases: Vec<PhaseModel>,
 }
 
-impl Into<duty::DutyReview> for DutyModel {
-    fn into(self) -> duty::DutyReview {
+impl From<DutyModel> for duty::DutyReview {
+    fn from(value: DutyModel) -> Self {
         duty::DutyReview {
-            id: self.id,
-            name: self.name,
-            short_name: self.short_name,
+            id: value.id,
+            name: value.name,
+            short_name: value.short_name,
         }
     }
 }
 
-impl Into<duty::Duty> for DutyModel {
-    fn into(self) -> duty::Duty {
+impl From<DutyModel> for duty::Duty {
+    fn from(value: DutyModel) -> Self {
         duty::Duty {
-            id: self.id,
-            name: self.name,
-            short_name: self.short_name,
-            image_url: self.image_url,
+            id: value.id,
+            name: value.name,
+            short_name: value.short_name,
+            image_url: value.image_url,
 
-            phases: self.phases.into_iter().map(Into::into).collect(),
+            phases: value.phases.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -142,6 +142,6 @@ impl duty::DutyRepo for &DutyRepo {
             .check()?
             .take::<Option<DutyModel>>(0)?
             .map(Into::into)
-            .ok_or_else(|| Error::NotFound)
+            .ok_or(Error::NotFound)
     }
 }
@@ -30,14 +30,14 @@ impl From<event::EventStatus> for EventStatusModel {
     }
 }
 
-impl Into<event::EventStatus> for EventStatusModel {
-    fn into(self) -> event::EventStatus {
-        match self {
-            Self::Draft => event::EventStatus::Draft,
-            Self::Public => event::EventStatus::Public,
-            Self::Private => event::EventStatus::Private,
-            Self::InProgress => event::EventStatus::InProcess,
-            Self::Finished => event::EventStatus::Finished,
+impl From<EventStatusModel> for event::EventStatus {
+    fn from(value: EventStatusModel) -> Self {
+        match value {
+            EventStatusModel::Draft => event::EventStatus::Draft,
+            EventStatusModel::Public => event::EventStatus::Public,
+            EventStatusModel::Private => event::EventStatus::Private,
+            EventStatusModel::InProgress => event::EventStatus::InProcess,
+            EventStatusModel::Finished => event::EventStatus::Finished,
         }
     }
 }
@@ -101,20 +101,20 @@ impl From<&event::Event> for EventModel {
     }
 }
 
-impl Into<event::Event> for EventModel {
-    fn into(self) -> event::Event {
+impl From<EventModel> for event::Event {
+    fn from(value: EventModel) -> Self {
         event::Event {
-            id: self.id.clone(),
+            id: value.id.clone(),
             info: event::EventInfo {
-                title: self.title,
-                description: self.description,
+                title: value.title,
+                description: value.description,
             },
-            status: self.status.into(),
-            host: match self.host {
+            status: value.status.into(),
+            host: match value.host {
                 Some(key) => event::EventHost::Member(key),
                 None => event::EventHost::System,
             },
-            slots: self
+            slots: value
                 .slots
                 .iter()
                 .map(|s| event::EventSlot {
@@ -122,13 +122,13 @@ impl Into<event::Event> for EventModel {
                 })
                 .collect(),
             schedule: event::EventSchedule {
-                start_at: self.start_at,
-                deadline_at: self.deadline_at,
-                duration: chrono::Duration::minutes(self.duration),
+                start_at: value.start_at,
+                deadline_at: value.deadline_at,
+                duration: chrono::Duration::minutes(value.duration),
             },
-            created_at: self.created_at,
-            updated_at: self.updated_at,
-            published_at: self.published_at,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            published_at: value.published_at,
         }
     }
 }
@@ -310,7 +310,7 @@ impl event::EventRepo for &EventRepo {
             .bind(("id", RecordId::from_table_key("event", id)))
             .await?
             .take::<Option<EventModel>>(0)?
-            .ok_or_else(|| Error::NotFound)
+            .ok_or(Error::NotFound)
             .map(Into::into)
     }
 }
@@ -18,15 +18,15 @@ pub(super) struct MemberModel {
     pub updated_at: DateTime<Utc>,
 }
 
-impl Into<member::Member> for MemberModel {
-    fn into(self) -> member::Member {
+impl From<MemberModel> for member::Member {
+    fn from(value: MemberModel) -> Self {
         member::Member {
-            id: self.id,
-            display_name: self.name,
-            avatar: self.avatar,
-            roles: self.roles,
-            joined_at: self.joined_at,
-            updated_at: self.updated_at,
+            id: value.id,
+            display_name: value.name,
+            avatar: value.avatar,
+            roles: value.roles,
+            joined_at: value.joined_at,
+            updated_at: value.updated_at,
         }
     }
 }
@@ -85,7 +85,7 @@ impl member::MemberRepo for &MemberRepo {
             .await?
             .check()?
             .take::<Option<MemberModel>>(0)?
-            .ok_or_else(|| Error::NotFound)
+            .ok_or(Error::NotFound)
             .map(Into::into)
     }
 }
@@ -17,9 +17,9 @@ pub struct SessionToken {
     signature: String,
 }
 
-impl ToString for SessionToken {
-    fn to_string(&self) -> String {
-        format!("{}.{}", self.session_id, self.signature)
+impl std::fmt::Display for SessionToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.session_id, self.signature)
     }
 }
 
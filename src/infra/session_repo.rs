@@ -99,7 +99,7 @@ impl auth::SessionRepo for &SessionRepo {
             .bind(("exp", input.expires_at))
             .await?
             .take::<Option<SessionModel>>(0)?
-            .ok_or_else(|| Error::FailedToInsert)
+            .ok_or(Error::FailedToInsert)
             .and_then(TryInto::try_into)
     }
 
@@ -122,7 +122,7 @@ impl auth::SessionRepo for &SessionRepo {
             .bind(("id", RecordId::from_table_key("session", session_id)))
             .await?
             .take::<Option<SessionModel>>(0)?
-            .ok_or_else(|| Error::NotFound)
+            .ok_or(Error::NotFound)
             .and_then(TryInto::try_into)
     }
 }
@@ -1,3 +1,6 @@
+// Legacy application, kept around while features move into `crates/`.
+#![allow(dead_code)]
+
 use clap::{Parser, Subcommand};
 
 mod api;
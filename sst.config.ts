@@ -35,6 +35,8 @@ export default $config({
         GSI4SK: "string",
       },
       primaryIndex: { hashKey: "PK", rangeKey: "SK" },
+      // Expired sessions are removed by DynamoDB
+      ttl: "ttl",
       globalIndexes: {
         GSI1: { hashKey: "GSI1PK", rangeKey: "GSI1SK" },
         GSI2: { hashKey: "GSI2PK", rangeKey: "GSI2SK" },